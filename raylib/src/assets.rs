@@ -0,0 +1,311 @@
+//! Asset Caching and Hot Reload
+//!
+//! `Assets<T>` loads each path once through a loader function and hands out
+//! reference counted `Handle<T>` keys. Assets stay loaded while a handle
+//! exists and are unloaded (dropped) by `update()` once unreferenced:
+//!
+//! ```ignore
+//! let mut textures = Assets::new(|path| window.load_texture(path.to_string_lossy()))
+//!     .hot_reload(cfg!(debug_assertions));
+//! let player = textures.load("player.png")?;
+//! // Every frame, between frames
+//! textures.update();
+//! d.draw_texture(&textures[&player], 10, 10, colors::WHITE);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::Index;
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+use std::time::SystemTime;
+
+use crate::{Error, Result};
+
+/// Reference counted key for an asset in an `Assets<T>` store
+pub struct Handle<T> {
+    index: usize,
+    refs: Rc<()>,
+    _asset: PhantomData<fn() -> T>,
+}
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Handle<T> {
+        Handle {
+            index: self.index,
+            refs: self.refs.clone(),
+            _asset: PhantomData,
+        }
+    }
+}
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Handle<T>) -> bool {
+        Rc::ptr_eq(&self.refs, &other.refs)
+    }
+}
+impl<T> Eq for Handle<T> {}
+impl<T> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Rc::as_ptr(&self.refs).hash(state)
+    }
+}
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Handle")
+            .field("index", &self.index)
+            .field("refs", &Rc::strong_count(&self.refs))
+            .finish()
+    }
+}
+
+/// Change reported by `Assets::update()`
+#[derive(Debug)]
+pub enum AssetEvent {
+    /// Asset file changed and was reloaded in place
+    Reloaded(PathBuf),
+    /// Asset file changed but failed to load, the previous asset is kept
+    ReloadFailed(PathBuf, Error),
+    /// Asset had no handles left and was unloaded
+    Unloaded(PathBuf),
+}
+
+struct Entry<T> {
+    path: PathBuf,
+    asset: T,
+    refs: Weak<()>,
+    modified: Option<SystemTime>,
+}
+
+type Loader<'l, T> = Box<dyn FnMut(&Path) -> Result<T> + 'l>;
+
+/// Asset store, deduplicates loads by path
+pub struct Assets<'l, T> {
+    loader: Loader<'l, T>,
+    entries: Vec<Option<Entry<T>>>,
+    indices: HashMap<PathBuf, usize>,
+    hot_reload: bool,
+}
+impl<'l, T> Assets<'l, T> {
+    /// Create a store that loads assets with `loader`
+    pub fn new<F>(loader: F) -> Assets<'l, T>
+    where
+        F: FnMut(&Path) -> Result<T> + 'l,
+    {
+        Assets {
+            loader: Box::new(loader),
+            entries: Vec::new(),
+            indices: HashMap::new(),
+            hot_reload: false,
+        }
+    }
+    /// Set reloading of changed files in `update()` (for development)
+    pub fn hot_reload(mut self, hot_reload: bool) -> Assets<'l, T> {
+        self.hot_reload = hot_reload;
+        self
+    }
+    /// Load asset from file, or get a new handle to it if already loaded
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<Handle<T>> {
+        let path = fs::canonicalize(path.as_ref()).unwrap_or_else(|_| path.as_ref().to_owned());
+        if let Some(&index) = self.indices.get(&path) {
+            let entry = self.entries[index].as_mut().unwrap();
+            // Revive an unreferenced asset that hasn't been unloaded yet
+            let refs = entry.refs.upgrade().unwrap_or_else(|| {
+                let refs = Rc::new(());
+                entry.refs = Rc::downgrade(&refs);
+                refs
+            });
+            return Ok(Handle {
+                index: index,
+                refs: refs,
+                _asset: PhantomData,
+            });
+        }
+
+        let asset = (self.loader)(&path)?;
+        let refs = Rc::new(());
+        let entry = Entry {
+            modified: modified_time(&path),
+            path: path.clone(),
+            asset: asset,
+            refs: Rc::downgrade(&refs),
+        };
+        let index = match self.entries.iter().position(Option::is_none) {
+            Some(index) => {
+                self.entries[index] = Some(entry);
+                index
+            }
+            None => {
+                self.entries.push(Some(entry));
+                self.entries.len() - 1
+            }
+        };
+        self.indices.insert(path, index);
+        Ok(Handle {
+            index: index,
+            refs: refs,
+            _asset: PhantomData,
+        })
+    }
+    /// Get asset, `None` if the handle belongs to another store
+    pub fn get(&self, handle: &Handle<T>) -> Option<&T> {
+        self.entry(handle).map(|entry| &entry.asset)
+    }
+    /// Get asset mutably, `None` if the handle belongs to another store
+    pub fn get_mut(&mut self, handle: &Handle<T>) -> Option<&mut T> {
+        self.entry(handle)?;
+        self.entries[handle.index]
+            .as_mut()
+            .map(|entry| &mut entry.asset)
+    }
+    /// Get the path an asset was loaded from
+    pub fn path(&self, handle: &Handle<T>) -> Option<&Path> {
+        self.entry(handle).map(|entry| entry.path.as_path())
+    }
+    /// Number of loaded assets, including unreferenced ones not unloaded yet
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+    /// Check if no assets are loaded
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+    /// Unload unreferenced assets and reload changed files if hot reload is set
+    ///
+    /// NOTE: Call between frames, no asset borrowed from the store can be alive
+    pub fn update(&mut self) -> Vec<AssetEvent> {
+        let mut events = Vec::new();
+        for slot in self.entries.iter_mut() {
+            let unreferenced = match slot {
+                Some(entry) => entry.refs.strong_count() == 0,
+                None => continue,
+            };
+            if unreferenced {
+                let entry = slot.take().unwrap();
+                self.indices.remove(&entry.path);
+                events.push(AssetEvent::Unloaded(entry.path));
+                continue;
+            }
+
+            let entry = slot.as_mut().unwrap();
+            if !self.hot_reload {
+                continue;
+            }
+            let modified = modified_time(&entry.path);
+            if modified.is_none() || modified == entry.modified {
+                continue;
+            }
+            // Remember the new time either way so a broken file isn't retried every frame
+            entry.modified = modified;
+            match (self.loader)(&entry.path) {
+                Ok(asset) => {
+                    entry.asset = asset;
+                    events.push(AssetEvent::Reloaded(entry.path.clone()));
+                }
+                Err(error) => events.push(AssetEvent::ReloadFailed(entry.path.clone(), error)),
+            }
+        }
+        events
+    }
+
+    fn entry(&self, handle: &Handle<T>) -> Option<&Entry<T>> {
+        self.entries
+            .get(handle.index)?
+            .as_ref()
+            .filter(|entry| entry.refs.as_ptr() == Rc::as_ptr(&handle.refs))
+    }
+}
+impl<'a, 'l, T> Index<&'a Handle<T>> for Assets<'l, T> {
+    type Output = T;
+
+    /// NOTE: Panics if the handle belongs to another store
+    fn index(&self, handle: &'a Handle<T>) -> &T {
+        self.get(handle)
+            .expect("handle belongs to another asset store")
+    }
+}
+impl<'l, T> fmt::Debug for Assets<'l, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let paths: Vec<&Path> = self.indices.keys().map(PathBuf::as_path).collect();
+        f.debug_struct("Assets")
+            .field("paths", &paths)
+            .field("hot_reload", &self.hot_reload)
+            .finish()
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::fs::File;
+    use std::time::Duration;
+
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("raylib-assets-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn read(path: &Path) -> Result<String> {
+        fs::read_to_string(path).map_err(|e| Error::LoadFailed(e.to_string()))
+    }
+
+    #[test]
+    fn dedupe_and_unload() {
+        let path = temp_file("dedupe.txt", "hello");
+        let loads = Cell::new(0);
+        let mut assets = Assets::new(|path: &Path| {
+            loads.set(loads.get() + 1);
+            read(path)
+        });
+        let a = assets.load(&path).unwrap();
+        let b = assets.load(&path).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(assets[&a], "hello");
+        assert!(assets.load(path.with_extension("missing")).is_err());
+
+        drop(a);
+        assert!(assets.update().is_empty());
+        drop(b);
+        match assets.update().as_slice() {
+            [AssetEvent::Unloaded(unloaded)] => assert!(unloaded.ends_with("dedupe.txt")),
+            events => panic!("unexpected events {:?}", events),
+        }
+        assert!(assets.is_empty());
+
+        let c = assets.load(&path).unwrap();
+        assert_eq!(assets.get(&c).map(String::as_str), Some("hello"));
+        assert_eq!(loads.get(), 3);
+    }
+
+    #[test]
+    fn hot_reload_in_place() {
+        let path = temp_file("reload.txt", "one");
+        let mut assets = Assets::new(read).hot_reload(true);
+        let handle = assets.load(&path).unwrap();
+        assert!(assets.update().is_empty());
+
+        fs::write(&path, "two").unwrap();
+        let later = SystemTime::now() + Duration::from_secs(5);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        match assets.update().as_slice() {
+            [AssetEvent::Reloaded(_)] => {}
+            events => panic!("unexpected events {:?}", events),
+        }
+        assert_eq!(assets[&handle], "two");
+        assert!(assets.update().is_empty());
+    }
+}
@@ -0,0 +1,470 @@
+//! Texture Atlas Packing
+//!
+//! Packs named images into one atlas image with a MaxRects bin packer
+//! (best short side fit):
+//!
+//! ```ignore
+//! let options = AtlasOptions::new(1024, 1024).padding(2).extrude(1).trim(true);
+//! let atlas = pack_atlas(vec![("player", &player), ("coin", &coin)], &options)?;
+//! export_image("atlas.png", &atlas.image)?;
+//! let json = serde_json::to_string(&atlas.sprites)?;
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::textures::load_image_ex;
+use crate::{Color, Error, Image, Rectangle, Result, Vector2};
+
+/// Atlas packing settings
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AtlasOptions {
+    /// Maximum atlas width
+    pub max_width: i32,
+    /// Maximum atlas height
+    pub max_height: i32,
+    /// Empty pixels between sprites
+    pub padding: i32,
+    /// Pixels of sprite edge repeated around each sprite, avoids filtering seams
+    pub extrude: i32,
+    /// Allow sprites to be stored rotated 90 degrees clockwise
+    pub allow_rotation: bool,
+    /// Trim fully transparent borders, keeping the original offset
+    pub trim: bool,
+    /// Round atlas size up to a power of two
+    pub power_of_two: bool,
+}
+impl AtlasOptions {
+    pub fn new(max_width: i32, max_height: i32) -> AtlasOptions {
+        AtlasOptions {
+            max_width: max_width,
+            max_height: max_height,
+            padding: 0,
+            extrude: 0,
+            allow_rotation: false,
+            trim: false,
+            power_of_two: false,
+        }
+    }
+    /// Set empty pixels between sprites
+    pub fn padding(mut self, padding: i32) -> AtlasOptions {
+        self.padding = padding;
+        self
+    }
+    /// Set pixels of sprite edge repeated around each sprite
+    pub fn extrude(mut self, extrude: i32) -> AtlasOptions {
+        self.extrude = extrude;
+        self
+    }
+    /// Allow sprites to be stored rotated
+    pub fn allow_rotation(mut self, allow_rotation: bool) -> AtlasOptions {
+        self.allow_rotation = allow_rotation;
+        self
+    }
+    /// Trim fully transparent borders
+    pub fn trim(mut self, trim: bool) -> AtlasOptions {
+        self.trim = trim;
+        self
+    }
+    /// Round atlas size up to a power of two
+    pub fn power_of_two(mut self, power_of_two: bool) -> AtlasOptions {
+        self.power_of_two = power_of_two;
+        self
+    }
+}
+
+/// Sprite placement in an atlas
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AtlasSprite {
+    /// Sprite area in the atlas, excluding padding and extrusion
+    ///
+    /// NOTE: Width and height are swapped for rotated sprites
+    pub rec: Rectangle,
+    /// Sprite is stored rotated 90 degrees clockwise
+    pub rotated: bool,
+    /// Position of the trimmed area within the original image
+    pub offset: Vector2,
+    /// Size of the original image, before trimming
+    pub source_size: Vector2,
+}
+
+/// Packed atlas image and sprite placements by name
+#[derive(Debug)]
+pub struct Atlas {
+    pub image: Image,
+    pub sprites: BTreeMap<String, AtlasSprite>,
+}
+
+/// Pack named images into an R8G8B8A8 atlas image
+pub fn pack_atlas<'a, S, I>(images: I, options: &AtlasOptions) -> Result<Atlas>
+where
+    S: Into<String>,
+    I: IntoIterator<Item = (S, &'a Image)>,
+{
+    let mut sources = Vec::new();
+    for (name, image) in images {
+        let name = name.into();
+        if sources.iter().any(|s: &Source| s.name == name) {
+            return Err(Error::InvalidData(format!(
+                "duplicate sprite name `{}`",
+                name
+            )));
+        }
+        sources.push(Source::new(name, image, options.trim)?);
+    }
+
+    // Place the largest sprites first
+    let mut order: Vec<usize> = (0..sources.len()).collect();
+    order.sort_by_key(|&i| {
+        let s = &sources[i];
+        (-s.width.max(s.height), -(s.width * s.height))
+    });
+
+    let border = 2 * options.extrude + options.padding;
+    let mut packer = MaxRects::new(
+        options.max_width + options.padding,
+        options.max_height + options.padding,
+    );
+    let mut placements = vec![None; sources.len()];
+    for i in order {
+        let s = &sources[i];
+        let placement = packer
+            .insert(s.width + border, s.height + border, options.allow_rotation)
+            .ok_or_else(|| {
+                Error::InvalidData(format!(
+                    "sprite `{}` does not fit in a {}x{} atlas",
+                    s.name, options.max_width, options.max_height
+                ))
+            })?;
+        placements[i] = Some(placement);
+    }
+
+    let (mut width, mut height) = (1, 1);
+    for rect in placements.iter().flatten() {
+        width = width.max(rect.x + rect.width - options.padding);
+        height = height.max(rect.y + rect.height - options.padding);
+    }
+    if options.power_of_two {
+        width = (width as u32).next_power_of_two() as i32;
+        height = (height as u32).next_power_of_two() as i32;
+    }
+
+    let mut pixels = vec![Color::new(0, 0, 0, 0); (width * height) as usize];
+    let mut sprites = BTreeMap::new();
+    for (source, placement) in sources.iter().zip(placements) {
+        let placement = placement.expect("every sprite is placed");
+        let (w, h) = if placement.rotated {
+            (source.height, source.width)
+        } else {
+            (source.width, source.height)
+        };
+        // Clamped sampling repeats the sprite edge into the extruded border
+        let e = options.extrude;
+        for dy in 0..h + 2 * e {
+            for dx in 0..w + 2 * e {
+                let (x, y) = ((dx - e).clamp(0, w - 1), (dy - e).clamp(0, h - 1));
+                let color = if placement.rotated {
+                    source.get(y, source.height - 1 - x)
+                } else {
+                    source.get(x, y)
+                };
+                pixels[((placement.y + dy) * width + placement.x + dx) as usize] = color;
+            }
+        }
+        let sprite = AtlasSprite {
+            rec: Rectangle::new(
+                (placement.x + e) as f32,
+                (placement.y + e) as f32,
+                w as f32,
+                h as f32,
+            ),
+            rotated: placement.rotated,
+            offset: Vector2::new(source.offset_x as f32, source.offset_y as f32),
+            source_size: Vector2::new(source.source_width as f32, source.source_height as f32),
+        };
+        sprites.insert(source.name.clone(), sprite);
+    }
+
+    Ok(Atlas {
+        image: load_image_ex(&pixels, width, height)?,
+        sprites: sprites,
+    })
+}
+
+// Source image decoded to colors, cropped to its trimmed area
+struct Source {
+    name: String,
+    colors: Vec<Color>,
+    source_width: i32,
+    source_height: i32,
+    offset_x: i32,
+    offset_y: i32,
+    width: i32,
+    height: i32,
+}
+impl Source {
+    fn new(name: String, image: &Image, trim: bool) -> Result<Source> {
+        let colors = image.get_colors()?;
+        let (source_width, source_height) = (image.width(), image.height());
+        let (mut x0, mut y0, mut x1, mut y1) = (0, 0, source_width, source_height);
+        if trim {
+            let opaque = |x: i32, y: i32| colors[(y * source_width + x) as usize].a != 0;
+            let (mut min_x, mut min_y, mut max_x, mut max_y) = (source_width, source_height, 0, 0);
+            for y in 0..source_height {
+                for x in 0..source_width {
+                    if opaque(x, y) {
+                        min_x = min_x.min(x);
+                        min_y = min_y.min(y);
+                        max_x = max_x.max(x + 1);
+                        max_y = max_y.max(y + 1);
+                    }
+                }
+            }
+            // Fully transparent images keep a single pixel
+            if min_x < max_x {
+                x0 = min_x;
+                y0 = min_y;
+                x1 = max_x;
+                y1 = max_y;
+            } else {
+                x1 = 1.min(source_width);
+                y1 = 1.min(source_height);
+            }
+        }
+        Ok(Source {
+            name: name,
+            colors: colors,
+            source_width: source_width,
+            source_height: source_height,
+            offset_x: x0,
+            offset_y: y0,
+            width: x1 - x0,
+            height: y1 - y0,
+        })
+    }
+    // Color at (x, y) of the trimmed area
+    fn get(&self, x: i32, y: i32) -> Color {
+        let index = (y + self.offset_y) * self.source_width + x + self.offset_x;
+        self.colors[index as usize]
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    rotated: bool,
+}
+impl Rect {
+    fn contains(&self, other: &Rect) -> bool {
+        other.x >= self.x
+            && other.y >= self.y
+            && other.x + other.width <= self.x + self.width
+            && other.y + other.height <= self.y + self.height
+    }
+    fn intersects(&self, other: &Rect) -> bool {
+        other.x < self.x + self.width
+            && other.x + other.width > self.x
+            && other.y < self.y + self.height
+            && other.y + other.height > self.y
+    }
+}
+
+// MaxRects bin, tracking maximal free rectangles
+struct MaxRects {
+    free: Vec<Rect>,
+}
+impl MaxRects {
+    fn new(width: i32, height: i32) -> MaxRects {
+        MaxRects {
+            free: vec![Rect {
+                x: 0,
+                y: 0,
+                width: width,
+                height: height,
+                rotated: false,
+            }],
+        }
+    }
+    // Place a rectangle using best short side fit, returns the placed area
+    fn insert(&mut self, width: i32, height: i32, allow_rotation: bool) -> Option<Rect> {
+        let mut best: Option<((i32, i32), Rect)> = None;
+        let mut consider = |free: &Rect, w: i32, h: i32, rotated: bool| {
+            if w <= free.width && h <= free.height {
+                let (short, long) = {
+                    let (dw, dh) = (free.width - w, free.height - h);
+                    (dw.min(dh), dw.max(dh))
+                };
+                if best.map_or(true, |(score, _)| (short, long) < score) {
+                    best = Some((
+                        (short, long),
+                        Rect {
+                            x: free.x,
+                            y: free.y,
+                            width: w,
+                            height: h,
+                            rotated: rotated,
+                        },
+                    ));
+                }
+            }
+        };
+        for free in &self.free {
+            consider(free, width, height, false);
+            if allow_rotation && width != height {
+                consider(free, height, width, true);
+            }
+        }
+        let (_, used) = best?;
+        self.split(&used);
+        Some(used)
+    }
+    fn split(&mut self, used: &Rect) {
+        let mut next = Vec::with_capacity(self.free.len() + 4);
+        for free in &self.free {
+            if !free.intersects(used) {
+                next.push(*free);
+                continue;
+            }
+            if used.x > free.x {
+                next.push(Rect {
+                    width: used.x - free.x,
+                    ..*free
+                });
+            }
+            if used.x + used.width < free.x + free.width {
+                next.push(Rect {
+                    x: used.x + used.width,
+                    width: free.x + free.width - (used.x + used.width),
+                    ..*free
+                });
+            }
+            if used.y > free.y {
+                next.push(Rect {
+                    height: used.y - free.y,
+                    ..*free
+                });
+            }
+            if used.y + used.height < free.y + free.height {
+                next.push(Rect {
+                    y: used.y + used.height,
+                    height: free.y + free.height - (used.y + used.height),
+                    ..*free
+                });
+            }
+        }
+        // Drop free rectangles contained in another
+        let mut pruned: Vec<Rect> = Vec::with_capacity(next.len());
+        for (i, rect) in next.iter().enumerate() {
+            let redundant = next
+                .iter()
+                .enumerate()
+                .any(|(j, other)| i != j && other.contains(rect) && (rect != other || j < i));
+            if !redundant {
+                pruned.push(*rect);
+            }
+        }
+        self.free = pruned;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: i32, height: i32, color: Color) -> Image {
+        load_image_ex(&vec![color; (width * height) as usize], width, height).unwrap()
+    }
+
+    fn overlaps(a: &Rectangle, b: &Rectangle) -> bool {
+        a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+    }
+
+    #[test]
+    fn sprites_do_not_overlap() {
+        let red = solid(10, 6, Color::new(255, 0, 0, 255));
+        let green = solid(4, 4, Color::new(0, 255, 0, 255));
+        let blue = solid(7, 3, Color::new(0, 0, 255, 255));
+        let options = AtlasOptions::new(16, 16).padding(1);
+        let atlas = pack_atlas(
+            vec![("red", &red), ("green", &green), ("blue", &blue)],
+            &options,
+        )
+        .unwrap();
+        let recs: Vec<Rectangle> = atlas.sprites.values().map(|s| s.rec).collect();
+        for (i, a) in recs.iter().enumerate() {
+            assert!(a.x + a.width <= atlas.image.width() as f32);
+            assert!(a.y + a.height <= atlas.image.height() as f32);
+            for b in &recs[i + 1..] {
+                let padded = Rectangle::new(b.x - 1.0, b.y - 1.0, b.width + 2.0, b.height + 2.0);
+                assert!(!overlaps(a, &padded), "{:?} touches {:?}", a, b);
+            }
+        }
+        let green_rec = atlas.sprites["green"].rec;
+        assert_eq!(
+            atlas
+                .image
+                .get_pixel(green_rec.x as i32 + 3, green_rec.y as i32 + 3)
+                .unwrap(),
+            Color::new(0, 255, 0, 255)
+        );
+
+        let too_small = AtlasOptions::new(8, 8);
+        assert!(pack_atlas(vec![("red", &red)], &too_small).is_err());
+        assert!(pack_atlas(vec![("a", &green), ("a", &green)], &options).is_err());
+    }
+
+    #[test]
+    fn trim_keeps_offset_and_extrudes_edges() {
+        let mut colors = vec![Color::new(0, 0, 0, 0); 8 * 8];
+        colors[2 * 8 + 3] = Color::new(255, 255, 255, 255);
+        colors[4 * 8 + 5] = Color::new(255, 255, 255, 255);
+        let image = load_image_ex(&colors, 8, 8).unwrap();
+        let options = AtlasOptions::new(32, 32).extrude(2).trim(true);
+        let atlas = pack_atlas(vec![("dots", &image)], &options).unwrap();
+
+        let sprite = atlas.sprites["dots"];
+        assert_eq!(sprite.rec, Rectangle::new(2.0, 2.0, 3.0, 3.0));
+        assert_eq!(sprite.offset, Vector2::new(3.0, 2.0));
+        assert_eq!(sprite.source_size, Vector2::new(8.0, 8.0));
+        assert_eq!((atlas.image.width(), atlas.image.height()), (7, 7));
+        // Top-left pixel of the sprite repeated into the corner
+        assert_eq!(
+            atlas.image.get_pixel(0, 0).unwrap(),
+            Color::new(255, 255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn rotation_fits_tall_sprite() {
+        let mut colors = vec![Color::new(0, 0, 255, 255); 2 * 8];
+        colors[0] = Color::new(255, 0, 0, 255);
+        let tall = load_image_ex(&colors, 2, 8).unwrap();
+        assert!(pack_atlas(vec![("tall", &tall)], &AtlasOptions::new(8, 2)).is_err());
+
+        let options = AtlasOptions::new(8, 2).allow_rotation(true);
+        let atlas = pack_atlas(vec![("tall", &tall)], &options).unwrap();
+        let sprite = atlas.sprites["tall"];
+        assert!(sprite.rotated);
+        assert_eq!(sprite.rec, Rectangle::new(0.0, 0.0, 8.0, 2.0));
+        // Clockwise rotation moves the top-left pixel to the top-right
+        assert_eq!(
+            atlas.image.get_pixel(7, 0).unwrap(),
+            Color::new(255, 0, 0, 255)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sprites_serialize_to_json() {
+        let image = solid(2, 2, Color::new(255, 255, 255, 255));
+        let atlas = pack_atlas(vec![("dot", &image)], &AtlasOptions::new(4, 4)).unwrap();
+        let json = serde_json::to_string(&atlas.sprites).unwrap();
+        assert!(json.starts_with(r#"{"dot":{"rec":{"x":0.0"#));
+        let sprites: BTreeMap<String, AtlasSprite> = serde_json::from_str(&json).unwrap();
+        assert_eq!(sprites, atlas.sprites);
+    }
+}
@@ -1 +1,58 @@
 //! Audio Loading and Playing Functions
+
+use std::ffi::CString;
+use std::os::raw::c_void;
+
+use crate::{raw, Error, Result, Wave};
+
+//------------------------------------------------------------------------------
+// Wave loading functions
+//------------------------------------------------------------------------------
+
+/// Load wave data from file
+pub fn load_wave<S: Into<String>>(file_name: S) -> Result<Wave> {
+    let file_name = file_name.into();
+    let raw_file_name = CString::new(file_name.clone()).unwrap();
+    let raw_wave = unsafe { raw::LoadWave(raw_file_name.as_ptr()) };
+    wave_or_error(raw_wave, file_name)
+}
+/// Load wave data from 16 bit interleaved samples
+pub fn load_wave_ex(samples: &[i16], sample_rate: u32, channels: u32) -> Result<Wave> {
+    if channels == 0 || samples.len() % channels as usize != 0 {
+        return Err(Error::InvalidData(format!(
+            "{} samples do not divide into {} channels",
+            samples.len(),
+            channels
+        )));
+    }
+    let raw_wave = unsafe {
+        raw::LoadWaveEx(
+            samples.as_ptr() as *mut c_void,
+            (samples.len() / channels as usize) as i32,
+            sample_rate as i32,
+            16,
+            channels as i32,
+        )
+    };
+    wave_or_error(raw_wave, String::from("<samples>"))
+}
+
+#[cfg(feature = "decode")]
+impl Wave {
+    /// Load wave from file data in memory, `ext` is the file extension (`"ogg"`)
+    ///
+    /// NOTE: Decodes WAV, OGG, FLAC and MP3 data to 16 bit samples
+    pub fn from_memory(data: &[u8], ext: &str) -> Result<Wave> {
+        let decoded = crate::decode::decode_wave(data, ext)?;
+        load_wave_ex(&decoded.samples, decoded.sample_rate, decoded.channels)
+    }
+}
+
+// raylib signals load failure with a null data pointer
+fn wave_or_error(raw_wave: raw::Wave, source: String) -> Result<Wave> {
+    if raw_wave.data.is_null() {
+        Err(Error::LoadFailed(source))
+    } else {
+        Ok(Wave::from_raw(raw_wave))
+    }
+}
@@ -0,0 +1,717 @@
+//! Software Image Drawing Functions
+//!
+//! Pure Rust drawing into an [`Image`](../struct.Image.html), works without a
+//! window or GPU. All drawing is alpha blended and clipped to the image.
+
+use std::fmt;
+
+use crate::{Color, Image, PixelFormat, Rectangle, Result, Vector2};
+
+/// Default bitmap font base size, glyphs are 5x7 pixels in a 6x8 cell
+pub const CANVAS_FONT_SIZE: i32 = 8;
+
+/// Software canvas borrowing a R8G8B8A8 `Image`
+pub struct ImageCanvas<'a> {
+    pixels: &'a mut [Color],
+    width: i32,
+    height: i32,
+}
+impl<'a> ImageCanvas<'a> {
+    /// Borrow image for drawing
+    ///
+    /// NOTE: Image must be `UncompressedR8g8b8a8`, see `Image::set_format()`
+    pub fn new(image: &'a mut Image) -> Result<ImageCanvas<'a>> {
+        let (width, height) = (image.width(), image.height());
+        Ok(ImageCanvas {
+            pixels: image.pixels_mut()?,
+            width: width,
+            height: height,
+        })
+    }
+    /// Canvas width
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+    /// Canvas height
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    //--------------------------------------------------------------------------
+    // Basic shapes drawing functions
+    //--------------------------------------------------------------------------
+
+    /// Set background color (canvas clear color)
+    pub fn clear_background(&mut self, color: Color) {
+        for pixel in self.pixels.iter_mut() {
+            *pixel = color;
+        }
+    }
+    /// Draw a pixel
+    pub fn draw_pixel(&mut self, pos_x: i32, pos_y: i32, color: Color) {
+        if pos_x >= 0 && pos_y >= 0 && pos_x < self.width && pos_y < self.height {
+            let pixel = &mut self.pixels[(pos_y * self.width + pos_x) as usize];
+            *pixel = alpha_blend(*pixel, color);
+        }
+    }
+    /// Draw a pixel (Vector version)
+    pub fn draw_pixel_v(&mut self, position: Vector2, color: Color) {
+        self.draw_pixel(position.x.floor() as i32, position.y.floor() as i32, color)
+    }
+    /// Draw a line
+    pub fn draw_line(
+        &mut self,
+        start_pos_x: i32,
+        start_pos_y: i32,
+        end_pos_x: i32,
+        end_pos_y: i32,
+        color: Color,
+    ) {
+        let (start_pos_x, start_pos_y, end_pos_x, end_pos_y) =
+            match self.clip_line(start_pos_x, start_pos_y, end_pos_x, end_pos_y) {
+                Some(line) => line,
+                None => return,
+            };
+        // Bresenham's line algorithm
+        let (mut x, mut y) = (start_pos_x, start_pos_y);
+        let dx = (end_pos_x - start_pos_x).abs();
+        let dy = -(end_pos_y - start_pos_y).abs();
+        let sx = if start_pos_x < end_pos_x { 1 } else { -1 };
+        let sy = if start_pos_y < end_pos_y { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            self.draw_pixel(x, y, color);
+            if x == end_pos_x && y == end_pos_y {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+    /// Draw a line (Vector version)
+    pub fn draw_line_v(&mut self, start_pos: Vector2, end_pos: Vector2, color: Color) {
+        self.draw_line(
+            start_pos.x.floor() as i32,
+            start_pos.y.floor() as i32,
+            end_pos.x.floor() as i32,
+            end_pos.y.floor() as i32,
+            color,
+        )
+    }
+    /// Draw a color-filled circle
+    pub fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        let (center_x, center_y) = (i64::from(center_x), i64::from(center_y));
+        for (y, half_width) in self.circle_rows(center_y, radius) {
+            self.draw_span(
+                center_x.saturating_sub(half_width),
+                center_x.saturating_add(half_width),
+                y,
+                color,
+            );
+        }
+    }
+    /// Draw a color-filled circle (Vector version)
+    pub fn draw_circle_v(&mut self, center: Vector2, radius: f32, color: Color) {
+        self.draw_circle(
+            center.x.floor() as i32,
+            center.y.floor() as i32,
+            radius,
+            color,
+        )
+    }
+    /// Draw circle outline
+    ///
+    /// NOTE: Draws the edge pixels of `draw_circle()` with the same radius
+    pub fn draw_circle_lines(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        let (center_x, center_y) = (i64::from(center_x), i64::from(center_y));
+        let radius_f64 = f64::from(radius);
+        for (y, half_width) in self.circle_rows(center_y, radius) {
+            // Pixels with a neighbor above or below outside the circle
+            let dy = y - center_y;
+            let inner = circle_half_width(radius_f64, dy - 1)
+                .min(circle_half_width(radius_f64, dy + 1))
+                .saturating_add(1)
+                .clamp(0, half_width);
+            if inner == 0 {
+                self.draw_span(
+                    center_x.saturating_sub(half_width),
+                    center_x.saturating_add(half_width),
+                    y,
+                    color,
+                );
+            } else {
+                self.draw_span(
+                    center_x.saturating_sub(half_width),
+                    center_x.saturating_sub(inner),
+                    y,
+                    color,
+                );
+                self.draw_span(
+                    center_x.saturating_add(inner),
+                    center_x.saturating_add(half_width),
+                    y,
+                    color,
+                );
+            }
+        }
+    }
+    /// Draw a color-filled rectangle
+    pub fn draw_rectangle(
+        &mut self,
+        pos_x: i32,
+        pos_y: i32,
+        width: i32,
+        height: i32,
+        color: Color,
+    ) {
+        let x0 = pos_x.max(0);
+        let y0 = pos_y.max(0);
+        let x1 = pos_x.saturating_add(width).min(self.width);
+        let y1 = pos_y.saturating_add(height).min(self.height);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                self.draw_pixel(x, y, color);
+            }
+        }
+    }
+    /// Draw a color-filled rectangle (Vector version)
+    pub fn draw_rectangle_v(&mut self, position: Vector2, size: Vector2, color: Color) {
+        self.draw_rectangle_rec(
+            Rectangle::new(position.x, position.y, size.x, size.y),
+            color,
+        )
+    }
+    /// Draw a color-filled rectangle
+    pub fn draw_rectangle_rec(&mut self, rec: Rectangle, color: Color) {
+        self.draw_rectangle(
+            rec.x.round() as i32,
+            rec.y.round() as i32,
+            rec.width.round() as i32,
+            rec.height.round() as i32,
+            color,
+        )
+    }
+    /// Draw rectangle outline
+    pub fn draw_rectangle_lines(
+        &mut self,
+        pos_x: i32,
+        pos_y: i32,
+        width: i32,
+        height: i32,
+        color: Color,
+    ) {
+        if width <= 0 || height <= 0 {
+            return;
+        }
+        let right = pos_x.saturating_add(width - 1);
+        let bottom = pos_y.saturating_add(height - 1);
+        self.draw_rectangle(pos_x, pos_y, width, 1, color);
+        if height > 1 {
+            self.draw_rectangle(pos_x, bottom, width, 1, color);
+        }
+        if height > 2 {
+            let top = pos_y.saturating_add(1);
+            self.draw_rectangle(pos_x, top, 1, height - 2, color);
+            if width > 1 {
+                self.draw_rectangle(right, top, 1, height - 2, color);
+            }
+        }
+    }
+    /// Draw a color-filled triangle (either winding order)
+    pub fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        let edge = |a: Vector2, b: Vector2, x: f32, y: f32| {
+            (b.x - a.x) * (y - a.y) - (b.y - a.y) * (x - a.x)
+        };
+        let area = edge(v1, v2, v3.x, v3.y);
+        if area == 0.0 {
+            return;
+        }
+        let x0 = v1.x.min(v2.x).min(v3.x).floor().max(0.0) as i32;
+        let y0 = v1.y.min(v2.y).min(v3.y).floor().max(0.0) as i32;
+        let x1 = (v1.x.max(v2.x).max(v3.x).ceil() as i32).min(self.width);
+        let y1 = (v1.y.max(v2.y).max(v3.y).ceil() as i32).min(self.height);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                // Sample pixel centers
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                let w1 = edge(v2, v3, px, py) * area.signum();
+                let w2 = edge(v3, v1, px, py) * area.signum();
+                let w3 = edge(v1, v2, px, py) * area.signum();
+                if w1 >= 0.0 && w2 >= 0.0 && w3 >= 0.0 {
+                    self.draw_pixel(x, y, color);
+                }
+            }
+        }
+    }
+    /// Draw triangle outline
+    pub fn draw_triangle_lines(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        self.draw_line_v(v1, v2, color);
+        self.draw_line_v(v2, v3, color);
+        self.draw_line_v(v3, v1, color);
+    }
+
+    //--------------------------------------------------------------------------
+    // Image drawing functions
+    //--------------------------------------------------------------------------
+
+    /// Draw a source image within a destination rectangle (nearest-neighbor scaling)
+    ///
+    /// NOTE: Source can be any uncompressed format
+    pub fn draw_image(
+        &mut self,
+        src: &Image,
+        src_rec: Rectangle,
+        dst_rec: Rectangle,
+        tint: Color,
+    ) -> Result<()> {
+        let colors = src.get_colors()?;
+        let dst_x = dst_rec.x.round() as i32;
+        let dst_y = dst_rec.y.round() as i32;
+        let dst_w = dst_rec.width.round() as i32;
+        let dst_h = dst_rec.height.round() as i32;
+        for y in dst_y.max(0)..dst_y.saturating_add(dst_h).min(self.height) {
+            for x in dst_x.max(0)..dst_x.saturating_add(dst_w).min(self.width) {
+                let u = (i64::from(x) - i64::from(dst_x)) as f32 + 0.5;
+                let v = (i64::from(y) - i64::from(dst_y)) as f32 + 0.5;
+                let sx = (src_rec.x + u * src_rec.width / dst_w as f32).floor() as i32;
+                let sy = (src_rec.y + v * src_rec.height / dst_h as f32).floor() as i32;
+                if sx >= 0 && sy >= 0 && sx < src.width() && sy < src.height() {
+                    let color = colors[(sy * src.width() + sx) as usize];
+                    self.draw_pixel(x, y, color_tint(color, tint));
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Draw a source image at position
+    pub fn draw_image_v(&mut self, src: &Image, position: Vector2, tint: Color) -> Result<()> {
+        let (width, height) = (src.width() as f32, src.height() as f32);
+        self.draw_image(
+            src,
+            Rectangle::new(0.0, 0.0, width, height),
+            Rectangle::new(position.x, position.y, width, height),
+            tint,
+        )
+    }
+
+    //--------------------------------------------------------------------------
+    // Text drawing functions
+    //--------------------------------------------------------------------------
+
+    /// Draw text (using the canvas bitmap font)
+    ///
+    /// NOTE: Font is scaled by whole multiples of `CANVAS_FONT_SIZE`, characters
+    /// outside printable ASCII are drawn as `?`
+    pub fn draw_text(&mut self, text: &str, pos_x: i32, pos_y: i32, font_size: i32, color: Color) {
+        canvas_text_pixels(text, pos_x, pos_y, font_size, |x, y, size| {
+            self.draw_rectangle(x, y, size, size, color)
+        });
+    }
+
+    // Clip a line to the canvas (Liang-Barsky), `None` if it misses the canvas
+    //
+    // NOTE: Lines inside the canvas are unchanged, clipped ends are rounded
+    fn clip_line(&self, x0: i32, y0: i32, x1: i32, y1: i32) -> Option<(i32, i32, i32, i32)> {
+        let (x0, y0) = (f64::from(x0), f64::from(y0));
+        let (dx, dy) = (f64::from(x1) - x0, f64::from(y1) - y0);
+        let (max_x, max_y) = (f64::from(self.width - 1), f64::from(self.height - 1));
+        let (mut t0, mut t1) = (0.0f64, 1.0f64);
+        for &(p, q) in &[(-dx, x0), (dx, max_x - x0), (-dy, y0), (dy, max_y - y0)] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else if p < 0.0 {
+                t0 = t0.max(q / p);
+            } else {
+                t1 = t1.min(q / p);
+            }
+        }
+        if t0 > t1 {
+            return None;
+        }
+        let point = |t: f64| ((x0 + t * dx).round() as i32, (y0 + t * dy).round() as i32);
+        let ((x0, y0), (x1, y1)) = (point(t0), point(t1));
+        Some((x0, y0, x1, y1))
+    }
+    // Rows of a filled circle on the canvas with their half widths
+    fn circle_rows(&self, center_y: i64, radius: f32) -> impl Iterator<Item = (i64, i64)> {
+        let radius = f64::from(radius);
+        let (first, last) = if radius >= 0.0 {
+            let r = radius.ceil() as i64;
+            (
+                center_y.saturating_sub(r).max(0),
+                center_y.saturating_add(r).min(i64::from(self.height) - 1),
+            )
+        } else {
+            // Negative or NaN radius draws nothing
+            (0, -1)
+        };
+        (first..=last)
+            .map(move |y| (y, circle_half_width(radius, y - center_y)))
+            .filter(|&(_, half_width)| half_width >= 0)
+    }
+    // Draw pixels from `x0` to `x1` (inclusive) on row `y`, clipped to the canvas
+    fn draw_span(&mut self, x0: i64, x1: i64, y: i64, color: Color) {
+        if y < 0 || y >= i64::from(self.height) {
+            return;
+        }
+        let (x0, x1) = (x0.max(0), x1.min(i64::from(self.width) - 1));
+        for x in x0..=x1 {
+            self.draw_pixel(x as i32, y as i32, color);
+        }
+    }
+}
+impl<'a> fmt::Debug for ImageCanvas<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ImageCanvas")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("format", &PixelFormat::UncompressedR8g8b8a8)
+            .finish()
+    }
+}
+
+/// Call `pixel(x, y, size)` for each square pixel of text in the canvas bitmap font
+pub(crate) fn canvas_text_pixels<F>(
+    text: &str,
+    pos_x: i32,
+    pos_y: i32,
+    font_size: i32,
+    mut pixel: F,
+) where
+    F: FnMut(i32, i32, i32),
+{
+    let scale = (font_size / CANVAS_FONT_SIZE).max(1);
+    let (mut x, mut y) = (pos_x, pos_y);
+    for c in text.chars() {
+        if c == '\n' {
+            x = pos_x;
+            y = y.saturating_add(CANVAS_FONT_SIZE.saturating_mul(scale));
+            continue;
+        }
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for col in 0..5 {
+                if bits & (0x10 >> col) != 0 {
+                    pixel(
+                        x.saturating_add(col * scale),
+                        y.saturating_add((row as i32).saturating_mul(scale)),
+                        scale,
+                    );
+                }
+            }
+        }
+        x = x.saturating_add(6i32.saturating_mul(scale));
+    }
+}
+
+/// Measure string width for the canvas bitmap font
+pub fn measure_canvas_text(text: &str, font_size: i32) -> i32 {
+    let scale = (font_size / CANVAS_FONT_SIZE).max(1);
+    let longest = text.lines().map(|line| line.chars().count()).max();
+    match longest {
+        Some(0) | None => 0,
+        // No spacing after the last character
+        Some(count) => (count as i32 * 6 - 1) * scale,
+    }
+}
+
+// Half width of a filled circle on row `dy` from its center, -1 if the row misses it
+fn circle_half_width(radius: f64, dy: i64) -> i64 {
+    let dy = dy as f64;
+    if dy * dy > radius * radius {
+        -1
+    } else {
+        (radius * radius - dy * dy).sqrt().floor() as i64
+    }
+}
+
+/// Blend `src` over `dst` using `src` alpha
+fn alpha_blend(dst: Color, src: Color) -> Color {
+    match src.a {
+        255 => src,
+        0 => dst,
+        _ => {
+            let sa = u32::from(src.a);
+            let da = u32::from(dst.a) * (255 - sa) / 255;
+            let out_a = sa + da;
+            let channel = |s: u8, d: u8| ((u32::from(s) * sa + u32::from(d) * da) / out_a) as u8;
+            Color::new(
+                channel(src.r, dst.r),
+                channel(src.g, dst.g),
+                channel(src.b, dst.b),
+                out_a as u8,
+            )
+        }
+    }
+}
+
+fn color_tint(color: Color, tint: Color) -> Color {
+    let channel = |c: u8, t: u8| (u32::from(c) * u32::from(t) / 255) as u8;
+    Color::new(
+        channel(color.r, tint.r),
+        channel(color.g, tint.g),
+        channel(color.b, tint.b),
+        channel(color.a, tint.a),
+    )
+}
+
+fn glyph(c: char) -> &'static [u8; 7] {
+    let index = match c {
+        ' '..='~' => c as usize - ' ' as usize,
+        _ => '?' as usize - ' ' as usize,
+    };
+    &FONT[index]
+}
+
+//------------------------------------------------------------------------------
+// Default bitmap font
+//------------------------------------------------------------------------------
+
+// Printable ASCII (0x20-0x7e), 7 rows of 5 pixels, MSB is the leftmost pixel
+#[rustfmt::skip]
+const FONT: [[u8; 7]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04], // '!'
+    [0x0a, 0x0a, 0x0a, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x0a, 0x0a, 0x1f, 0x0a, 0x1f, 0x0a, 0x0a], // '#'
+    [0x04, 0x0f, 0x14, 0x0e, 0x05, 0x1e, 0x04], // '$'
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03], // '%'
+    [0x0c, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0d], // '&'
+    [0x0c, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00], // '''
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02], // '('
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08], // ')'
+    [0x00, 0x04, 0x15, 0x0e, 0x15, 0x04, 0x00], // '*'
+    [0x00, 0x04, 0x04, 0x1f, 0x04, 0x04, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x0c, 0x04, 0x08], // ','
+    [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c], // '.'
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00], // '/'
+    [0x0e, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0e], // '0'
+    [0x04, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x0e], // '1'
+    [0x0e, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1f], // '2'
+    [0x1f, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0e], // '3'
+    [0x02, 0x06, 0x0a, 0x12, 0x1f, 0x02, 0x02], // '4'
+    [0x1f, 0x10, 0x1e, 0x01, 0x01, 0x11, 0x0e], // '5'
+    [0x06, 0x08, 0x10, 0x1e, 0x11, 0x11, 0x0e], // '6'
+    [0x1f, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08], // '7'
+    [0x0e, 0x11, 0x11, 0x0e, 0x11, 0x11, 0x0e], // '8'
+    [0x0e, 0x11, 0x11, 0x0f, 0x01, 0x02, 0x0c], // '9'
+    [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x0c, 0x00], // ':'
+    [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x04, 0x08], // ';'
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02], // '<'
+    [0x00, 0x00, 0x1f, 0x00, 0x1f, 0x00, 0x00], // '='
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08], // '>'
+    [0x0e, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04], // '?'
+    [0x0e, 0x11, 0x01, 0x0d, 0x15, 0x15, 0x0e], // '@'
+    [0x0e, 0x11, 0x11, 0x11, 0x1f, 0x11, 0x11], // 'A'
+    [0x1e, 0x11, 0x11, 0x1e, 0x11, 0x11, 0x1e], // 'B'
+    [0x0e, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0e], // 'C'
+    [0x1c, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1c], // 'D'
+    [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x1f], // 'E'
+    [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x10], // 'F'
+    [0x0e, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0f], // 'G'
+    [0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11], // 'H'
+    [0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e], // 'I'
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0c], // 'J'
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11], // 'K'
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1f], // 'L'
+    [0x11, 0x1b, 0x15, 0x15, 0x11, 0x11, 0x11], // 'M'
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11], // 'N'
+    [0x0e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e], // 'O'
+    [0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10, 0x10], // 'P'
+    [0x0e, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0d], // 'Q'
+    [0x1e, 0x11, 0x11, 0x1e, 0x14, 0x12, 0x11], // 'R'
+    [0x0f, 0x10, 0x10, 0x0e, 0x01, 0x01, 0x1e], // 'S'
+    [0x1f, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // 'T'
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e], // 'U'
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0a, 0x04], // 'V'
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0a], // 'W'
+    [0x11, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x11], // 'X'
+    [0x11, 0x11, 0x11, 0x0a, 0x04, 0x04, 0x04], // 'Y'
+    [0x1f, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1f], // 'Z'
+    [0x0e, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0e], // '['
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00], // '\'
+    [0x0e, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0e], // ']'
+    [0x04, 0x0a, 0x11, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1f], // '_'
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x0e, 0x01, 0x0f, 0x11, 0x0f], // 'a'
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1e], // 'b'
+    [0x00, 0x00, 0x0e, 0x10, 0x10, 0x11, 0x0e], // 'c'
+    [0x01, 0x01, 0x0d, 0x13, 0x11, 0x11, 0x0f], // 'd'
+    [0x00, 0x00, 0x0e, 0x11, 0x1f, 0x10, 0x0e], // 'e'
+    [0x06, 0x09, 0x08, 0x1c, 0x08, 0x08, 0x08], // 'f'
+    [0x00, 0x0f, 0x11, 0x11, 0x0f, 0x01, 0x0e], // 'g'
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11], // 'h'
+    [0x04, 0x00, 0x0c, 0x04, 0x04, 0x04, 0x0e], // 'i'
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0c], // 'j'
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12], // 'k'
+    [0x0c, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e], // 'l'
+    [0x00, 0x00, 0x1a, 0x15, 0x15, 0x11, 0x11], // 'm'
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11], // 'n'
+    [0x00, 0x00, 0x0e, 0x11, 0x11, 0x11, 0x0e], // 'o'
+    [0x00, 0x00, 0x1e, 0x11, 0x1e, 0x10, 0x10], // 'p'
+    [0x00, 0x00, 0x0d, 0x13, 0x0f, 0x01, 0x01], // 'q'
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10], // 'r'
+    [0x00, 0x00, 0x0e, 0x10, 0x0e, 0x01, 0x1e], // 's'
+    [0x08, 0x08, 0x1c, 0x08, 0x08, 0x09, 0x06], // 't'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0d], // 'u'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0a, 0x04], // 'v'
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0a], // 'w'
+    [0x00, 0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11], // 'x'
+    [0x00, 0x00, 0x11, 0x11, 0x0f, 0x01, 0x0e], // 'y'
+    [0x00, 0x00, 0x1f, 0x02, 0x04, 0x08, 0x1f], // 'z'
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02], // '{'
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // '|'
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08], // '}'
+    [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00], // '~'
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::textures::load_image_ex;
+
+    const BLACK: Color = Color {
+        r: 0,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+    const WHITE: Color = Color {
+        r: 255,
+        g: 255,
+        b: 255,
+        a: 255,
+    };
+
+    fn blank(width: i32, height: i32) -> Image {
+        load_image_ex(&vec![BLACK; (width * height) as usize], width, height).unwrap()
+    }
+
+    fn count(image: &Image, color: Color) -> usize {
+        image
+            .pixels()
+            .unwrap()
+            .iter()
+            .filter(|&&c| c == color)
+            .count()
+    }
+
+    #[test]
+    fn shapes_are_clipped() {
+        let mut image = blank(8, 8);
+        {
+            let mut canvas = ImageCanvas::new(&mut image).unwrap();
+            canvas.draw_rectangle(-2, -2, 4, 4, WHITE);
+            canvas.draw_line(0, 7, 20, 7, WHITE);
+            canvas.draw_pixel(100, 100, WHITE);
+        }
+        assert_eq!(count(&image, WHITE), 4 + 8);
+        assert_eq!(image.get_pixel(1, 1).unwrap(), WHITE);
+    }
+
+    #[test]
+    fn extreme_coordinates() {
+        let mut image = blank(8, 8);
+        {
+            let mut canvas = ImageCanvas::new(&mut image).unwrap();
+            canvas.draw_circle(4, 4, f32::INFINITY, WHITE);
+            canvas.draw_circle(i32::MIN, i32::MAX, 1e9, WHITE);
+            canvas.draw_circle(4, 4, f32::NAN, WHITE);
+            canvas.draw_circle_lines(4, 4, 1e9, WHITE);
+            canvas.draw_circle_lines(4, 4, f32::INFINITY, WHITE);
+            canvas.draw_rectangle(i32::MAX, i32::MAX, i32::MAX, i32::MAX, WHITE);
+            canvas.draw_rectangle_lines(i32::MAX - 1, 0, i32::MAX, i32::MAX, WHITE);
+            canvas.draw_line(i32::MIN, i32::MIN, i32::MAX, i32::MAX, WHITE);
+            canvas.draw_text("x", i32::MAX, 0, i32::MAX, WHITE);
+        }
+        assert_eq!(count(&image, WHITE), 64);
+
+        // A long line far outside the canvas is skipped, one through it is clipped
+        let mut image = blank(8, 8);
+        {
+            let mut canvas = ImageCanvas::new(&mut image).unwrap();
+            canvas.draw_line(-1_000_000_000, -5, 1_000_000_000, -5, WHITE);
+            canvas.draw_line(-1_000_000_000, 3, 1_000_000_000, 3, WHITE);
+        }
+        assert_eq!(count(&image, WHITE), 8);
+        assert_eq!(image.get_pixel(0, 3).unwrap(), WHITE);
+    }
+
+    #[test]
+    fn alpha_blending() {
+        let mut image = blank(2, 1);
+        ImageCanvas::new(&mut image)
+            .unwrap()
+            .draw_pixel(0, 0, Color::new(255, 0, 0, 128));
+        assert_eq!(image.get_pixel(0, 0).unwrap(), Color::new(128, 0, 0, 255));
+        assert_eq!(image.get_pixel(1, 0).unwrap(), BLACK);
+    }
+
+    #[test]
+    fn circles_and_triangles() {
+        let mut image = blank(16, 16);
+        ImageCanvas::new(&mut image)
+            .unwrap()
+            .draw_circle(8, 8, 3.0, WHITE);
+        // Symmetric disc of radius 3
+        assert_eq!(count(&image, WHITE), 29);
+        let mut outline = blank(16, 16);
+        ImageCanvas::new(&mut outline)
+            .unwrap()
+            .draw_circle_lines(8, 8, 3.0, WHITE);
+        // Edge pixels of the same disc
+        assert_eq!(count(&outline, WHITE), 16);
+        assert_eq!(outline.get_pixel(8, 8).unwrap(), BLACK);
+        assert_eq!(outline.get_pixel(11, 8).unwrap(), WHITE);
+
+        let (a, b, c) = (
+            Vector2::new(0.0, 0.0),
+            Vector2::new(8.0, 0.0),
+            Vector2::new(0.0, 8.0),
+        );
+        let mut cw = blank(8, 8);
+        ImageCanvas::new(&mut cw)
+            .unwrap()
+            .draw_triangle(a, b, c, WHITE);
+        let mut ccw = blank(8, 8);
+        ImageCanvas::new(&mut ccw)
+            .unwrap()
+            .draw_triangle(a, c, b, WHITE);
+        assert_eq!(cw.pixels().unwrap(), ccw.pixels().unwrap());
+        assert_eq!(count(&cw, WHITE), 36);
+    }
+
+    #[test]
+    fn blit_and_text() {
+        let src = load_image_ex(&[WHITE; 4], 2, 2).unwrap();
+        let mut image = blank(8, 8);
+        ImageCanvas::new(&mut image)
+            .unwrap()
+            .draw_image(
+                &src,
+                Rectangle::new(0.0, 0.0, 2.0, 2.0),
+                Rectangle::new(4.0, 4.0, 4.0, 4.0),
+                WHITE,
+            )
+            .unwrap();
+        assert_eq!(count(&image, WHITE), 16);
+
+        let mut image = blank(16, 8);
+        ImageCanvas::new(&mut image)
+            .unwrap()
+            .draw_text("|", 0, 0, 8, WHITE);
+        assert_eq!(count(&image, WHITE), 7);
+        assert_eq!(measure_canvas_text("Hi", 8), 11);
+        assert_eq!(measure_canvas_text("Hi", 16), 22);
+    }
+}
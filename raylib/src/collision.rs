@@ -0,0 +1,587 @@
+//! Basic 2D and 3D Shape Collision Detection Functions
+//!
+//! NOTE: Implemented in pure Rust, no window or OpenGL context is required
+
+use crate::{BoundingBox, Ray, RayHitInfo, Rectangle, Vector2, Vector3};
+
+const EPSILON: f32 = 0.000_001;
+
+/// 2D collision contact information
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Contact2D {
+    /// Contact point, in the middle of the overlapping region
+    pub point: Vector2,
+    /// Collision normal, pointing from the first shape towards the second
+    pub normal: Vector2,
+    /// Penetration depth along the normal
+    pub depth: f32,
+}
+
+//------------------------------------------------------------------------------
+// Basic 2D shapes collision detection functions
+//------------------------------------------------------------------------------
+
+/// Check collision between two rectangles
+pub fn check_collision_recs(rec1: Rectangle, rec2: Rectangle) -> bool {
+    rec1.x < rec2.x + rec2.width
+        && rec1.x + rec1.width > rec2.x
+        && rec1.y < rec2.y + rec2.height
+        && rec1.y + rec1.height > rec2.y
+}
+/// Check collision between two circles
+pub fn check_collision_circles(
+    center1: Vector2,
+    radius1: f32,
+    center2: Vector2,
+    radius2: f32,
+) -> bool {
+    center1.distance(center2) <= radius1 + radius2
+}
+/// Check collision between circle and rectangle
+pub fn check_collision_circle_rec(center: Vector2, radius: f32, rec: Rectangle) -> bool {
+    let closest = closest_point_on_rec(center, rec);
+    let delta = center - closest;
+    delta.dot(delta) <= radius * radius
+}
+/// Get collision rectangle for two rectangles collision
+pub fn get_collision_rec(rec1: Rectangle, rec2: Rectangle) -> Option<Rectangle> {
+    if !check_collision_recs(rec1, rec2) {
+        return None;
+    }
+    let x = rec1.x.max(rec2.x);
+    let y = rec1.y.max(rec2.y);
+    let right = (rec1.x + rec1.width).min(rec2.x + rec2.width);
+    let bottom = (rec1.y + rec1.height).min(rec2.y + rec2.height);
+    Some(Rectangle::new(x, y, right - x, bottom - y))
+}
+/// Check if point is inside rectangle
+pub fn check_collision_point_rec(point: Vector2, rec: Rectangle) -> bool {
+    point.x >= rec.x
+        && point.x <= rec.x + rec.width
+        && point.y >= rec.y
+        && point.y <= rec.y + rec.height
+}
+/// Check if point is inside circle
+pub fn check_collision_point_circle(point: Vector2, center: Vector2, radius: f32) -> bool {
+    check_collision_circles(point, 0.0, center, radius)
+}
+/// Check if point is inside a triangle defined by three points (p1, p2, p3)
+pub fn check_collision_point_triangle(
+    point: Vector2,
+    p1: Vector2,
+    p2: Vector2,
+    p3: Vector2,
+) -> bool {
+    let den = (p2.y - p3.y) * (p1.x - p3.x) + (p3.x - p2.x) * (p1.y - p3.y);
+    let alpha = ((p2.y - p3.y) * (point.x - p3.x) + (p3.x - p2.x) * (point.y - p3.y)) / den;
+    let beta = ((p3.y - p1.y) * (point.x - p3.x) + (p1.x - p3.x) * (point.y - p3.y)) / den;
+    let gamma = 1.0 - alpha - beta;
+    alpha > 0.0 && beta > 0.0 && gamma > 0.0
+}
+
+//------------------------------------------------------------------------------
+// 2D contact generation functions
+//------------------------------------------------------------------------------
+
+/// Get contact information for two rectangles collision
+///
+/// NOTE: The normal is the axis of least penetration
+pub fn get_contact_recs(rec1: Rectangle, rec2: Rectangle) -> Option<Contact2D> {
+    let overlap = get_collision_rec(rec1, rec2)?;
+    let delta = rec_center(rec2) - rec_center(rec1);
+    let (normal, depth) = if overlap.width < overlap.height {
+        (Vector2::new(sign(delta.x), 0.0), overlap.width)
+    } else {
+        (Vector2::new(0.0, sign(delta.y)), overlap.height)
+    };
+    Some(Contact2D {
+        point: rec_center(overlap),
+        normal: normal,
+        depth: depth,
+    })
+}
+/// Get contact information for two circles collision
+pub fn get_contact_circles(
+    center1: Vector2,
+    radius1: f32,
+    center2: Vector2,
+    radius2: f32,
+) -> Option<Contact2D> {
+    let delta = center2 - center1;
+    let distance = delta.length();
+    if distance > radius1 + radius2 {
+        return None;
+    }
+    let normal = if distance > 0.0 {
+        delta.scale(1.0 / distance)
+    } else {
+        Vector2::new(1.0, 0.0)
+    };
+    let depth = radius1 + radius2 - distance;
+    Some(Contact2D {
+        point: center1 + normal.scale(radius1 - depth * 0.5),
+        normal: normal,
+        depth: depth,
+    })
+}
+/// Get contact information for circle and rectangle collision
+pub fn get_contact_circle_rec(center: Vector2, radius: f32, rec: Rectangle) -> Option<Contact2D> {
+    let closest = closest_point_on_rec(center, rec);
+    let delta = closest - center;
+    let distance = delta.length();
+
+    if distance > 0.0 {
+        if distance > radius {
+            return None;
+        }
+        let depth = radius - distance;
+        return Some(Contact2D {
+            point: closest,
+            normal: delta.scale(1.0 / distance),
+            depth: depth,
+        });
+    }
+
+    // Circle center is inside the rectangle, push out through the nearest edge
+    let left = center.x - rec.x;
+    let right = rec.x + rec.width - center.x;
+    let top = center.y - rec.y;
+    let bottom = rec.y + rec.height - center.y;
+    let nearest = left.min(right).min(top).min(bottom);
+    let (normal, point) = if nearest == left {
+        (Vector2::new(1.0, 0.0), Vector2::new(rec.x, center.y))
+    } else if nearest == right {
+        (
+            Vector2::new(-1.0, 0.0),
+            Vector2::new(rec.x + rec.width, center.y),
+        )
+    } else if nearest == top {
+        (Vector2::new(0.0, 1.0), Vector2::new(center.x, rec.y))
+    } else {
+        (
+            Vector2::new(0.0, -1.0),
+            Vector2::new(center.x, rec.y + rec.height),
+        )
+    };
+    Some(Contact2D {
+        point: point,
+        normal: normal,
+        depth: radius + nearest,
+    })
+}
+
+//------------------------------------------------------------------------------
+// Basic 3D shapes collision detection functions
+//------------------------------------------------------------------------------
+
+/// Detect collision between two spheres
+pub fn check_collision_spheres(
+    center_a: Vector3,
+    radius_a: f32,
+    center_b: Vector3,
+    radius_b: f32,
+) -> bool {
+    center_a.distance(center_b) <= radius_a + radius_b
+}
+/// Detect collision between two boxes
+pub fn check_collision_boxes(box1: BoundingBox, box2: BoundingBox) -> bool {
+    box1.max.x >= box2.min.x
+        && box1.min.x <= box2.max.x
+        && box1.max.y >= box2.min.y
+        && box1.min.y <= box2.max.y
+        && box1.max.z >= box2.min.z
+        && box1.min.z <= box2.max.z
+}
+/// Detect collision between box and sphere
+pub fn check_collision_box_sphere(bbox: BoundingBox, center: Vector3, radius: f32) -> bool {
+    let closest = closest_point_on_box(center, bbox);
+    let delta = center - closest;
+    delta.dot(delta) <= radius * radius
+}
+/// Detect collision between ray and sphere
+pub fn check_collision_ray_sphere(ray: Ray, center: Vector3, radius: f32) -> bool {
+    get_collision_ray_sphere(ray, center, radius).is_some()
+}
+/// Detect collision between ray and sphere, returns collision point
+pub fn check_collision_ray_sphere_ex(ray: Ray, center: Vector3, radius: f32) -> Option<Vector3> {
+    get_collision_ray_sphere(ray, center, radius).map(|hit| hit.position)
+}
+/// Detect collision between ray and box
+pub fn check_collision_ray_box(ray: Ray, bbox: BoundingBox) -> bool {
+    get_collision_ray_box(ray, bbox).is_some()
+}
+
+//------------------------------------------------------------------------------
+// Ray casting functions
+//------------------------------------------------------------------------------
+
+/// Get collision info between ray and sphere
+///
+/// NOTE: When the ray starts inside the sphere the exit point is returned,
+/// a zero direction never hits
+pub fn get_collision_ray_sphere(ray: Ray, center: Vector3, radius: f32) -> Option<RayHitInfo> {
+    if ray.direction.length() == 0.0 {
+        return None;
+    }
+    let direction = ray.direction.normalize();
+    let ray_sphere_pos = center - ray.position;
+    let vector = ray_sphere_pos.dot(direction);
+    let distance_sq = ray_sphere_pos.dot(ray_sphere_pos);
+    let d = radius * radius - (distance_sq - vector * vector);
+    if d < 0.0 {
+        return None;
+    }
+
+    let inside = distance_sq < radius * radius;
+    let distance = if inside {
+        vector + d.sqrt()
+    } else {
+        vector - d.sqrt()
+    };
+    if distance < 0.0 {
+        return None;
+    }
+
+    let position = ray.position + direction.scale(distance);
+    Some(RayHitInfo {
+        distance: distance,
+        position: position,
+        normal: (position - center).normalize(),
+    })
+}
+/// Get collision info between ray and box
+///
+/// NOTE: When the ray starts inside the box the exit point is returned,
+/// a zero direction never hits
+pub fn get_collision_ray_box(ray: Ray, bbox: BoundingBox) -> Option<RayHitInfo> {
+    if ray.direction.length() == 0.0 {
+        return None;
+    }
+    let direction = ray.direction.normalize();
+    let origin = [ray.position.x, ray.position.y, ray.position.z];
+    let dir = [direction.x, direction.y, direction.z];
+    let min = [bbox.min.x, bbox.min.y, bbox.min.z];
+    let max = [bbox.max.x, bbox.max.y, bbox.max.z];
+
+    let mut t_near = f32::NEG_INFINITY;
+    let mut t_far = f32::INFINITY;
+    let mut near_axis = 0;
+    let mut far_axis = 0;
+    for axis in 0..3 {
+        if dir[axis] == 0.0 {
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let t1 = (min[axis] - origin[axis]) / dir[axis];
+        let t2 = (max[axis] - origin[axis]) / dir[axis];
+        let (t1, t2) = if t1 <= t2 { (t1, t2) } else { (t2, t1) };
+        if t1 > t_near {
+            t_near = t1;
+            near_axis = axis;
+        }
+        if t2 < t_far {
+            t_far = t2;
+            far_axis = axis;
+        }
+    }
+    if t_far < 0.0 || t_near > t_far {
+        return None;
+    }
+
+    // Entering faces point against the ray, exiting faces along it
+    let (distance, axis, facing) = if t_near >= 0.0 {
+        (t_near, near_axis, -1.0)
+    } else {
+        (t_far, far_axis, 1.0)
+    };
+    let mut normal = [0.0; 3];
+    normal[axis] = facing * sign(dir[axis]);
+    Some(RayHitInfo {
+        distance: distance,
+        position: ray.position + direction.scale(distance),
+        normal: Vector3::new(normal[0], normal[1], normal[2]),
+    })
+}
+/// Get collision info between ray and triangle
+pub fn get_collision_ray_triangle(
+    ray: Ray,
+    p1: Vector3,
+    p2: Vector3,
+    p3: Vector3,
+) -> Option<RayHitInfo> {
+    let direction = ray.direction.normalize();
+    let edge1 = p2 - p1;
+    let edge2 = p3 - p1;
+
+    // Begin calculating determinant - also used to calculate u parameter
+    let p = direction.cross(edge2);
+    // If determinant is near zero, ray lies in plane of triangle or ray is parallel to plane of triangle
+    let det = edge1.dot(p);
+    if det > -EPSILON && det < EPSILON {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+
+    // Calculate distance from p1 to ray origin and u parameter
+    let tv = ray.position - p1;
+    let u = tv.dot(p) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    // Prepare to test v parameter
+    let q = tv.cross(edge1);
+    let v = direction.dot(q) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let t = edge2.dot(q) * inv_det;
+    if t <= EPSILON {
+        return None;
+    }
+    Some(RayHitInfo {
+        distance: t,
+        position: ray.position + direction.scale(t),
+        normal: edge1.cross(edge2).normalize(),
+    })
+}
+/// Get collision info between ray and ground plane (Y-normal plane)
+pub fn get_collision_ray_ground(ray: Ray, ground_height: f32) -> Option<RayHitInfo> {
+    let direction = ray.direction.normalize();
+    if direction.y.abs() <= EPSILON {
+        return None;
+    }
+    let distance = (ray.position.y - ground_height) / -direction.y;
+    if distance < 0.0 {
+        return None;
+    }
+    Some(RayHitInfo {
+        distance: distance,
+        position: ray.position + direction.scale(distance),
+        normal: Vector3::new(0.0, 1.0, 0.0),
+    })
+}
+
+//------------------------------------------------------------------------------
+// Module specific functions
+//------------------------------------------------------------------------------
+
+fn sign(value: f32) -> f32 {
+    if value < 0.0 {
+        -1.0
+    } else {
+        1.0
+    }
+}
+
+fn rec_center(rec: Rectangle) -> Vector2 {
+    Vector2::new(rec.x + rec.width * 0.5, rec.y + rec.height * 0.5)
+}
+
+fn closest_point_on_rec(point: Vector2, rec: Rectangle) -> Vector2 {
+    Vector2::new(
+        point.x.max(rec.x).min(rec.x + rec.width),
+        point.y.max(rec.y).min(rec.y + rec.height),
+    )
+}
+
+fn closest_point_on_box(point: Vector3, bbox: BoundingBox) -> Vector3 {
+    Vector3::new(
+        point.x.max(bbox.min.x).min(bbox.max.x),
+        point.y.max(bbox.min.y).min(bbox.max.y),
+        point.z.max(bbox.min.z).min(bbox.max.z),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_box() -> BoundingBox {
+        BoundingBox::new(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn rectangles() {
+        let a = Rectangle::new(0.0, 0.0, 10.0, 10.0);
+        let b = Rectangle::new(8.0, 5.0, 10.0, 10.0);
+        let c = Rectangle::new(10.0, 0.0, 5.0, 5.0);
+        assert!(check_collision_recs(a, b));
+        assert!(!check_collision_recs(a, c));
+        assert_eq!(
+            get_collision_rec(a, b),
+            Some(Rectangle::new(8.0, 5.0, 2.0, 5.0))
+        );
+        assert_eq!(get_collision_rec(a, c), None);
+
+        let contact = get_contact_recs(a, b).unwrap();
+        assert_eq!(contact.normal, Vector2::new(1.0, 0.0));
+        assert_eq!(contact.depth, 2.0);
+        assert_eq!(contact.point, Vector2::new(9.0, 7.5));
+    }
+
+    #[test]
+    fn points() {
+        let rec = Rectangle::new(0.0, 0.0, 10.0, 10.0);
+        assert!(check_collision_point_rec(Vector2::new(10.0, 0.0), rec));
+        assert!(!check_collision_point_rec(Vector2::new(10.1, 0.0), rec));
+        assert!(check_collision_point_circle(
+            Vector2::new(3.0, 4.0),
+            Vector2::zero(),
+            5.0
+        ));
+        let (p1, p2, p3) = (
+            Vector2::new(0.0, 0.0),
+            Vector2::new(10.0, 0.0),
+            Vector2::new(0.0, 10.0),
+        );
+        assert!(check_collision_point_triangle(
+            Vector2::new(2.0, 2.0),
+            p1,
+            p2,
+            p3
+        ));
+        assert!(!check_collision_point_triangle(
+            Vector2::new(6.0, 6.0),
+            p1,
+            p2,
+            p3
+        ));
+    }
+
+    #[test]
+    fn circles() {
+        let c1 = Vector2::new(0.0, 0.0);
+        let c2 = Vector2::new(3.0, 0.0);
+        assert!(check_collision_circles(c1, 2.0, c2, 1.0));
+        assert!(!check_collision_circles(c1, 1.0, c2, 1.0));
+        let contact = get_contact_circles(c1, 2.0, c2, 2.0).unwrap();
+        assert_eq!(contact.normal, Vector2::new(1.0, 0.0));
+        assert_eq!(contact.depth, 1.0);
+        assert_eq!(contact.point, Vector2::new(1.5, 0.0));
+    }
+
+    #[test]
+    fn circle_and_rectangle() {
+        let rec = Rectangle::new(0.0, 0.0, 10.0, 10.0);
+        assert!(check_collision_circle_rec(
+            Vector2::new(-1.0, 5.0),
+            1.5,
+            rec
+        ));
+        assert!(!check_collision_circle_rec(
+            Vector2::new(-1.0, -1.0),
+            1.0,
+            rec
+        ));
+        assert!(check_collision_circle_rec(Vector2::new(5.0, 5.0), 1.0, rec));
+
+        let contact = get_contact_circle_rec(Vector2::new(-1.0, 5.0), 1.5, rec).unwrap();
+        assert_eq!(contact.point, Vector2::new(0.0, 5.0));
+        assert_eq!(contact.normal, Vector2::new(1.0, 0.0));
+        assert_eq!(contact.depth, 0.5);
+
+        let inside = get_contact_circle_rec(Vector2::new(5.0, 9.0), 2.0, rec).unwrap();
+        assert_eq!(inside.point, Vector2::new(5.0, 10.0));
+        assert_eq!(inside.normal, Vector2::new(0.0, -1.0));
+        assert_eq!(inside.depth, 3.0);
+    }
+
+    #[test]
+    fn spheres_and_boxes() {
+        assert!(check_collision_spheres(
+            Vector3::zero(),
+            1.0,
+            Vector3::new(0.0, 2.0, 0.0),
+            1.0
+        ));
+        assert!(check_collision_boxes(
+            unit_box(),
+            BoundingBox::new(Vector3::one(), Vector3::new(2.0, 2.0, 2.0))
+        ));
+        assert!(!check_collision_boxes(
+            unit_box(),
+            BoundingBox::new(Vector3::new(1.5, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0))
+        ));
+        assert!(check_collision_box_sphere(
+            unit_box(),
+            Vector3::new(2.0, 0.0, 0.0),
+            1.0
+        ));
+        assert!(!check_collision_box_sphere(
+            unit_box(),
+            Vector3::new(2.0, 2.0, 0.0),
+            1.0
+        ));
+    }
+
+    #[test]
+    fn ray_sphere() {
+        let ray = Ray::new(Vector3::new(0.0, 0.0, -10.0), Vector3::new(0.0, 0.0, 2.0));
+        let hit = get_collision_ray_sphere(ray, Vector3::zero(), 2.0).unwrap();
+        assert_eq!(hit.distance, 8.0);
+        assert_eq!(hit.position, Vector3::new(0.0, 0.0, -2.0));
+        assert_eq!(hit.normal, Vector3::new(0.0, 0.0, -1.0));
+        assert_eq!(
+            check_collision_ray_sphere_ex(ray, Vector3::zero(), 2.0),
+            Some(Vector3::new(0.0, 0.0, -2.0))
+        );
+
+        let inside = Ray::new(Vector3::zero(), Vector3::new(1.0, 0.0, 0.0));
+        let hit = get_collision_ray_sphere(inside, Vector3::zero(), 2.0).unwrap();
+        assert_eq!(hit.position, Vector3::new(2.0, 0.0, 0.0));
+
+        let away = Ray::new(Vector3::new(0.0, 0.0, -10.0), Vector3::new(0.0, 0.0, -1.0));
+        assert!(!check_collision_ray_sphere(away, Vector3::zero(), 2.0));
+        let miss = Ray::new(Vector3::new(0.0, 3.0, -10.0), Vector3::new(0.0, 0.0, 1.0));
+        assert!(!check_collision_ray_sphere(miss, Vector3::zero(), 2.0));
+        let still = Ray::new(Vector3::zero(), Vector3::zero());
+        assert_eq!(get_collision_ray_sphere(still, Vector3::zero(), 2.0), None);
+    }
+
+    #[test]
+    fn ray_box() {
+        let ray = Ray::new(Vector3::new(-5.0, 0.5, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        let hit = get_collision_ray_box(ray, unit_box()).unwrap();
+        assert_eq!(hit.distance, 4.0);
+        assert_eq!(hit.position, Vector3::new(-1.0, 0.5, 0.0));
+        assert_eq!(hit.normal, Vector3::new(-1.0, 0.0, 0.0));
+
+        let inside = Ray::new(Vector3::zero(), Vector3::new(0.0, -1.0, 0.0));
+        let hit = get_collision_ray_box(inside, unit_box()).unwrap();
+        assert_eq!(hit.position, Vector3::new(0.0, -1.0, 0.0));
+        assert_eq!(hit.normal, Vector3::new(0.0, -1.0, 0.0));
+
+        let miss = Ray::new(Vector3::new(-5.0, 2.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        assert!(!check_collision_ray_box(miss, unit_box()));
+        let behind = Ray::new(Vector3::new(5.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        assert!(!check_collision_ray_box(behind, unit_box()));
+        let still = Ray::new(Vector3::zero(), Vector3::zero());
+        assert_eq!(get_collision_ray_box(still, unit_box()), None);
+    }
+
+    #[test]
+    fn ray_triangle_and_ground() {
+        let ray = Ray::new(Vector3::new(0.25, 0.25, 5.0), Vector3::new(0.0, 0.0, -1.0));
+        let hit = get_collision_ray_triangle(
+            ray,
+            Vector3::zero(),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        )
+        .unwrap();
+        assert_eq!(hit.distance, 5.0);
+        assert_eq!(hit.position, Vector3::new(0.25, 0.25, 0.0));
+        assert_eq!(hit.normal, Vector3::new(0.0, 0.0, 1.0));
+
+        let ray = Ray::new(Vector3::new(0.0, 10.0, 0.0), Vector3::new(0.0, -2.0, 0.0));
+        let hit = get_collision_ray_ground(ray, 1.0).unwrap();
+        assert_eq!(hit.distance, 9.0);
+        assert_eq!(hit.position, Vector3::new(0.0, 1.0, 0.0));
+        assert!(get_collision_ray_ground(ray, 11.0).is_none());
+    }
+}
@@ -0,0 +1,644 @@
+//! Compressed Texture Containers
+//!
+//! Pure Rust parsing of DDS, KTX (1.1), PVR (v3) and ASTC files, so format,
+//! size and mipmap count can be inspected without a GL context. Images keep
+//! every mipmap level stored in the file.
+//!
+//! DXT1/3/5 and ETC1 images decode in software through
+//! [`Image::get_colors`](../struct.Image.html#method.get_colors) and
+//! [`Image::to_rgba8`](../struct.Image.html#method.to_rgba8), for previews and
+//! headless tests.
+
+use std::fs;
+use std::slice;
+
+use crate::textures::load_image_pro;
+use crate::{Color, Error, Image, PixelFormat, Result};
+
+const KTX_IDENTIFIER: [u8; 12] = [
+    0xab, 0x4b, 0x54, 0x58, 0x20, 0x31, 0x31, 0xbb, 0x0d, 0x0a, 0x1a, 0x0a,
+];
+const ASTC_MAGIC: [u8; 4] = [0x13, 0xab, 0xa1, 0x5c];
+
+// ETC1 intensity modifiers, per table codeword
+const ETC1_MODIFIERS: [[i32; 2]; 8] = [
+    [2, 8],
+    [5, 17],
+    [9, 29],
+    [13, 42],
+    [18, 60],
+    [24, 80],
+    [33, 106],
+    [47, 183],
+];
+
+/// Compressed texture file container
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Container {
+    Dds,
+    Ktx,
+    Pvr,
+    Astc,
+}
+
+/// Compressed texture file header information
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CompressedInfo {
+    pub container: Container,
+    pub format: PixelFormat,
+    pub width: i32,
+    pub height: i32,
+    /// Mipmap levels stored in the file
+    pub mipmaps: i32,
+}
+
+/// Read format, size and mipmap count from a DDS, KTX, PVR or ASTC file header
+pub fn inspect_compressed(data: &[u8]) -> Result<CompressedInfo> {
+    parse(data).map(|(info, _)| info)
+}
+
+/// Load a DDS, KTX, PVR or ASTC file from memory, keeping all mipmap levels
+pub fn load_compressed(data: &[u8]) -> Result<Image> {
+    let (info, levels) = parse(data)?;
+    let mut bytes = Vec::new();
+    for (offset, size) in levels {
+        bytes.extend_from_slice(data.get(offset..offset + size).ok_or_else(truncated)?);
+    }
+    // raylib owns image memory, so copy the levels in as one row of bytes
+    let mut image = load_image_pro(
+        &bytes,
+        bytes.len() as i32,
+        1,
+        PixelFormat::UncompressedGrayscale,
+    )?;
+    image.width = info.width;
+    image.height = info.height;
+    image.mipmaps = info.mipmaps;
+    image.format = info.format;
+    image.data_size = bytes.len();
+    Ok(image)
+}
+
+/// Load a DDS, KTX, PVR or ASTC file, keeping all mipmap levels
+pub fn load_compressed_file<S: Into<String>>(file_name: S) -> Result<Image> {
+    let file_name = file_name.into();
+    let data = fs::read(&file_name).map_err(|_| Error::LoadFailed(file_name))?;
+    load_compressed(&data)
+}
+
+// Header information and the byte range of each mipmap level
+fn parse(data: &[u8]) -> Result<(CompressedInfo, Vec<(usize, usize)>)> {
+    if data.starts_with(b"DDS ") {
+        parse_dds(data)
+    } else if data.starts_with(&KTX_IDENTIFIER) {
+        parse_ktx(data)
+    } else if data.starts_with(b"PVR\x03") {
+        parse_pvr(data)
+    } else if data.starts_with(&ASTC_MAGIC) {
+        parse_astc(data)
+    } else {
+        Err(Error::InvalidData(String::from(
+            "not a DDS, KTX, PVR or ASTC file",
+        )))
+    }
+}
+
+fn parse_dds(data: &[u8]) -> Result<(CompressedInfo, Vec<(usize, usize)>)> {
+    let flags = read_u32(data, 8)?;
+    let alpha_pixels = read_u32(data, 80)? & 0x1 != 0;
+    let format = match &read_u32(data, 84)?.to_le_bytes() {
+        b"DXT1" if alpha_pixels => PixelFormat::CompressedDxt1Rgba,
+        b"DXT1" => PixelFormat::CompressedDxt1Rgb,
+        b"DXT3" => PixelFormat::CompressedDxt3Rgba,
+        b"DXT5" => PixelFormat::CompressedDxt5Rgba,
+        four_cc => return Err(unsupported("DDS", &String::from_utf8_lossy(four_cc))),
+    };
+    // DDSD_MIPMAPCOUNT
+    let mipmaps = if flags & 0x20000 != 0 {
+        read_u32(data, 28)?.max(1)
+    } else {
+        1
+    };
+    let info = CompressedInfo {
+        container: Container::Dds,
+        format: format,
+        width: read_u32(data, 16)? as i32,
+        height: read_u32(data, 12)? as i32,
+        mipmaps: mipmaps as i32,
+    };
+    check_info(&info)?;
+    Ok((info, sequential_levels(&info, 128)?))
+}
+
+fn parse_ktx(data: &[u8]) -> Result<(CompressedInfo, Vec<(usize, usize)>)> {
+    if read_u32(data, 12)? != 0x0403_0201 {
+        return Err(Error::InvalidData(String::from(
+            "big-endian KTX files are not supported",
+        )));
+    }
+    let format = match read_u32(data, 28)? {
+        0x83f0 => PixelFormat::CompressedDxt1Rgb,
+        0x83f1 => PixelFormat::CompressedDxt1Rgba,
+        0x83f2 => PixelFormat::CompressedDxt3Rgba,
+        0x83f3 => PixelFormat::CompressedDxt5Rgba,
+        0x8d64 => PixelFormat::CompressedEtc1Rgb,
+        0x9274 => PixelFormat::CompressedEtc2Rgb,
+        0x9278 => PixelFormat::CompressedEtc2EacRgba,
+        0x8c00 => PixelFormat::CompressedPvrtRgb,
+        0x8c02 => PixelFormat::CompressedPvrtRgba,
+        0x93b0 => PixelFormat::CompressedAstc4x4Rgba,
+        0x93b7 => PixelFormat::CompressedAstc8x8Rgba,
+        internal_format => {
+            return Err(unsupported("KTX", &format!("{:#x}", internal_format)));
+        }
+    };
+    let info = CompressedInfo {
+        container: Container::Ktx,
+        format: format,
+        width: read_u32(data, 36)? as i32,
+        height: read_u32(data, 40)?.max(1) as i32,
+        mipmaps: read_u32(data, 56)?.max(1) as i32,
+    };
+    check_info(&info)?;
+    // Each level is prefixed by its size and padded to 4 bytes
+    let mut offset = 64 + read_u32(data, 60)? as usize;
+    let mut levels = Vec::new();
+    for level in 0..info.mipmaps {
+        let size = read_u32(data, offset)? as usize;
+        if Some(size) != mipmap_size(&info, level) {
+            return Err(Error::InvalidData(format!(
+                "KTX mipmap level {} has the wrong size ({} bytes)",
+                level, size
+            )));
+        }
+        levels.push((offset + 4, size));
+        offset += 4 + (size + 3) / 4 * 4;
+    }
+    Ok((info, levels))
+}
+
+fn parse_pvr(data: &[u8]) -> Result<(CompressedInfo, Vec<(usize, usize)>)> {
+    let (format_low, format_high) = (read_u32(data, 8)?, read_u32(data, 12)?);
+    let format = match (format_high, format_low) {
+        (0, 2) => PixelFormat::CompressedPvrtRgb,
+        (0, 3) => PixelFormat::CompressedPvrtRgba,
+        (0, 6) => PixelFormat::CompressedEtc1Rgb,
+        (0, 7) => PixelFormat::CompressedDxt1Rgba,
+        (0, 9) => PixelFormat::CompressedDxt3Rgba,
+        (0, 11) => PixelFormat::CompressedDxt5Rgba,
+        (0, 22) => PixelFormat::CompressedEtc2Rgb,
+        (0, 23) => PixelFormat::CompressedEtc2EacRgba,
+        (high, low) => {
+            return Err(unsupported(
+                "PVR",
+                &format!("{:#x}", (u64::from(high) << 32) | u64::from(low)),
+            ));
+        }
+    };
+    let info = CompressedInfo {
+        container: Container::Pvr,
+        format: format,
+        width: read_u32(data, 28)? as i32,
+        height: read_u32(data, 24)? as i32,
+        mipmaps: read_u32(data, 44)?.max(1) as i32,
+    };
+    check_info(&info)?;
+    let offset = 52 + read_u32(data, 48)? as usize;
+    Ok((info, sequential_levels(&info, offset)?))
+}
+
+fn parse_astc(data: &[u8]) -> Result<(CompressedInfo, Vec<(usize, usize)>)> {
+    let header = data.get(..16).ok_or_else(truncated)?;
+    let format = match (header[4], header[5]) {
+        (4, 4) => PixelFormat::CompressedAstc4x4Rgba,
+        (8, 8) => PixelFormat::CompressedAstc8x8Rgba,
+        (x, y) => return Err(unsupported("ASTC", &format!("{}x{} blocks", x, y))),
+    };
+    let read_u24 = |at: usize| {
+        i32::from(header[at]) | i32::from(header[at + 1]) << 8 | i32::from(header[at + 2]) << 16
+    };
+    let info = CompressedInfo {
+        container: Container::Astc,
+        format: format,
+        width: read_u24(7),
+        height: read_u24(10),
+        mipmaps: 1,
+    };
+    check_info(&info)?;
+    Ok((info, sequential_levels(&info, 16)?))
+}
+
+// Reject empty sizes and more mipmap levels than a full chain down to 1x1
+fn check_info(info: &CompressedInfo) -> Result<()> {
+    if info.width <= 0 || info.height <= 0 {
+        return Err(Error::InvalidData(format!(
+            "invalid texture size {}x{}",
+            info.width, info.height
+        )));
+    }
+    let max_mipmaps = 32 - info.width.max(info.height).leading_zeros() as i32;
+    if info.mipmaps < 1 || info.mipmaps > max_mipmaps {
+        return Err(Error::InvalidData(format!(
+            "{} mipmap levels for a {}x{} texture, at most {}",
+            info.mipmaps, info.width, info.height, max_mipmaps
+        )));
+    }
+    Ok(())
+}
+
+// Size in bytes of a mipmap level
+fn mipmap_size(info: &CompressedInfo, level: i32) -> Option<usize> {
+    let (width, height) = ((info.width >> level).max(1), (info.height >> level).max(1));
+    level_size(info.format, width, height)
+}
+
+// Byte ranges of mipmap levels stored back to back from `offset`
+fn sequential_levels(info: &CompressedInfo, mut offset: usize) -> Result<Vec<(usize, usize)>> {
+    let mut levels = Vec::new();
+    for level in 0..info.mipmaps {
+        let size = mipmap_size(info, level).ok_or_else(|| {
+            Error::InvalidData(format!(
+                "{}x{} texture is too large",
+                info.width, info.height
+            ))
+        })?;
+        levels.push((offset, size));
+        offset += size;
+    }
+    Ok(levels)
+}
+
+// Block width, block height and bytes per block of a compressed format
+fn block_layout(format: PixelFormat) -> Option<(i32, i32, usize)> {
+    match format {
+        PixelFormat::CompressedDxt1Rgb
+        | PixelFormat::CompressedDxt1Rgba
+        | PixelFormat::CompressedEtc1Rgb
+        | PixelFormat::CompressedEtc2Rgb
+        | PixelFormat::CompressedPvrtRgb
+        | PixelFormat::CompressedPvrtRgba => Some((4, 4, 8)),
+        PixelFormat::CompressedDxt3Rgba
+        | PixelFormat::CompressedDxt5Rgba
+        | PixelFormat::CompressedEtc2EacRgba
+        | PixelFormat::CompressedAstc4x4Rgba => Some((4, 4, 16)),
+        PixelFormat::CompressedAstc8x8Rgba => Some((8, 8, 16)),
+        _ => None,
+    }
+}
+
+// Size in bytes of one compressed level
+fn level_size(format: PixelFormat, width: i32, height: i32) -> Option<usize> {
+    let (block_width, block_height, block_bytes) = block_layout(format)?;
+    let (mut width, mut height) = (width, height);
+    // PVRTC 4bpp levels are at least 8x8 pixels
+    if let PixelFormat::CompressedPvrtRgb | PixelFormat::CompressedPvrtRgba = format {
+        width = width.max(8);
+        height = height.max(8);
+    }
+    let blocks_x = (width.max(0) as usize + block_width as usize - 1) / block_width as usize;
+    let blocks_y = (height.max(0) as usize + block_height as usize - 1) / block_height as usize;
+    blocks_x.checked_mul(blocks_y)?.checked_mul(block_bytes)
+}
+
+/// Decode the base level of a DXT1/3/5 or ETC1 image to RGBA (32bit) colors
+pub(crate) fn decode_colors(image: &Image) -> Result<Vec<Color>> {
+    let decode_block: fn(&[u8]) -> [Color; 16] = match image.format {
+        PixelFormat::CompressedDxt1Rgb => |block| dxt_colors(block, Some(false)),
+        PixelFormat::CompressedDxt1Rgba => |block| dxt_colors(block, Some(true)),
+        PixelFormat::CompressedDxt3Rgba => decode_dxt3,
+        PixelFormat::CompressedDxt5Rgba => decode_dxt5,
+        PixelFormat::CompressedEtc1Rgb => decode_etc1,
+        format => return Err(Error::UnsupportedFormat(format)),
+    };
+    let (width, height) = (image.width, image.height);
+    let (_, _, block_bytes) = block_layout(image.format).expect("block format");
+    let size = level_size(image.format, width, height).ok_or_else(truncated)?;
+    if image.data_size == 0 {
+        return Err(Error::InvalidData(String::from(
+            "compressed data size is unknown, load the file with `load_compressed()`",
+        )));
+    }
+    if image.data_size < size {
+        return Err(truncated());
+    }
+    let data = unsafe { slice::from_raw_parts(image.data as *const u8, size) };
+    let blocks_x = (width + 3) / 4;
+    let mut colors = vec![Color::new(0, 0, 0, 0); (width * height) as usize];
+    for (i, block) in data.chunks_exact(block_bytes).enumerate() {
+        let (block_x, block_y) = (i as i32 % blocks_x * 4, i as i32 / blocks_x * 4);
+        for (j, &color) in decode_block(block).iter().enumerate() {
+            let (x, y) = (block_x + j as i32 % 4, block_y + j as i32 / 4);
+            if x < width && y < height {
+                colors[(y * width + x) as usize] = color;
+            }
+        }
+    }
+    Ok(colors)
+}
+
+fn rgb565(color: u16) -> [u32; 3] {
+    let (r, g, b) = (
+        u32::from(color >> 11),
+        u32::from(color >> 5 & 0x3f),
+        u32::from(color & 0x1f),
+    );
+    [r << 3 | r >> 2, g << 2 | g >> 4, b << 3 | b >> 2]
+}
+
+// DXT color block, `dxt1_alpha` enables DXT1 three color mode with index 3
+// black, transparent if `Some(true)`
+fn dxt_colors(block: &[u8], dxt1_alpha: Option<bool>) -> [Color; 16] {
+    let (c0, c1) = (
+        u16::from_le_bytes([block[0], block[1]]),
+        u16::from_le_bytes([block[2], block[3]]),
+    );
+    let (a, b) = (rgb565(c0), rgb565(c1));
+    let mix = |wa: u32, wb: u32| {
+        let channel = |i: usize| ((a[i] * wa + b[i] * wb) / (wa + wb)) as u8;
+        Color::new(channel(0), channel(1), channel(2), 255)
+    };
+    let palette = match dxt1_alpha {
+        Some(alpha) if c0 <= c1 => [
+            mix(1, 0),
+            mix(0, 1),
+            mix(1, 1),
+            Color::new(0, 0, 0, if alpha { 0 } else { 255 }),
+        ],
+        _ => [mix(1, 0), mix(0, 1), mix(2, 1), mix(1, 2)],
+    };
+    let indices = u32::from_le_bytes([block[4], block[5], block[6], block[7]]);
+    let mut colors = [Color::new(0, 0, 0, 0); 16];
+    for (i, color) in colors.iter_mut().enumerate() {
+        *color = palette[(indices >> (2 * i) & 3) as usize];
+    }
+    colors
+}
+
+fn decode_dxt3(block: &[u8]) -> [Color; 16] {
+    let mut colors = dxt_colors(&block[8..], None);
+    let mut alpha_bits = [0; 8];
+    alpha_bits.copy_from_slice(&block[..8]);
+    let alphas = u64::from_le_bytes(alpha_bits);
+    for (i, color) in colors.iter_mut().enumerate() {
+        color.a = (alphas >> (4 * i) & 0xf) as u8 * 17;
+    }
+    colors
+}
+
+fn decode_dxt5(block: &[u8]) -> [Color; 16] {
+    let mut colors = dxt_colors(&block[8..], None);
+    let (a0, a1) = (u32::from(block[0]), u32::from(block[1]));
+    let mut palette = [0u8; 8];
+    for (i, alpha) in palette.iter_mut().enumerate() {
+        let i = i as u32;
+        *alpha = match i {
+            0 => a0,
+            1 => a1,
+            _ if a0 > a1 => ((8 - i) * a0 + (i - 1) * a1) / 7,
+            6 => 0,
+            7 => 255,
+            _ => ((6 - i) * a0 + (i - 1) * a1) / 5,
+        } as u8;
+    }
+    let mut index_bits = [0; 8];
+    index_bits[..6].copy_from_slice(&block[2..8]);
+    let indices = u64::from_le_bytes(index_bits);
+    for (i, color) in colors.iter_mut().enumerate() {
+        color.a = palette[(indices >> (3 * i) & 7) as usize];
+    }
+    colors
+}
+
+fn decode_etc1(block: &[u8]) -> [Color; 16] {
+    let mut block_bits = [0; 8];
+    block_bits.copy_from_slice(&block[..8]);
+    let bits = u64::from_be_bytes(block_bits);
+    let (differential, flip) = (bits >> 33 & 1 == 1, bits >> 32 & 1 == 1);
+    let (mut base1, mut base2) = ([0i32; 3], [0i32; 3]);
+    for (c, shift) in [59, 51, 43].iter().enumerate() {
+        if differential {
+            let value = (bits >> shift & 0x1f) as i32;
+            let delta = (bits >> (shift - 3) & 0x7) as i32;
+            let value2 = value + if delta >= 4 { delta - 8 } else { delta };
+            base1[c] = value << 3 | value >> 2;
+            base2[c] = value2 << 3 | value2 >> 2;
+        } else {
+            base1[c] = (bits >> (shift + 1) & 0xf) as i32 * 17;
+            base2[c] = (bits >> (shift - 3) & 0xf) as i32 * 17;
+        }
+    }
+    let tables = [(bits >> 37 & 7) as usize, (bits >> 34 & 7) as usize];
+    let mut colors = [Color::new(0, 0, 0, 0); 16];
+    for (i, color) in colors.iter_mut().enumerate() {
+        let (x, y) = (i % 4, i / 4);
+        let second = if flip { y >= 2 } else { x >= 2 };
+        let (base, table) = if second {
+            (base2, tables[1])
+        } else {
+            (base1, tables[0])
+        };
+        // Pixel indices are stored column by column
+        let index = x * 4 + y;
+        let modifier = match (bits >> (16 + index) & 1, bits >> index & 1) {
+            (0, 0) => ETC1_MODIFIERS[table][0],
+            (0, _) => ETC1_MODIFIERS[table][1],
+            (_, 0) => -ETC1_MODIFIERS[table][0],
+            _ => -ETC1_MODIFIERS[table][1],
+        };
+        let channel = |c: usize| (base[c] + modifier).clamp(0, 255) as u8;
+        *color = Color::new(channel(0), channel(1), channel(2), 255);
+    }
+    colors
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(truncated)
+}
+
+fn truncated() -> Error {
+    Error::InvalidData(String::from("compressed texture data is truncated"))
+}
+
+fn unsupported(container: &str, format: &str) -> Error {
+    Error::InvalidData(format!(
+        "unsupported {} pixel format `{}`",
+        container, format
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn put_u32(data: &mut [u8], offset: usize, value: u32) {
+        data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    // Red and blue endpoints, pixels 1 and 2 use the second and third colors
+    const DXT1_BLOCK: [u8; 8] = [0x00, 0xf8, 0x1f, 0x00, 0b10_01_00, 0, 0, 0];
+
+    #[test]
+    fn dds_keeps_mipmaps_and_decodes_dxt1() {
+        let mut dds = vec![0u8; 128];
+        dds[..4].copy_from_slice(b"DDS ");
+        put_u32(&mut dds, 4, 124);
+        put_u32(&mut dds, 8, 0x1007 | 0x20000);
+        put_u32(&mut dds, 12, 4);
+        put_u32(&mut dds, 16, 8);
+        put_u32(&mut dds, 28, 2);
+        put_u32(&mut dds, 76, 32);
+        put_u32(&mut dds, 80, 0x4);
+        dds[84..88].copy_from_slice(b"DXT1");
+        // 8x4 base level is two blocks, 4x2 second level is one
+        for _ in 0..3 {
+            dds.extend_from_slice(&DXT1_BLOCK);
+        }
+
+        let info = inspect_compressed(&dds[..128]).unwrap();
+        assert_eq!(
+            info,
+            CompressedInfo {
+                container: Container::Dds,
+                format: PixelFormat::CompressedDxt1Rgb,
+                width: 8,
+                height: 4,
+                mipmaps: 2,
+            }
+        );
+        assert!(load_compressed(&dds[..140]).is_err());
+
+        let image = load_compressed(&dds).unwrap();
+        assert_eq!((image.width(), image.height()), (8, 4));
+        assert_eq!(image.mipmaps(), 2);
+        let colors = image.to_rgba8().unwrap().get_colors().unwrap();
+        assert_eq!(colors[0], Color::new(255, 0, 0, 255));
+        assert_eq!(colors[1], Color::new(0, 0, 255, 255));
+        assert_eq!(colors[2], Color::new(170, 0, 85, 255));
+        assert_eq!(colors[6], Color::new(170, 0, 85, 255));
+    }
+
+    #[test]
+    fn ktx_decodes_etc1() {
+        // Individual mode, base colors 136 and 0, table 0, pixel (0, 0) uses +8
+        let block: u64 = 8 << 60 | 8 << 52 | 8 << 44 | 1;
+        let mut ktx = vec![0u8; 64];
+        ktx[..12].copy_from_slice(&KTX_IDENTIFIER);
+        put_u32(&mut ktx, 12, 0x0403_0201);
+        put_u32(&mut ktx, 28, 0x8d64);
+        put_u32(&mut ktx, 36, 4);
+        put_u32(&mut ktx, 40, 4);
+        put_u32(&mut ktx, 52, 1);
+        put_u32(&mut ktx, 56, 1);
+        ktx.extend_from_slice(&8u32.to_le_bytes());
+        ktx.extend_from_slice(&block.to_be_bytes());
+
+        let image = load_compressed(&ktx).unwrap();
+        assert_eq!(image.format(), PixelFormat::CompressedEtc1Rgb);
+        assert_eq!(
+            image.get_colors().unwrap()[0],
+            Color::new(144, 144, 144, 255)
+        );
+        assert_eq!(
+            image.get_colors().unwrap()[1],
+            Color::new(138, 138, 138, 255)
+        );
+        assert_eq!(image.get_colors().unwrap()[2], Color::new(2, 2, 2, 255));
+
+        // Level size must match the declared size and format
+        let mut short = ktx[..64].to_vec();
+        put_u32(&mut short, 36, 256);
+        put_u32(&mut short, 40, 256);
+        short.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(load_compressed(&short).is_err());
+        put_u32(&mut ktx, 36, 0);
+        assert!(load_compressed(&ktx).is_err());
+    }
+
+    #[test]
+    fn clone_keeps_partial_blocks() {
+        // 2x2 DXT1 is one 8 byte block, raylib would size it as 2 bytes
+        let mut dds = vec![0u8; 128];
+        dds[..4].copy_from_slice(b"DDS ");
+        put_u32(&mut dds, 8, 0x1007);
+        put_u32(&mut dds, 12, 2);
+        put_u32(&mut dds, 16, 2);
+        dds[84..88].copy_from_slice(b"DXT1");
+        dds.extend_from_slice(&DXT1_BLOCK);
+        let image = load_compressed(&dds).unwrap();
+        let copy = image.clone();
+        assert_eq!(copy.data_size, 8);
+        assert_eq!(copy.get_colors().unwrap(), image.get_colors().unwrap());
+
+        // 5x3 ETC1 is two blocks
+        let mut ktx = vec![0u8; 64];
+        ktx[..12].copy_from_slice(&KTX_IDENTIFIER);
+        put_u32(&mut ktx, 12, 0x0403_0201);
+        put_u32(&mut ktx, 28, 0x8d64);
+        put_u32(&mut ktx, 36, 5);
+        put_u32(&mut ktx, 40, 3);
+        put_u32(&mut ktx, 52, 1);
+        put_u32(&mut ktx, 56, 1);
+        ktx.extend_from_slice(&16u32.to_le_bytes());
+        ktx.extend_from_slice(&[0x88; 16]);
+        let image = load_compressed(&ktx).unwrap();
+        let copy = image.clone();
+        assert_eq!(copy.get_colors().unwrap().len(), 15);
+        assert_eq!(copy.get_colors().unwrap(), image.get_colors().unwrap());
+
+        // Compressed data sized by raylib can't be decoded
+        let mut unknown = copy.clone();
+        unknown.data_size = 0;
+        assert!(unknown.get_colors().is_err());
+    }
+
+    #[test]
+    fn rejects_too_many_mipmaps() {
+        let mut dds = vec![0u8; 128];
+        dds[..4].copy_from_slice(b"DDS ");
+        put_u32(&mut dds, 8, 0x1007 | 0x20000);
+        put_u32(&mut dds, 12, 4);
+        put_u32(&mut dds, 16, 8);
+        dds[84..88].copy_from_slice(b"DXT1");
+        put_u32(&mut dds, 28, 4);
+        assert_eq!(inspect_compressed(&dds).unwrap().mipmaps, 4);
+        put_u32(&mut dds, 28, 40);
+        assert!(inspect_compressed(&dds).is_err());
+        put_u32(&mut dds, 28, u32::MAX);
+        assert!(inspect_compressed(&dds).is_err());
+    }
+
+    #[test]
+    fn pvr_decodes_dxt5_alpha() {
+        let mut pvr = vec![0u8; 52];
+        pvr[..4].copy_from_slice(b"PVR\x03");
+        put_u32(&mut pvr, 8, 11);
+        put_u32(&mut pvr, 24, 4);
+        put_u32(&mut pvr, 28, 4);
+        put_u32(&mut pvr, 44, 1);
+        // Alpha endpoints 255 and 0, pixels 1 and 2 use indices 1 and 2
+        pvr.extend_from_slice(&[255, 0, 1 << 3 | 2 << 6, 0, 0, 0, 0, 0]);
+        pvr.extend_from_slice(&DXT1_BLOCK);
+
+        let colors = load_compressed(&pvr).unwrap().get_colors().unwrap();
+        assert_eq!(colors[0].a, 255);
+        assert_eq!(colors[1].a, 0);
+        assert_eq!(colors[2].a, 218);
+    }
+
+    #[test]
+    fn astc_header() {
+        let mut astc = ASTC_MAGIC.to_vec();
+        astc.extend_from_slice(&[8, 8, 1, 16, 0, 0, 24, 0, 0, 1, 0, 0]);
+        let info = inspect_compressed(&astc).unwrap();
+        assert_eq!(info.format, PixelFormat::CompressedAstc8x8Rgba);
+        assert_eq!((info.width, info.height, info.mipmaps), (16, 24, 1));
+        astc.resize(16 + 6 * 16, 0);
+        let image = load_compressed(&astc).unwrap();
+        assert!(image.get_colors().is_err());
+        assert!(inspect_compressed(b"not a texture").is_err());
+    }
+}
@@ -1,22 +1,28 @@
 //! Window, Graphics Device and Input Handling Functions
 
 use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
 use std::os::raw::{c_int, c_uchar};
 use std::slice;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::{raw, BitFlags, Color, ConfigFlag, Image, LogType};
+use crate::{
+    raw, BitFlags, Camera, Color, ConfigFlag, Image, LogType, PixelFormat, Ray, RenderTexture2D,
+    Vector2,
+};
 
 //------------------------------------------------------------------------------
 // Window-related functions
 //------------------------------------------------------------------------------
 
-/// Initialize window and OpenGL context
-pub fn init_window<S: Into<String>>(width: i32, height: i32, title: S) {
+// Initialize window and OpenGL context, only through `Window`
+fn init_window<S: Into<String>>(width: i32, height: i32, title: S) {
     let raw_title = CString::new(title.into()).unwrap();
     unsafe { raw::InitWindow(width, height, raw_title.as_ptr()) }
 }
-/// Close window and unload OpenGL context
-pub fn close_window() {
+// Close window and unload OpenGL context, only through `Window`
+fn close_window() {
     unsafe { raw::CloseWindow() }
 }
 /// Check if window has been initialized successfully
@@ -36,9 +42,18 @@ pub fn toggle_fullscreen() {
     unsafe { raw::ToggleFullscreen() }
 }
 /// Set icon for window (only PLATFORM_DESKTOP)
-pub fn set_window_icon(image: Image) {
-    let raw_image = image.into_raw();
-    unsafe { raw::SetWindowIcon(raw_image) }
+///
+/// NOTE: Image is converted to R8G8B8A8 on a copy, raylib frees the pixels it converts
+pub fn set_window_icon(image: &Image) -> crate::Result<()> {
+    let format = PixelFormat::UncompressedR8g8b8a8;
+    if image.format() == format {
+        unsafe { raw::SetWindowIcon(image.as_raw()) }
+        return Ok(());
+    }
+    let mut icon = image.clone();
+    icon.set_format(format)?;
+    unsafe { raw::SetWindowIcon(icon.as_raw()) }
+    Ok(())
 }
 /// Set title for window (only PLATFORM_DESKTOP)
 pub fn set_window_title<S: Into<String>>(title: S) {
@@ -70,6 +85,46 @@ pub fn get_screen_height() -> i32 {
     unsafe { raw::GetScreenHeight() }
 }
 
+/// Window handle, initializes window and OpenGL context on creation, closes on drop
+///
+/// NOTE: GPU resources borrow the window, so they can't outlive the context
+#[derive(Debug)]
+pub struct Window {
+    // Not Send or Sync, the OpenGL context belongs to the creating thread
+    _private: PhantomData<*const ()>,
+}
+impl Window {
+    /// Initialize window and OpenGL context
+    ///
+    /// NOTE: Panics if a window is already open
+    pub fn init<S: Into<String>>(width: i32, height: i32, title: S) -> Window {
+        assert!(
+            !WINDOW_OPEN.swap(true, Ordering::SeqCst),
+            "window is already initialized"
+        );
+        init_window(width, height, title);
+        Window {
+            _private: PhantomData,
+        }
+    }
+    /// Setup canvas (framebuffer) to start drawing, ends drawing when the handle drops
+    ///
+    /// NOTE: Panics if drawing has already begun, the handle can't outlive the window
+    pub fn begin_drawing(&self) -> DrawHandle<'_> {
+        DrawHandle::begin()
+    }
+}
+impl Drop for Window {
+    /// Close window and unload OpenGL context
+    fn drop(&mut self) {
+        close_window();
+        WINDOW_OPEN.store(false, Ordering::SeqCst);
+    }
+}
+
+static WINDOW_OPEN: AtomicBool = AtomicBool::new(false);
+static DRAWING: AtomicBool = AtomicBool::new(false);
+
 //------------------------------------------------------------------------------
 // Cursor-related functions
 //------------------------------------------------------------------------------
@@ -104,14 +159,50 @@ pub fn clear_background(color: Color) {
     let raw_color = color.into_raw();
     unsafe { raw::ClearBackground(raw_color) }
 }
-/// Setup canvas (framebuffer) to start drawing
-pub fn begin_drawing() {
+// Setup canvas (framebuffer) to start drawing, only through `DrawHandle`
+fn begin_drawing() {
     unsafe { raw::BeginDrawing() }
 }
-/// End canvas drawing and swap buffers (double buffering)
-pub fn end_drawing() {
+// End canvas drawing and swap buffers (double buffering)
+fn end_drawing() {
     unsafe { raw::EndDrawing() }
 }
+/// Drawing handle, begins drawing on creation and ends drawing on drop
+#[derive(Debug)]
+#[must_use]
+pub struct DrawHandle<'w> {
+    // Textures queued draws still use, unloaded once the batch is flushed
+    temporary_textures: Vec<raw::Texture2D>,
+    _window: PhantomData<&'w Window>,
+}
+impl<'w> DrawHandle<'w> {
+    // Setup canvas (framebuffer) to start drawing, only through `Window`
+    fn begin() -> DrawHandle<'w> {
+        assert!(
+            !DRAWING.swap(true, Ordering::SeqCst),
+            "drawing has already begun"
+        );
+        begin_drawing();
+        DrawHandle {
+            temporary_textures: Vec::new(),
+            _window: PhantomData,
+        }
+    }
+    /// Unload a texture after drawing ends
+    pub(crate) fn unload_after_drawing(&mut self, texture: raw::Texture2D) {
+        self.temporary_textures.push(texture);
+    }
+}
+impl<'w> Drop for DrawHandle<'w> {
+    /// End canvas drawing and swap buffers (double buffering)
+    fn drop(&mut self) {
+        end_drawing();
+        for texture in self.temporary_textures.drain(..) {
+            unsafe { raw::UnloadTexture(texture) }
+        }
+        DRAWING.store(false, Ordering::SeqCst);
+    }
+}
 // /// Initialize 2D mode with custom camera (2D)
 // pub fn begin_mode_2d(camera: Camera2D) {
 //     unsafe { raw::BeginMode2D(camera) }
@@ -128,23 +219,61 @@ pub fn end_mode_2d() {
 pub fn end_mode_3d() {
     unsafe { raw::EndMode3D() }
 }
-// /// Initializes render texture for drawing
-// pub fn begin_texture_mode(target: RenderTexture2D) {
-//     unsafe { raw::BeginTextureMode(target) }
-// }
-/// Ends drawing to render texture
-pub fn end_texture_mode() {
+// Initializes render texture for drawing, only through `TextureModeHandle`
+fn begin_texture_mode(target: &mut RenderTexture2D) {
+    unsafe { raw::BeginTextureMode(target.as_raw()) }
+}
+// Ends drawing to render texture
+fn end_texture_mode() {
     unsafe { raw::EndTextureMode() }
 }
+impl<'w> DrawHandle<'w> {
+    /// Initializes render texture for drawing, ends texture mode when the handle drops
+    pub fn begin_texture_mode<'a>(
+        &'a mut self,
+        target: &'a mut RenderTexture2D,
+    ) -> TextureModeHandle<'a, 'w> {
+        begin_texture_mode(target);
+        TextureModeHandle { draw: self }
+    }
+}
+/// Texture mode handle, draws into a render texture until dropped
+///
+/// NOTE: Dereferences to the `DrawHandle`, the target stays borrowed while drawing
+#[derive(Debug)]
+#[must_use]
+pub struct TextureModeHandle<'a, 'w> {
+    draw: &'a mut DrawHandle<'w>,
+}
+impl<'a, 'w> Deref for TextureModeHandle<'a, 'w> {
+    type Target = DrawHandle<'w>;
+
+    fn deref(&self) -> &DrawHandle<'w> {
+        self.draw
+    }
+}
+impl<'a, 'w> DerefMut for TextureModeHandle<'a, 'w> {
+    fn deref_mut(&mut self) -> &mut DrawHandle<'w> {
+        self.draw
+    }
+}
+impl<'a, 'w> Drop for TextureModeHandle<'a, 'w> {
+    /// Ends drawing to render texture
+    fn drop(&mut self) {
+        end_texture_mode()
+    }
+}
 
 //------------------------------------------------------------------------------
 // Screen-space-related functions
 //------------------------------------------------------------------------------
 
-// /// Returns a ray trace from mouse position
-// pub fn get_mouse_ray(mouse_position: Vector2, camera: Camera) -> Ray {
-//     unsafe { raw::GetMouseRay(mouse_position, camera) }
-// }
+/// Returns a ray trace from mouse position
+pub fn get_mouse_ray(mouse_position: Vector2, camera: Camera) -> Ray {
+    let raw_mouse_position = mouse_position.into_raw();
+    let raw_camera = camera.into_raw();
+    Ray::from_raw(unsafe { raw::GetMouseRay(raw_mouse_position, raw_camera) })
+}
 // /// Returns the screen space position for a 3D world space position
 // pub fn get_world_to_screen(position: Vector3, camera: Camera) -> Vector2 {
 //     unsafe { raw::GetWorldToScreen(position, camera) }
@@ -170,7 +299,7 @@ pub fn get_fps() -> i32 {
 pub fn get_frame_time() -> f32 {
     unsafe { raw::GetFrameTime() }
 }
-/// Returns elapsed time in seconds since [`init_window`](fn.init_window.html)
+/// Returns elapsed time in seconds since [`Window::init`](struct.Window.html#method.init)
 pub fn get_time() -> f64 {
     unsafe { raw::GetTime() }
 }
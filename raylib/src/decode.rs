@@ -0,0 +1,205 @@
+//! In-memory Asset Decoding
+//!
+//! raylib 2.0 only loads images and waves from files, so data in memory is
+//! decoded here and handed to raylib as raw pixels or samples.
+
+use std::io::Cursor;
+use std::mem;
+use std::os::raw::c_int;
+
+use image::{DynamicImage, ImageFormat};
+
+use crate::{Error, Result};
+
+/// Decoded 16 bit interleaved audio samples
+pub struct DecodedWave {
+    pub samples: Vec<i16>,
+    pub sample_rate: u32,
+    pub channels: u32,
+}
+
+/// Decode a PNG, BMP, TGA, JPG, GIF or HDR file
+pub fn decode_image(bytes: &[u8], ext: &str) -> Result<DynamicImage> {
+    let ext = normalize(ext);
+    let format = match ext.as_str() {
+        "png" => ImageFormat::Png,
+        "bmp" => ImageFormat::Bmp,
+        "tga" => ImageFormat::Tga,
+        "jpg" | "jpeg" => ImageFormat::Jpeg,
+        "gif" => ImageFormat::Gif,
+        "hdr" => ImageFormat::Hdr,
+        _ => return Err(unsupported(&ext)),
+    };
+    image::load_from_memory_with_format(bytes, format).map_err(decode_failed)
+}
+
+/// Decode a WAV, OGG, FLAC or MP3 file
+pub fn decode_wave(bytes: &[u8], ext: &str) -> Result<DecodedWave> {
+    let ext = normalize(ext);
+    match ext.as_str() {
+        "wav" => decode_wav(bytes),
+        "ogg" => decode_ogg(bytes),
+        "flac" => decode_flac(bytes),
+        "mp3" => decode_mp3(bytes),
+        _ => Err(unsupported(&ext)),
+    }
+}
+
+fn decode_wav(bytes: &[u8]) -> Result<DecodedWave> {
+    let reader = hound::WavReader::new(Cursor::new(bytes)).map_err(decode_failed)?;
+    let spec = reader.spec();
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Int => reader
+            .into_samples::<i32>()
+            .map(|s| s.map(|s| to_i16(s, u32::from(spec.bits_per_sample))))
+            .collect::<std::result::Result<Vec<_>, _>>(),
+        hound::SampleFormat::Float => reader
+            .into_samples::<f32>()
+            .map(|s| s.map(|s| (s.clamp(-1.0, 1.0) * 32767.0) as i16))
+            .collect::<std::result::Result<Vec<_>, _>>(),
+    }
+    .map_err(decode_failed)?;
+    Ok(DecodedWave {
+        samples: samples,
+        sample_rate: spec.sample_rate,
+        channels: u32::from(spec.channels),
+    })
+}
+
+fn decode_ogg(bytes: &[u8]) -> Result<DecodedWave> {
+    let mut reader =
+        lewton::inside_ogg::OggStreamReader::new(Cursor::new(bytes)).map_err(decode_failed)?;
+    let mut samples = Vec::new();
+    while let Some(packet) = reader.read_dec_packet_itl().map_err(decode_failed)? {
+        samples.extend_from_slice(&packet);
+    }
+    Ok(DecodedWave {
+        samples: samples,
+        sample_rate: reader.ident_hdr.audio_sample_rate,
+        channels: u32::from(reader.ident_hdr.audio_channels),
+    })
+}
+
+fn decode_flac(bytes: &[u8]) -> Result<DecodedWave> {
+    let mut reader = claxon::FlacReader::new(Cursor::new(bytes)).map_err(decode_failed)?;
+    let info = reader.streaminfo();
+    let samples = reader
+        .samples()
+        .map(|s| s.map(|s| to_i16(s, info.bits_per_sample)))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(decode_failed)?;
+    Ok(DecodedWave {
+        samples: samples,
+        sample_rate: info.sample_rate,
+        channels: info.channels,
+    })
+}
+
+fn decode_mp3(bytes: &[u8]) -> Result<DecodedWave> {
+    let mut decoder: minimp3_sys::mp3dec_t = unsafe { mem::zeroed() };
+    unsafe { minimp3_sys::mp3dec_init(&mut decoder) };
+    let mut pcm = [0i16; minimp3_sys::MINIMP3_MAX_SAMPLES_PER_FRAME as usize];
+    let mut samples = Vec::new();
+    let (mut sample_rate, mut channels) = (0, 0);
+    let mut remaining = bytes;
+    while !remaining.is_empty() {
+        let mut info: minimp3_sys::mp3dec_frame_info_t = unsafe { mem::zeroed() };
+        let frame_samples = unsafe {
+            minimp3_sys::mp3dec_decode_frame(
+                &mut decoder,
+                remaining.as_ptr(),
+                remaining.len().min(c_int::MAX as usize) as c_int,
+                pcm.as_mut_ptr(),
+                &mut info,
+            )
+        };
+        // No frame found in the rest of the data
+        if info.frame_bytes == 0 {
+            break;
+        }
+        if frame_samples > 0 {
+            sample_rate = info.hz as u32;
+            channels = info.channels as u32;
+            samples.extend_from_slice(&pcm[..frame_samples as usize * info.channels as usize]);
+        }
+        remaining = &remaining[info.frame_bytes as usize..];
+    }
+    if channels == 0 {
+        return Err(Error::DecodeFailed(String::from("no MP3 frames found")));
+    }
+    Ok(DecodedWave {
+        samples: samples,
+        sample_rate: sample_rate,
+        channels: channels,
+    })
+}
+
+// Scale an integer sample of the given bit depth to 16 bits
+fn to_i16(sample: i32, bits: u32) -> i16 {
+    if bits >= 16 {
+        (sample >> (bits - 16)) as i16
+    } else {
+        (sample << (16 - bits)) as i16
+    }
+}
+
+fn normalize(ext: &str) -> String {
+    ext.trim_start_matches('.').to_ascii_lowercase()
+}
+
+fn unsupported(ext: &str) -> Error {
+    Error::DecodeFailed(format!("unsupported file extension `{}`", ext))
+}
+
+fn decode_failed<E: std::fmt::Display>(error: E) -> Error {
+    Error::DecodeFailed(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Image, PixelFormat, Wave};
+
+    #[test]
+    fn image_from_png_bytes() {
+        let mut png = Vec::new();
+        let source =
+            image::RgbaImage::from_fn(3, 2, |x, y| image::Rgba([x as u8, y as u8, 7, 255]));
+        DynamicImage::ImageRgba8(source)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+
+        let image = Image::from_memory(&png, ".PNG").unwrap();
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image.format(), PixelFormat::UncompressedR8g8b8a8);
+        assert_eq!(image.get_pixel(2, 1).unwrap(), Color::new(2, 1, 7, 255));
+
+        assert!(Image::from_memory(&png[..8], "png").is_err());
+        assert!(Image::from_memory(&png, "psd").is_err());
+    }
+
+    #[test]
+    fn wave_from_wav_bytes() {
+        let mut wav = Vec::new();
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 22050,
+            bits_per_sample: 8,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::new(Cursor::new(&mut wav), spec).unwrap();
+        for sample in [1i8, -1, 2, -2, 3, -3].iter() {
+            writer.write_sample(*sample).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let decoded = decode_wave(&wav, "wav").unwrap();
+        assert_eq!(decoded.samples, vec![256, -256, 512, -512, 768, -768]);
+
+        let wave = Wave::from_memory(&wav, "wav").unwrap();
+        assert_eq!(wave.sample_count(), 3);
+        assert_eq!(wave.sample_rate(), 22050);
+        assert_eq!((wave.sample_size(), wave.channels()), (16, 2));
+        assert!(Wave::from_memory(&wav, "mp3").is_err());
+    }
+}
@@ -0,0 +1,305 @@
+//! Shared 2D Drawing Trait
+//!
+//! Code written against `impl Draw2D` draws unchanged to the window through a
+//! [`DrawHandle`](../core/struct.DrawHandle.html) or into an
+//! [`Image`](../struct.Image.html) through an
+//! [`ImageCanvas`](../canvas/struct.ImageCanvas.html).
+
+use crate::canvas::{canvas_text_pixels, measure_canvas_text, ImageCanvas};
+use crate::core::{self, DrawHandle};
+use crate::{raw, shapes, Color, Error, Image, Rectangle, Result, Vector2};
+
+/// 2D drawing operations shared by the window and image canvases
+///
+/// NOTE: Text uses the canvas bitmap font on every target, so screen and image
+/// output match
+pub trait Draw2D {
+    /// Set background color
+    fn clear_background(&mut self, color: Color);
+    /// Draw a pixel
+    fn draw_pixel(&mut self, pos_x: i32, pos_y: i32, color: Color);
+    /// Draw a pixel (Vector version)
+    fn draw_pixel_v(&mut self, position: Vector2, color: Color) {
+        self.draw_pixel(position.x as i32, position.y as i32, color)
+    }
+    /// Draw a line
+    fn draw_line(
+        &mut self,
+        start_pos_x: i32,
+        start_pos_y: i32,
+        end_pos_x: i32,
+        end_pos_y: i32,
+        color: Color,
+    );
+    /// Draw a line (Vector version)
+    fn draw_line_v(&mut self, start_pos: Vector2, end_pos: Vector2, color: Color) {
+        self.draw_line(
+            start_pos.x as i32,
+            start_pos.y as i32,
+            end_pos.x as i32,
+            end_pos.y as i32,
+            color,
+        )
+    }
+    /// Draw a color-filled circle
+    fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color);
+    /// Draw a color-filled circle (Vector version)
+    fn draw_circle_v(&mut self, center: Vector2, radius: f32, color: Color) {
+        self.draw_circle(center.x as i32, center.y as i32, radius, color)
+    }
+    /// Draw circle outline
+    fn draw_circle_lines(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color);
+    /// Draw a color-filled rectangle
+    fn draw_rectangle(&mut self, pos_x: i32, pos_y: i32, width: i32, height: i32, color: Color);
+    /// Draw a color-filled rectangle
+    fn draw_rectangle_rec(&mut self, rec: Rectangle, color: Color) {
+        self.draw_rectangle(
+            rec.x as i32,
+            rec.y as i32,
+            rec.width as i32,
+            rec.height as i32,
+            color,
+        )
+    }
+    /// Draw rectangle outline
+    fn draw_rectangle_lines(
+        &mut self,
+        pos_x: i32,
+        pos_y: i32,
+        width: i32,
+        height: i32,
+        color: Color,
+    );
+    /// Draw a color-filled triangle
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color);
+    /// Draw triangle outline
+    fn draw_triangle_lines(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        self.draw_line_v(v1, v2, color);
+        self.draw_line_v(v2, v3, color);
+        self.draw_line_v(v3, v1, color);
+    }
+    /// Draw text (using the canvas bitmap font)
+    fn draw_text(&mut self, text: &str, pos_x: i32, pos_y: i32, font_size: i32, color: Color);
+    /// Measure string width for the canvas bitmap font
+    fn measure_text(&self, text: &str, font_size: i32) -> i32;
+    /// Draw a part of an image within a destination rectangle
+    ///
+    /// NOTE: On a `DrawHandle` every call uploads the image to a new GPU
+    /// texture that lives until drawing ends. Images drawn every frame should be
+    /// loaded once as a `Texture2D` and drawn with `draw_texture_pro()` instead.
+    fn draw_image(
+        &mut self,
+        image: &Image,
+        src_rec: Rectangle,
+        dst_rec: Rectangle,
+        tint: Color,
+    ) -> Result<()>;
+    /// Draw an image at position
+    fn draw_image_v(&mut self, image: &Image, position: Vector2, tint: Color) -> Result<()> {
+        let (width, height) = (image.width() as f32, image.height() as f32);
+        self.draw_image(
+            image,
+            Rectangle::new(0.0, 0.0, width, height),
+            Rectangle::new(position.x, position.y, width, height),
+            tint,
+        )
+    }
+}
+
+impl Draw2D for DrawHandle<'_> {
+    fn clear_background(&mut self, color: Color) {
+        core::clear_background(color)
+    }
+    fn draw_pixel(&mut self, pos_x: i32, pos_y: i32, color: Color) {
+        shapes::draw_pixel(pos_x, pos_y, color)
+    }
+    fn draw_pixel_v(&mut self, position: Vector2, color: Color) {
+        shapes::draw_pixel_v(position, color)
+    }
+    fn draw_line(
+        &mut self,
+        start_pos_x: i32,
+        start_pos_y: i32,
+        end_pos_x: i32,
+        end_pos_y: i32,
+        color: Color,
+    ) {
+        shapes::draw_line(start_pos_x, start_pos_y, end_pos_x, end_pos_y, color)
+    }
+    fn draw_line_v(&mut self, start_pos: Vector2, end_pos: Vector2, color: Color) {
+        shapes::draw_line_v(start_pos, end_pos, color)
+    }
+    fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        shapes::draw_circle(center_x, center_y, radius, color)
+    }
+    fn draw_circle_v(&mut self, center: Vector2, radius: f32, color: Color) {
+        shapes::draw_circle_v(center, radius, color)
+    }
+    fn draw_circle_lines(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        shapes::draw_circle_lines(center_x, center_y, radius, color)
+    }
+    fn draw_rectangle(&mut self, pos_x: i32, pos_y: i32, width: i32, height: i32, color: Color) {
+        shapes::draw_rectangle(pos_x, pos_y, width, height, color)
+    }
+    fn draw_rectangle_rec(&mut self, rec: Rectangle, color: Color) {
+        shapes::draw_rectangle_rec(rec, color)
+    }
+    fn draw_rectangle_lines(
+        &mut self,
+        pos_x: i32,
+        pos_y: i32,
+        width: i32,
+        height: i32,
+        color: Color,
+    ) {
+        shapes::draw_rectangle_lines(pos_x, pos_y, width, height, color)
+    }
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        shapes::draw_triangle(v1, v2, v3, color)
+    }
+    fn draw_triangle_lines(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        shapes::draw_triangle_lines(v1, v2, v3, color)
+    }
+    /// NOTE: Drawn as rectangles, use `text::draw_text()` for raylib's default font
+    fn draw_text(&mut self, text: &str, pos_x: i32, pos_y: i32, font_size: i32, color: Color) {
+        canvas_text_pixels(text, pos_x, pos_y, font_size, |x, y, size| {
+            shapes::draw_rectangle(x, y, size, size, color)
+        });
+    }
+    fn measure_text(&self, text: &str, font_size: i32) -> i32 {
+        measure_canvas_text(text, font_size)
+    }
+    /// NOTE: Uploads the image to a new temporary texture on every call, unloaded
+    /// after drawing ends. Drawing many images creates and destroys one texture
+    /// per call per frame, prefer a `Texture2D` for images drawn every frame.
+    fn draw_image(
+        &mut self,
+        image: &Image,
+        src_rec: Rectangle,
+        dst_rec: Rectangle,
+        tint: Color,
+    ) -> Result<()> {
+        let texture = unsafe { raw::LoadTextureFromImage(image.as_raw()) };
+        if texture.id == 0 {
+            return Err(Error::LoadFailed(String::from("<texture>")));
+        }
+        let origin = raw::Vector2 { x: 0.0, y: 0.0 };
+        unsafe {
+            raw::DrawTexturePro(
+                texture,
+                src_rec.into_raw(),
+                dst_rec.into_raw(),
+                origin,
+                0.0,
+                tint.into_raw(),
+            );
+        }
+        // The batch is only flushed at the end of drawing
+        self.unload_after_drawing(texture);
+        Ok(())
+    }
+}
+
+impl<'a> Draw2D for ImageCanvas<'a> {
+    fn clear_background(&mut self, color: Color) {
+        ImageCanvas::clear_background(self, color)
+    }
+    fn draw_pixel(&mut self, pos_x: i32, pos_y: i32, color: Color) {
+        ImageCanvas::draw_pixel(self, pos_x, pos_y, color)
+    }
+    fn draw_pixel_v(&mut self, position: Vector2, color: Color) {
+        ImageCanvas::draw_pixel_v(self, position, color)
+    }
+    fn draw_line(
+        &mut self,
+        start_pos_x: i32,
+        start_pos_y: i32,
+        end_pos_x: i32,
+        end_pos_y: i32,
+        color: Color,
+    ) {
+        ImageCanvas::draw_line(self, start_pos_x, start_pos_y, end_pos_x, end_pos_y, color)
+    }
+    fn draw_line_v(&mut self, start_pos: Vector2, end_pos: Vector2, color: Color) {
+        ImageCanvas::draw_line_v(self, start_pos, end_pos, color)
+    }
+    fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        ImageCanvas::draw_circle(self, center_x, center_y, radius, color)
+    }
+    fn draw_circle_v(&mut self, center: Vector2, radius: f32, color: Color) {
+        ImageCanvas::draw_circle_v(self, center, radius, color)
+    }
+    fn draw_circle_lines(&mut self, center_x: i32, center_y: i32, radius: f32, color: Color) {
+        ImageCanvas::draw_circle_lines(self, center_x, center_y, radius, color)
+    }
+    fn draw_rectangle(&mut self, pos_x: i32, pos_y: i32, width: i32, height: i32, color: Color) {
+        ImageCanvas::draw_rectangle(self, pos_x, pos_y, width, height, color)
+    }
+    fn draw_rectangle_rec(&mut self, rec: Rectangle, color: Color) {
+        ImageCanvas::draw_rectangle_rec(self, rec, color)
+    }
+    fn draw_rectangle_lines(
+        &mut self,
+        pos_x: i32,
+        pos_y: i32,
+        width: i32,
+        height: i32,
+        color: Color,
+    ) {
+        ImageCanvas::draw_rectangle_lines(self, pos_x, pos_y, width, height, color)
+    }
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        ImageCanvas::draw_triangle(self, v1, v2, v3, color)
+    }
+    fn draw_triangle_lines(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        ImageCanvas::draw_triangle_lines(self, v1, v2, v3, color)
+    }
+    fn draw_text(&mut self, text: &str, pos_x: i32, pos_y: i32, font_size: i32, color: Color) {
+        ImageCanvas::draw_text(self, text, pos_x, pos_y, font_size, color)
+    }
+    fn measure_text(&self, text: &str, font_size: i32) -> i32 {
+        measure_canvas_text(text, font_size)
+    }
+    fn draw_image(
+        &mut self,
+        image: &Image,
+        src_rec: Rectangle,
+        dst_rec: Rectangle,
+        tint: Color,
+    ) -> Result<()> {
+        ImageCanvas::draw_image(self, image, src_rec, dst_rec, tint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::textures::load_image_ex;
+
+    // Draw a HUD panel against any target
+    fn draw_panel<D: Draw2D>(d: &mut D, label: &str) {
+        let width = d.measure_text(label, 8) + 4;
+        d.draw_rectangle(0, 0, width, 12, Color::new(0, 0, 255, 255));
+        d.draw_rectangle_lines(0, 0, width, 12, Color::new(255, 255, 255, 255));
+        d.draw_text(label, 2, 2, 8, Color::new(255, 255, 0, 255));
+    }
+
+    #[test]
+    fn generic_code_draws_into_canvas() {
+        let mut image = load_image_ex(&[Color::new(0, 0, 0, 0); 32 * 16], 32, 16).unwrap();
+        draw_panel(&mut ImageCanvas::new(&mut image).unwrap(), "OK");
+        assert_eq!(
+            image.get_pixel(0, 0).unwrap(),
+            Color::new(255, 255, 255, 255)
+        );
+        assert_eq!(image.get_pixel(1, 1).unwrap(), Color::new(0, 0, 255, 255));
+        // 'O' top row starts one pixel in from the left of its cell
+        assert_eq!(image.get_pixel(3, 2).unwrap(), Color::new(255, 255, 0, 255));
+        // Panel is sized from the measured text
+        assert_eq!(
+            image.get_pixel(14, 5).unwrap(),
+            Color::new(255, 255, 255, 255)
+        );
+        assert_eq!(image.get_pixel(15, 5).unwrap(), Color::new(0, 0, 0, 0));
+    }
+}
@@ -0,0 +1,392 @@
+//! Robert Penner Easing Functions
+//!
+//! Port of raylib's `easings.h`. Every function takes the current time `t`,
+//! the beginning value `b`, the total change in value `c` and the duration `d`.
+
+use std::f32::consts::PI;
+
+//------------------------------------------------------------------------------
+// Linear easing functions
+//------------------------------------------------------------------------------
+
+/// Linear easing
+pub fn ease_linear_none(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    c * t / d + b
+}
+/// Linear easing (same as [`ease_linear_none`](fn.ease_linear_none.html))
+pub fn ease_linear_in(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    ease_linear_none(t, b, c, d)
+}
+/// Linear easing (same as [`ease_linear_none`](fn.ease_linear_none.html))
+pub fn ease_linear_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    ease_linear_none(t, b, c, d)
+}
+/// Linear easing (same as [`ease_linear_none`](fn.ease_linear_none.html))
+pub fn ease_linear_in_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    ease_linear_none(t, b, c, d)
+}
+
+//------------------------------------------------------------------------------
+// Sine easing functions
+//------------------------------------------------------------------------------
+
+/// Sine easing in
+pub fn ease_sine_in(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    -c * (t / d * (PI / 2.0)).cos() + c + b
+}
+/// Sine easing out
+pub fn ease_sine_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    c * (t / d * (PI / 2.0)).sin() + b
+}
+/// Sine easing in and out
+pub fn ease_sine_in_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    -c / 2.0 * ((PI * t / d).cos() - 1.0) + b
+}
+
+//------------------------------------------------------------------------------
+// Circular easing functions
+//------------------------------------------------------------------------------
+
+/// Circular easing in
+pub fn ease_circ_in(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let t = t / d;
+    -c * ((1.0 - t * t).sqrt() - 1.0) + b
+}
+/// Circular easing out
+pub fn ease_circ_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let t = t / d - 1.0;
+    c * (1.0 - t * t).sqrt() + b
+}
+/// Circular easing in and out
+pub fn ease_circ_in_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let t = t / (d / 2.0);
+    if t < 1.0 {
+        return -c / 2.0 * ((1.0 - t * t).sqrt() - 1.0) + b;
+    }
+    let t = t - 2.0;
+    c / 2.0 * ((1.0 - t * t).sqrt() + 1.0) + b
+}
+
+//------------------------------------------------------------------------------
+// Cubic easing functions
+//------------------------------------------------------------------------------
+
+/// Cubic easing in
+pub fn ease_cubic_in(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let t = t / d;
+    c * t * t * t + b
+}
+/// Cubic easing out
+pub fn ease_cubic_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let t = t / d - 1.0;
+    c * (t * t * t + 1.0) + b
+}
+/// Cubic easing in and out
+pub fn ease_cubic_in_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let t = t / (d / 2.0);
+    if t < 1.0 {
+        return c / 2.0 * t * t * t + b;
+    }
+    let t = t - 2.0;
+    c / 2.0 * (t * t * t + 2.0) + b
+}
+
+//------------------------------------------------------------------------------
+// Quadratic easing functions
+//------------------------------------------------------------------------------
+
+/// Quadratic easing in
+pub fn ease_quad_in(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let t = t / d;
+    c * t * t + b
+}
+/// Quadratic easing out
+pub fn ease_quad_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let t = t / d;
+    -c * t * (t - 2.0) + b
+}
+/// Quadratic easing in and out
+pub fn ease_quad_in_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let t = t / (d / 2.0);
+    if t < 1.0 {
+        return c / 2.0 * t * t + b;
+    }
+    -c / 2.0 * ((t - 1.0) * (t - 3.0) - 1.0) + b
+}
+
+//------------------------------------------------------------------------------
+// Exponential easing functions
+//------------------------------------------------------------------------------
+
+/// Exponential easing in
+pub fn ease_expo_in(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    if t == 0.0 {
+        b
+    } else {
+        c * 2.0f32.powf(10.0 * (t / d - 1.0)) + b
+    }
+}
+/// Exponential easing out
+pub fn ease_expo_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    if t == d {
+        b + c
+    } else {
+        c * (-(2.0f32.powf(-10.0 * t / d)) + 1.0) + b
+    }
+}
+/// Exponential easing in and out
+pub fn ease_expo_in_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    if t == 0.0 {
+        return b;
+    }
+    if t == d {
+        return b + c;
+    }
+    let t = t / (d / 2.0);
+    if t < 1.0 {
+        return c / 2.0 * 2.0f32.powf(10.0 * (t - 1.0)) + b;
+    }
+    c / 2.0 * (-(2.0f32.powf(-10.0 * (t - 1.0))) + 2.0) + b
+}
+
+//------------------------------------------------------------------------------
+// Back easing functions
+//------------------------------------------------------------------------------
+
+/// Back easing in
+pub fn ease_back_in(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let s = 1.70158;
+    let t = t / d;
+    c * t * t * ((s + 1.0) * t - s) + b
+}
+/// Back easing out
+pub fn ease_back_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let s = 1.70158;
+    let t = t / d - 1.0;
+    c * (t * t * ((s + 1.0) * t + s) + 1.0) + b
+}
+/// Back easing in and out
+pub fn ease_back_in_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let s = 1.70158 * 1.525;
+    let t = t / (d / 2.0);
+    if t < 1.0 {
+        return c / 2.0 * (t * t * ((s + 1.0) * t - s)) + b;
+    }
+    let t = t - 2.0;
+    c / 2.0 * (t * t * ((s + 1.0) * t + s) + 2.0) + b
+}
+
+//------------------------------------------------------------------------------
+// Bounce easing functions
+//------------------------------------------------------------------------------
+
+/// Bounce easing out
+pub fn ease_bounce_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    let t = t / d;
+    if t < 1.0 / 2.75 {
+        c * (7.5625 * t * t) + b
+    } else if t < 2.0 / 2.75 {
+        let t = t - 1.5 / 2.75;
+        c * (7.5625 * t * t + 0.75) + b
+    } else if t < 2.5 / 2.75 {
+        let t = t - 2.25 / 2.75;
+        c * (7.5625 * t * t + 0.9375) + b
+    } else {
+        let t = t - 2.625 / 2.75;
+        c * (7.5625 * t * t + 0.984375) + b
+    }
+}
+/// Bounce easing in
+pub fn ease_bounce_in(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    c - ease_bounce_out(d - t, 0.0, c, d) + b
+}
+/// Bounce easing in and out
+pub fn ease_bounce_in_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    if t < d / 2.0 {
+        ease_bounce_in(t * 2.0, 0.0, c, d) * 0.5 + b
+    } else {
+        ease_bounce_out(t * 2.0 - d, 0.0, c, d) * 0.5 + c * 0.5 + b
+    }
+}
+
+//------------------------------------------------------------------------------
+// Elastic easing functions
+//------------------------------------------------------------------------------
+
+/// Elastic easing in
+pub fn ease_elastic_in(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    if t == 0.0 {
+        return b;
+    }
+    let t = t / d;
+    if t == 1.0 {
+        return b + c;
+    }
+    let p = d * 0.3;
+    let s = p / 4.0;
+    let t = t - 1.0;
+    let post_fix = c * 2.0f32.powf(10.0 * t);
+    -(post_fix * ((t * d - s) * (2.0 * PI) / p).sin()) + b
+}
+/// Elastic easing out
+pub fn ease_elastic_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    if t == 0.0 {
+        return b;
+    }
+    let t = t / d;
+    if t == 1.0 {
+        return b + c;
+    }
+    let p = d * 0.3;
+    let s = p / 4.0;
+    c * 2.0f32.powf(-10.0 * t) * ((t * d - s) * (2.0 * PI) / p).sin() + c + b
+}
+/// Elastic easing in and out
+pub fn ease_elastic_in_out(t: f32, b: f32, c: f32, d: f32) -> f32 {
+    if t == 0.0 {
+        return b;
+    }
+    let t = t / (d / 2.0);
+    if t == 2.0 {
+        return b + c;
+    }
+    let p = d * (0.3 * 1.5);
+    let s = p / 4.0;
+    let t = t - 1.0;
+    if t < 0.0 {
+        let post_fix = c * 2.0f32.powf(10.0 * t);
+        return -0.5 * (post_fix * ((t * d - s) * (2.0 * PI) / p).sin()) + b;
+    }
+    let post_fix = c * 2.0f32.powf(-10.0 * t);
+    post_fix * ((t * d - s) * (2.0 * PI) / p).sin() * 0.5 + c + b
+}
+
+//------------------------------------------------------------------------------
+// Easing selection
+//------------------------------------------------------------------------------
+
+/// Easing curves, for use where the easing is chosen at runtime
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Easing {
+    #[default]
+    Linear,
+    SineIn,
+    SineOut,
+    SineInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+}
+impl Easing {
+    /// Evaluate the easing curve for a normalized time `t` in [`0.0f32..1.0f32`]
+    ///
+    /// NOTE: Back and elastic curves overshoot outside of [`0.0f32..1.0f32`]
+    pub fn apply(self, t: f32) -> f32 {
+        let f = match self {
+            Easing::Linear => ease_linear_none,
+            Easing::SineIn => ease_sine_in,
+            Easing::SineOut => ease_sine_out,
+            Easing::SineInOut => ease_sine_in_out,
+            Easing::CircIn => ease_circ_in,
+            Easing::CircOut => ease_circ_out,
+            Easing::CircInOut => ease_circ_in_out,
+            Easing::CubicIn => ease_cubic_in,
+            Easing::CubicOut => ease_cubic_out,
+            Easing::CubicInOut => ease_cubic_in_out,
+            Easing::QuadIn => ease_quad_in,
+            Easing::QuadOut => ease_quad_out,
+            Easing::QuadInOut => ease_quad_in_out,
+            Easing::ExpoIn => ease_expo_in,
+            Easing::ExpoOut => ease_expo_out,
+            Easing::ExpoInOut => ease_expo_in_out,
+            Easing::BackIn => ease_back_in,
+            Easing::BackOut => ease_back_out,
+            Easing::BackInOut => ease_back_in_out,
+            Easing::BounceIn => ease_bounce_in,
+            Easing::BounceOut => ease_bounce_out,
+            Easing::BounceInOut => ease_bounce_in_out,
+            Easing::ElasticIn => ease_elastic_in,
+            Easing::ElasticOut => ease_elastic_out,
+            Easing::ElasticInOut => ease_elastic_in_out,
+        };
+        f(t, 0.0, 1.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 25] = [
+        Easing::Linear,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+        Easing::CircIn,
+        Easing::CircOut,
+        Easing::CircInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+    ];
+
+    #[test]
+    fn curves_start_and_end_on_target() {
+        for easing in ALL.iter() {
+            assert!(easing.apply(0.0).abs() < 1e-3, "{:?} at 0", easing);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-3, "{:?} at 1", easing);
+        }
+    }
+
+    #[test]
+    fn in_out_curves_are_symmetric() {
+        for easing in [Easing::SineInOut, Easing::CubicInOut, Easing::QuadInOut].iter() {
+            assert!((easing.apply(0.5) - 0.5).abs() < 1e-5, "{:?}", easing);
+            assert!((easing.apply(0.25) + easing.apply(0.75) - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn penner_parameters() {
+        // Start at 10, change by 20 over 4 seconds
+        assert_eq!(ease_linear_none(1.0, 10.0, 20.0, 4.0), 15.0);
+        assert_eq!(ease_quad_in(2.0, 10.0, 20.0, 4.0), 15.0);
+        assert_eq!(ease_cubic_out(4.0, 10.0, 20.0, 4.0), 30.0);
+        assert!(ease_back_in(1.0, 0.0, 1.0, 4.0) < 0.0);
+        assert!(ease_elastic_out(0.5, 0.0, 1.0, 4.0) > 1.0);
+    }
+}
@@ -0,0 +1,81 @@
+//! Embedded Assets
+//!
+//! Bundle asset files into the executable at compile time and decode them when
+//! first needed:
+//!
+//! ```ignore
+//! static LOGO: EmbeddedAsset = embed_asset!("../assets/logo.png");
+//!
+//! let image = LOGO.load_image()?;
+//! ```
+
+#[cfg(feature = "decode")]
+use crate::{Image, Result, Wave};
+
+/// Asset file bytes embedded with [`embed_asset!`](../macro.embed_asset.html)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EmbeddedAsset {
+    path: &'static str,
+    bytes: &'static [u8],
+}
+impl EmbeddedAsset {
+    #[doc(hidden)]
+    pub const fn new(path: &'static str, bytes: &'static [u8]) -> EmbeddedAsset {
+        EmbeddedAsset {
+            path: path,
+            bytes: bytes,
+        }
+    }
+    /// Path the asset was embedded from
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+    /// Raw file bytes
+    pub fn bytes(&self) -> &'static [u8] {
+        self.bytes
+    }
+    /// File extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        let name = self.path.rsplit(['/', '\\']).next().unwrap_or("");
+        name.rfind('.').map_or("", |i| &name[i + 1..])
+    }
+    /// Decode the asset as an image
+    #[cfg(feature = "decode")]
+    pub fn load_image(&self) -> Result<Image> {
+        Image::from_memory(self.bytes, self.extension())
+    }
+    /// Decode the asset as a wave
+    #[cfg(feature = "decode")]
+    pub fn load_wave(&self) -> Result<Wave> {
+        Wave::from_memory(self.bytes, self.extension())
+    }
+}
+
+/// Embed an asset file at compile time, path is relative to the current file
+#[macro_export]
+macro_rules! embed_asset {
+    ($path:expr) => {
+        $crate::embed::EmbeddedAsset::new($path, include_bytes!($path))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static LOGO: EmbeddedAsset = embed_asset!("../../logo/raylib-rust_16x16.png");
+
+    #[test]
+    fn embedded_asset() {
+        assert_eq!(LOGO.extension(), "png");
+        assert_eq!(&LOGO.bytes()[1..4], b"PNG");
+        assert_eq!(EmbeddedAsset::new("a.b/c", &[]).extension(), "");
+    }
+
+    #[cfg(feature = "decode")]
+    #[test]
+    fn embedded_image_decodes() {
+        let image = LOGO.load_image().unwrap();
+        assert_eq!((image.width(), image.height()), (16, 16));
+    }
+}
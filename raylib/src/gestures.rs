@@ -9,6 +9,7 @@ use crate::{raw, BitFlags, Vector2};
 /// Gesture types
 #[repr(u32)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumFlags, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Gesture {
     Tap = 1,         // raw::Gestures::GESTURE_TAP
     DoubleTap = 2,   // raw::Gestures::GESTURE_DOUBLETAP
@@ -0,0 +1,217 @@
+//! Conversions to and from the `image` crate
+//!
+//! Enabled by the `image` cargo feature. Formats with a matching
+//! `image::ColorType` convert directly, the rest go through RGBA (32bit).
+
+#![cfg(feature = "image")]
+
+use std::convert::TryFrom;
+use std::slice;
+
+use image::{ColorType, DynamicImage, ImageBuffer, Pixel, RgbaImage};
+
+use crate::pixels::{R32g32b32, R32g32b32a32};
+use crate::textures::load_image_pro;
+use crate::{Error, Image, PixelFormat, Result};
+
+impl TryFrom<PixelFormat> for ColorType {
+    type Error = Error;
+
+    fn try_from(format: PixelFormat) -> Result<ColorType> {
+        match format {
+            PixelFormat::UncompressedGrayscale => Ok(ColorType::L8),
+            PixelFormat::UncompressedGrayAlpha => Ok(ColorType::La8),
+            PixelFormat::UncompressedR8g8b8 => Ok(ColorType::Rgb8),
+            PixelFormat::UncompressedR8g8b8a8 => Ok(ColorType::Rgba8),
+            PixelFormat::UncompressedR32g32b32 => Ok(ColorType::Rgb32F),
+            PixelFormat::UncompressedR32g32b32a32 => Ok(ColorType::Rgba32F),
+            format => Err(Error::UnsupportedFormat(format)),
+        }
+    }
+}
+
+impl TryFrom<ColorType> for PixelFormat {
+    type Error = Error;
+
+    fn try_from(color_type: ColorType) -> Result<PixelFormat> {
+        match color_type {
+            ColorType::L8 => Ok(PixelFormat::UncompressedGrayscale),
+            ColorType::La8 => Ok(PixelFormat::UncompressedGrayAlpha),
+            ColorType::Rgb8 => Ok(PixelFormat::UncompressedR8g8b8),
+            ColorType::Rgba8 => Ok(PixelFormat::UncompressedR8g8b8a8),
+            ColorType::Rgb32F => Ok(PixelFormat::UncompressedR32g32b32),
+            ColorType::Rgba32F => Ok(PixelFormat::UncompressedR32g32b32a32),
+            color_type => Err(Error::InvalidData(format!(
+                "no pixel format matches {:?}",
+                color_type
+            ))),
+        }
+    }
+}
+
+impl TryFrom<&DynamicImage> for Image {
+    type Error = Error;
+
+    /// NOTE: 16 bit images are converted to R8G8B8A8
+    fn try_from(image: &DynamicImage) -> Result<Image> {
+        let (width, height) = (image.width() as i32, image.height() as i32);
+        let format = PixelFormat::try_from(image.color());
+        let bytes = match image {
+            DynamicImage::ImageLuma8(buffer) => buffer.as_raw().as_slice(),
+            DynamicImage::ImageLumaA8(buffer) => buffer.as_raw().as_slice(),
+            DynamicImage::ImageRgb8(buffer) => buffer.as_raw().as_slice(),
+            DynamicImage::ImageRgba8(buffer) => buffer.as_raw().as_slice(),
+            DynamicImage::ImageRgb32F(buffer) => float_bytes(buffer.as_raw()),
+            DynamicImage::ImageRgba32F(buffer) => float_bytes(buffer.as_raw()),
+            other => {
+                let rgba = other.to_rgba8();
+                return load_image_pro(
+                    rgba.as_raw(),
+                    width,
+                    height,
+                    PixelFormat::UncompressedR8g8b8a8,
+                );
+            }
+        };
+        load_image_pro(bytes, width, height, format?)
+    }
+}
+
+impl TryFrom<DynamicImage> for Image {
+    type Error = Error;
+
+    fn try_from(image: DynamicImage) -> Result<Image> {
+        Image::try_from(&image)
+    }
+}
+
+impl TryFrom<&RgbaImage> for Image {
+    type Error = Error;
+
+    fn try_from(image: &RgbaImage) -> Result<Image> {
+        load_image_pro(
+            image.as_raw(),
+            image.width() as i32,
+            image.height() as i32,
+            PixelFormat::UncompressedR8g8b8a8,
+        )
+    }
+}
+
+impl TryFrom<&Image> for DynamicImage {
+    type Error = Error;
+
+    /// NOTE: Formats without a matching `ColorType` are converted to RGBA (32bit)
+    fn try_from(image: &Image) -> Result<DynamicImage> {
+        match image.format {
+            PixelFormat::UncompressedGrayscale => {
+                buffer(image, bytes(image).to_vec()).map(DynamicImage::ImageLuma8)
+            }
+            PixelFormat::UncompressedGrayAlpha => {
+                buffer(image, bytes(image).to_vec()).map(DynamicImage::ImageLumaA8)
+            }
+            PixelFormat::UncompressedR8g8b8 => {
+                buffer(image, bytes(image).to_vec()).map(DynamicImage::ImageRgb8)
+            }
+            PixelFormat::UncompressedR32g32b32 => {
+                let floats = image.view::<R32g32b32>()?.iter();
+                buffer(image, floats.flat_map(|p| [p.r, p.g, p.b]).collect())
+                    .map(DynamicImage::ImageRgb32F)
+            }
+            PixelFormat::UncompressedR32g32b32a32 => {
+                let floats = image.view::<R32g32b32a32>()?.iter();
+                buffer(image, floats.flat_map(|p| [p.r, p.g, p.b, p.a]).collect())
+                    .map(DynamicImage::ImageRgba32F)
+            }
+            _ => RgbaImage::try_from(image).map(DynamicImage::ImageRgba8),
+        }
+    }
+}
+
+impl TryFrom<&Image> for RgbaImage {
+    type Error = Error;
+
+    fn try_from(image: &Image) -> Result<RgbaImage> {
+        let data = match image.format {
+            PixelFormat::UncompressedR8g8b8a8 => bytes(image).to_vec(),
+            _ => image
+                .get_colors()?
+                .iter()
+                .flat_map(|c| [c.r, c.g, c.b, c.a])
+                .collect(),
+        };
+        buffer(image, data)
+    }
+}
+
+// Base level pixel data of an uncompressed image
+fn bytes(image: &Image) -> &[u8] {
+    let len = image.format.data_size(image.width, image.height);
+    unsafe { slice::from_raw_parts(image.data as *const u8, len) }
+}
+
+fn float_bytes(floats: &[f32]) -> &[u8] {
+    unsafe { slice::from_raw_parts(floats.as_ptr() as *const u8, floats.len() * 4) }
+}
+
+fn buffer<P: Pixel>(
+    image: &Image,
+    data: Vec<P::Subpixel>,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>> {
+    ImageBuffer::from_raw(image.width as u32, image.height as u32, data).ok_or_else(|| {
+        Error::InvalidData(format!(
+            "pixel data too short for a {}x{} image",
+            image.width, image.height
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    #[test]
+    fn format_mapping() {
+        for &format in &[
+            PixelFormat::UncompressedGrayscale,
+            PixelFormat::UncompressedR8g8b8a8,
+            PixelFormat::UncompressedR32g32b32,
+        ] {
+            let color_type = ColorType::try_from(format).unwrap();
+            assert_eq!(PixelFormat::try_from(color_type).unwrap(), format);
+        }
+        assert!(ColorType::try_from(PixelFormat::UncompressedR5g6b5).is_err());
+        assert!(PixelFormat::try_from(ColorType::Rgb16).is_err());
+    }
+
+    #[test]
+    fn dynamic_image_round_trip() {
+        let source = DynamicImage::ImageRgb8(image::RgbImage::from_fn(3, 2, |x, y| {
+            image::Rgb([x as u8, y as u8, 9])
+        }));
+        let image = Image::try_from(&source).unwrap();
+        assert_eq!(image.format(), PixelFormat::UncompressedR8g8b8);
+        assert_eq!(image.get_pixel(2, 1).unwrap(), Color::new(2, 1, 9, 255));
+        assert_eq!(DynamicImage::try_from(&image).unwrap(), source);
+
+        let wide = DynamicImage::ImageRgb16(image::ImageBuffer::new(2, 2));
+        let image = Image::try_from(wide).unwrap();
+        assert_eq!(image.format(), PixelFormat::UncompressedR8g8b8a8);
+    }
+
+    #[test]
+    fn rgba_image_from_any_format() {
+        let red: Vec<u8> = [0xf800u16; 4]
+            .iter()
+            .flat_map(|p| p.to_ne_bytes())
+            .collect();
+        let image = load_image_pro(&red, 2, 2, PixelFormat::UncompressedR5g6b5).unwrap();
+        let rgba = RgbaImage::try_from(&image).unwrap();
+        assert_eq!(rgba.get_pixel(1, 1), &image::Rgba([255, 0, 0, 255]));
+        assert_eq!(
+            DynamicImage::try_from(&image).unwrap().color(),
+            ColorType::Rgba8
+        );
+    }
+}
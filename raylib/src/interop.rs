@@ -0,0 +1,479 @@
+//! Conversions to and from third-party math libraries
+//!
+//! Each library is enabled by the cargo feature of the same name: `mint`,
+//! `glam`, `nalgebra` and `cgmath`. Colors convert to 4-component vectors
+//! normalized as [`0.0f32..1.0f32`], like [`Color::normalize`](../struct.Color.html#method.normalize).
+
+#![cfg(any(
+    feature = "mint",
+    feature = "glam",
+    feature = "nalgebra",
+    feature = "cgmath"
+))]
+
+use crate::{Color, Matrix, Quaternion, Vector2, Vector3, Vector4};
+
+fn color_to_normalized(color: Color) -> [f32; 4] {
+    [
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ]
+}
+
+fn color_from_normalized([r, g, b, a]: [f32; 4]) -> Color {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Color::new(channel(r), channel(g), channel(b), channel(a))
+}
+
+//------------------------------------------------------------------------------
+// mint
+//------------------------------------------------------------------------------
+
+#[cfg(feature = "mint")]
+mod mint_impls {
+    use super::*;
+
+    impl From<mint::Vector2<f32>> for Vector2 {
+        fn from(v: mint::Vector2<f32>) -> Vector2 {
+            Vector2::new(v.x, v.y)
+        }
+    }
+    impl From<Vector2> for mint::Vector2<f32> {
+        fn from(v: Vector2) -> mint::Vector2<f32> {
+            mint::Vector2 { x: v.x, y: v.y }
+        }
+    }
+    impl From<mint::Point2<f32>> for Vector2 {
+        fn from(p: mint::Point2<f32>) -> Vector2 {
+            Vector2::new(p.x, p.y)
+        }
+    }
+    impl From<Vector2> for mint::Point2<f32> {
+        fn from(v: Vector2) -> mint::Point2<f32> {
+            mint::Point2 { x: v.x, y: v.y }
+        }
+    }
+
+    impl From<mint::Vector3<f32>> for Vector3 {
+        fn from(v: mint::Vector3<f32>) -> Vector3 {
+            Vector3::new(v.x, v.y, v.z)
+        }
+    }
+    impl From<Vector3> for mint::Vector3<f32> {
+        fn from(v: Vector3) -> mint::Vector3<f32> {
+            mint::Vector3 {
+                x: v.x,
+                y: v.y,
+                z: v.z,
+            }
+        }
+    }
+    impl From<mint::Point3<f32>> for Vector3 {
+        fn from(p: mint::Point3<f32>) -> Vector3 {
+            Vector3::new(p.x, p.y, p.z)
+        }
+    }
+    impl From<Vector3> for mint::Point3<f32> {
+        fn from(v: Vector3) -> mint::Point3<f32> {
+            mint::Point3 {
+                x: v.x,
+                y: v.y,
+                z: v.z,
+            }
+        }
+    }
+
+    impl From<mint::Vector4<f32>> for Vector4 {
+        fn from(v: mint::Vector4<f32>) -> Vector4 {
+            Vector4::new(v.x, v.y, v.z, v.w)
+        }
+    }
+    impl From<Vector4> for mint::Vector4<f32> {
+        fn from(v: Vector4) -> mint::Vector4<f32> {
+            mint::Vector4 {
+                x: v.x,
+                y: v.y,
+                z: v.z,
+                w: v.w,
+            }
+        }
+    }
+
+    impl From<mint::Quaternion<f32>> for Quaternion {
+        fn from(q: mint::Quaternion<f32>) -> Quaternion {
+            Quaternion::new(q.v.x, q.v.y, q.v.z, q.s)
+        }
+    }
+    impl From<Quaternion> for mint::Quaternion<f32> {
+        fn from(q: Quaternion) -> mint::Quaternion<f32> {
+            mint::Quaternion {
+                v: mint::Vector3 {
+                    x: q.x,
+                    y: q.y,
+                    z: q.z,
+                },
+                s: q.w,
+            }
+        }
+    }
+
+    impl From<mint::ColumnMatrix4<f32>> for Matrix {
+        fn from(m: mint::ColumnMatrix4<f32>) -> Matrix {
+            let columns: [f32; 16] = m.into();
+            Matrix::from_array(columns)
+        }
+    }
+    impl From<Matrix> for mint::ColumnMatrix4<f32> {
+        fn from(m: Matrix) -> mint::ColumnMatrix4<f32> {
+            mint::ColumnMatrix4::from(m.to_array())
+        }
+    }
+
+    impl From<mint::Vector4<f32>> for Color {
+        fn from(v: mint::Vector4<f32>) -> Color {
+            color_from_normalized(v.into())
+        }
+    }
+    impl From<Color> for mint::Vector4<f32> {
+        fn from(color: Color) -> mint::Vector4<f32> {
+            color_to_normalized(color).into()
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// glam
+//------------------------------------------------------------------------------
+
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use super::*;
+
+    impl From<glam::Vec2> for Vector2 {
+        fn from(v: glam::Vec2) -> Vector2 {
+            Vector2::new(v.x, v.y)
+        }
+    }
+    impl From<Vector2> for glam::Vec2 {
+        fn from(v: Vector2) -> glam::Vec2 {
+            glam::Vec2::new(v.x, v.y)
+        }
+    }
+
+    impl From<glam::Vec3> for Vector3 {
+        fn from(v: glam::Vec3) -> Vector3 {
+            Vector3::new(v.x, v.y, v.z)
+        }
+    }
+    impl From<Vector3> for glam::Vec3 {
+        fn from(v: Vector3) -> glam::Vec3 {
+            glam::Vec3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl From<glam::Vec4> for Vector4 {
+        fn from(v: glam::Vec4) -> Vector4 {
+            Vector4::new(v.x, v.y, v.z, v.w)
+        }
+    }
+    impl From<Vector4> for glam::Vec4 {
+        fn from(v: Vector4) -> glam::Vec4 {
+            glam::Vec4::new(v.x, v.y, v.z, v.w)
+        }
+    }
+
+    impl From<glam::Quat> for Quaternion {
+        fn from(q: glam::Quat) -> Quaternion {
+            let [x, y, z, w] = q.to_array();
+            Quaternion::new(x, y, z, w)
+        }
+    }
+    impl From<Quaternion> for glam::Quat {
+        fn from(q: Quaternion) -> glam::Quat {
+            glam::Quat::from_xyzw(q.x, q.y, q.z, q.w)
+        }
+    }
+
+    impl From<glam::Mat4> for Matrix {
+        fn from(m: glam::Mat4) -> Matrix {
+            Matrix::from_array(m.to_cols_array())
+        }
+    }
+    impl From<Matrix> for glam::Mat4 {
+        fn from(m: Matrix) -> glam::Mat4 {
+            glam::Mat4::from_cols_array(&m.to_array())
+        }
+    }
+
+    impl From<glam::Vec4> for Color {
+        fn from(v: glam::Vec4) -> Color {
+            color_from_normalized(v.to_array())
+        }
+    }
+    impl From<Color> for glam::Vec4 {
+        fn from(color: Color) -> glam::Vec4 {
+            glam::Vec4::from_array(color_to_normalized(color))
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// nalgebra
+//------------------------------------------------------------------------------
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls {
+    use super::*;
+
+    impl From<nalgebra::Vector2<f32>> for Vector2 {
+        fn from(v: nalgebra::Vector2<f32>) -> Vector2 {
+            Vector2::new(v.x, v.y)
+        }
+    }
+    impl From<Vector2> for nalgebra::Vector2<f32> {
+        fn from(v: Vector2) -> nalgebra::Vector2<f32> {
+            nalgebra::Vector2::new(v.x, v.y)
+        }
+    }
+    impl From<nalgebra::Point2<f32>> for Vector2 {
+        fn from(p: nalgebra::Point2<f32>) -> Vector2 {
+            Vector2::new(p.x, p.y)
+        }
+    }
+    impl From<Vector2> for nalgebra::Point2<f32> {
+        fn from(v: Vector2) -> nalgebra::Point2<f32> {
+            nalgebra::Point2::new(v.x, v.y)
+        }
+    }
+
+    impl From<nalgebra::Vector3<f32>> for Vector3 {
+        fn from(v: nalgebra::Vector3<f32>) -> Vector3 {
+            Vector3::new(v.x, v.y, v.z)
+        }
+    }
+    impl From<Vector3> for nalgebra::Vector3<f32> {
+        fn from(v: Vector3) -> nalgebra::Vector3<f32> {
+            nalgebra::Vector3::new(v.x, v.y, v.z)
+        }
+    }
+    impl From<nalgebra::Point3<f32>> for Vector3 {
+        fn from(p: nalgebra::Point3<f32>) -> Vector3 {
+            Vector3::new(p.x, p.y, p.z)
+        }
+    }
+    impl From<Vector3> for nalgebra::Point3<f32> {
+        fn from(v: Vector3) -> nalgebra::Point3<f32> {
+            nalgebra::Point3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl From<nalgebra::Vector4<f32>> for Vector4 {
+        fn from(v: nalgebra::Vector4<f32>) -> Vector4 {
+            Vector4::new(v.x, v.y, v.z, v.w)
+        }
+    }
+    impl From<Vector4> for nalgebra::Vector4<f32> {
+        fn from(v: Vector4) -> nalgebra::Vector4<f32> {
+            nalgebra::Vector4::new(v.x, v.y, v.z, v.w)
+        }
+    }
+
+    impl From<nalgebra::Quaternion<f32>> for Quaternion {
+        fn from(q: nalgebra::Quaternion<f32>) -> Quaternion {
+            Quaternion::new(q.i, q.j, q.k, q.w)
+        }
+    }
+    impl From<Quaternion> for nalgebra::Quaternion<f32> {
+        fn from(q: Quaternion) -> nalgebra::Quaternion<f32> {
+            nalgebra::Quaternion::new(q.w, q.x, q.y, q.z)
+        }
+    }
+    impl From<nalgebra::UnitQuaternion<f32>> for Quaternion {
+        fn from(q: nalgebra::UnitQuaternion<f32>) -> Quaternion {
+            q.into_inner().into()
+        }
+    }
+    /// NOTE: The quaternion is normalized on the way in
+    impl From<Quaternion> for nalgebra::UnitQuaternion<f32> {
+        fn from(q: Quaternion) -> nalgebra::UnitQuaternion<f32> {
+            nalgebra::UnitQuaternion::from_quaternion(q.into())
+        }
+    }
+
+    impl From<nalgebra::Matrix4<f32>> for Matrix {
+        fn from(m: nalgebra::Matrix4<f32>) -> Matrix {
+            let mut array = [0.0; 16];
+            array.copy_from_slice(m.as_slice());
+            Matrix::from_array(array)
+        }
+    }
+    impl From<Matrix> for nalgebra::Matrix4<f32> {
+        fn from(m: Matrix) -> nalgebra::Matrix4<f32> {
+            nalgebra::Matrix4::from_column_slice(&m.to_array())
+        }
+    }
+
+    impl From<nalgebra::Vector4<f32>> for Color {
+        fn from(v: nalgebra::Vector4<f32>) -> Color {
+            color_from_normalized(v.into())
+        }
+    }
+    impl From<Color> for nalgebra::Vector4<f32> {
+        fn from(color: Color) -> nalgebra::Vector4<f32> {
+            color_to_normalized(color).into()
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// cgmath
+//------------------------------------------------------------------------------
+
+#[cfg(feature = "cgmath")]
+mod cgmath_impls {
+    use super::*;
+
+    impl From<cgmath::Vector2<f32>> for Vector2 {
+        fn from(v: cgmath::Vector2<f32>) -> Vector2 {
+            Vector2::new(v.x, v.y)
+        }
+    }
+    impl From<Vector2> for cgmath::Vector2<f32> {
+        fn from(v: Vector2) -> cgmath::Vector2<f32> {
+            cgmath::Vector2::new(v.x, v.y)
+        }
+    }
+    impl From<cgmath::Point2<f32>> for Vector2 {
+        fn from(p: cgmath::Point2<f32>) -> Vector2 {
+            Vector2::new(p.x, p.y)
+        }
+    }
+    impl From<Vector2> for cgmath::Point2<f32> {
+        fn from(v: Vector2) -> cgmath::Point2<f32> {
+            cgmath::Point2::new(v.x, v.y)
+        }
+    }
+
+    impl From<cgmath::Vector3<f32>> for Vector3 {
+        fn from(v: cgmath::Vector3<f32>) -> Vector3 {
+            Vector3::new(v.x, v.y, v.z)
+        }
+    }
+    impl From<Vector3> for cgmath::Vector3<f32> {
+        fn from(v: Vector3) -> cgmath::Vector3<f32> {
+            cgmath::Vector3::new(v.x, v.y, v.z)
+        }
+    }
+    impl From<cgmath::Point3<f32>> for Vector3 {
+        fn from(p: cgmath::Point3<f32>) -> Vector3 {
+            Vector3::new(p.x, p.y, p.z)
+        }
+    }
+    impl From<Vector3> for cgmath::Point3<f32> {
+        fn from(v: Vector3) -> cgmath::Point3<f32> {
+            cgmath::Point3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl From<cgmath::Vector4<f32>> for Vector4 {
+        fn from(v: cgmath::Vector4<f32>) -> Vector4 {
+            Vector4::new(v.x, v.y, v.z, v.w)
+        }
+    }
+    impl From<Vector4> for cgmath::Vector4<f32> {
+        fn from(v: Vector4) -> cgmath::Vector4<f32> {
+            cgmath::Vector4::new(v.x, v.y, v.z, v.w)
+        }
+    }
+
+    impl From<cgmath::Quaternion<f32>> for Quaternion {
+        fn from(q: cgmath::Quaternion<f32>) -> Quaternion {
+            Quaternion::new(q.v.x, q.v.y, q.v.z, q.s)
+        }
+    }
+    impl From<Quaternion> for cgmath::Quaternion<f32> {
+        fn from(q: Quaternion) -> cgmath::Quaternion<f32> {
+            cgmath::Quaternion::new(q.w, q.x, q.y, q.z)
+        }
+    }
+
+    impl From<cgmath::Matrix4<f32>> for Matrix {
+        fn from(m: cgmath::Matrix4<f32>) -> Matrix {
+            let columns: &[f32; 16] = m.as_ref();
+            Matrix::from_array(*columns)
+        }
+    }
+    impl From<Matrix> for cgmath::Matrix4<f32> {
+        fn from(m: Matrix) -> cgmath::Matrix4<f32> {
+            let a = m.to_array();
+            cgmath::Matrix4::new(
+                a[0], a[1], a[2], a[3], a[4], a[5], a[6], a[7], a[8], a[9], a[10], a[11], a[12],
+                a[13], a[14], a[15],
+            )
+        }
+    }
+
+    impl From<cgmath::Vector4<f32>> for Color {
+        fn from(v: cgmath::Vector4<f32>) -> Color {
+            color_from_normalized(v.into())
+        }
+    }
+    impl From<Color> for cgmath::Vector4<f32> {
+        fn from(color: Color) -> cgmath::Vector4<f32> {
+            color_to_normalized(color).into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mint")]
+    #[test]
+    fn mint_round_trip() {
+        use crate::{Matrix, Quaternion};
+        let m = Matrix::translate(1.0, 2.0, 3.0);
+        let mint_m: mint::ColumnMatrix4<f32> = m.into();
+        assert_eq!(mint_m.w.x, 1.0);
+        assert_eq!(Matrix::from(mint_m), m);
+        let q = Quaternion::new(0.1, 0.2, 0.3, 0.9);
+        let mint_q: mint::Quaternion<f32> = q.into();
+        assert_eq!(mint_q.s, 0.9);
+        assert_eq!(Quaternion::from(mint_q), q);
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn glam_matrix_agrees_on_translation() {
+        use crate::{Color, Matrix, Vector3};
+        let m: glam::Mat4 = Matrix::translate(1.0, 2.0, 3.0).into();
+        let p = m.transform_point3(glam::Vec3::ZERO);
+        assert_eq!(Vector3::from(p), Vector3::new(1.0, 2.0, 3.0));
+        let color: glam::Vec4 = Color::new(255, 0, 51, 255).into();
+        assert_eq!(color, glam::Vec4::new(1.0, 0.0, 0.2, 1.0));
+        assert_eq!(Color::from(color), Color::new(255, 0, 51, 255));
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn nalgebra_matrix_agrees_on_translation() {
+        use crate::{Matrix, Vector2, Vector3};
+        let m: nalgebra::Matrix4<f32> = Matrix::translate(1.0, 2.0, 3.0).into();
+        let p = m.transform_point(&nalgebra::Point3::origin());
+        assert_eq!(Vector3::from(p), Vector3::new(1.0, 2.0, 3.0));
+        let v: nalgebra::Vector2<f32> = Vector2::new(3.0, 4.0).into();
+        assert_eq!(v.norm(), 5.0);
+    }
+
+    #[cfg(feature = "cgmath")]
+    #[test]
+    fn cgmath_matrix_agrees_on_translation() {
+        use crate::{Matrix, Vector3};
+        use cgmath::Transform;
+        let m: cgmath::Matrix4<f32> = Matrix::translate(1.0, 2.0, 3.0).into();
+        let p = m.transform_point(cgmath::Point3::new(0.0, 0.0, 0.0));
+        assert_eq!(Vector3::from(p), Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(Matrix::from(m), Matrix::translate(1.0, 2.0, 3.0));
+    }
+}
@@ -11,11 +11,21 @@ extern crate enumflags_derive;
 extern crate num_derive;
 extern crate num_traits;
 extern crate raylib_sys as raw;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
 
+use std::error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
 use std::os::raw::{c_int, c_void};
+use std::slice;
 
 use num_traits::FromPrimitive;
 
+use crate::core::Window;
+
 //------------------------------------------------------------------------------
 // Re-exports
 //------------------------------------------------------------------------------
@@ -27,16 +37,38 @@ pub use enumflags::BitFlags;
 // Modules
 //------------------------------------------------------------------------------
 
+pub mod assets;
+pub mod atlas;
 pub mod audio;
 pub mod camera;
+pub mod canvas;
+pub mod collision;
 pub mod colors;
+pub mod compressed;
 pub mod core;
+#[cfg(feature = "decode")]
+mod decode;
+pub mod draw;
+pub mod easing;
+pub mod embed;
 pub mod gestures;
+mod image_interop;
+mod interop;
+pub mod loader;
+mod math;
 pub mod models;
+pub mod noise;
+pub mod npatch;
+pub mod pixels;
+#[cfg(feature = "serde")]
+pub mod serde_flags;
 pub mod shaders;
 pub mod shapes;
+pub mod sprite;
+pub mod testing;
 pub mod text;
 pub mod textures;
+pub mod tween;
 
 //------------------------------------------------------------------------------
 // Constants
@@ -51,7 +83,8 @@ pub const RAD2DEG: f64 = raw::RAD2DEG;
 //------------------------------------------------------------------------------
 
 /// Vector2 type
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Vector2 {
     pub x: f32,
     pub y: f32,
@@ -71,11 +104,112 @@ impl Vector2 {
     }
 }
 
-pub type Vector3 = raw::Vector3;
-pub type Vector4 = raw::Vector4;
+/// Vector3 type
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vector3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+impl Vector3 {
+    pub fn new(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3 { x: x, y: y, z: z }
+    }
+    fn from_raw(raw: raw::Vector3) -> Vector3 {
+        Vector3 {
+            x: raw.x,
+            y: raw.y,
+            z: raw.z,
+        }
+    }
+    fn into_raw(self) -> raw::Vector3 {
+        raw::Vector3 {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+}
+
+/// Vector4 type
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vector4 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+impl Vector4 {
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Vector4 {
+        Vector4 {
+            x: x,
+            y: y,
+            z: z,
+            w: w,
+        }
+    }
+    fn from_raw(raw: raw::Vector4) -> Vector4 {
+        Vector4 {
+            x: raw.x,
+            y: raw.y,
+            z: raw.z,
+            w: raw.w,
+        }
+    }
+}
+
+/// Matrix type (OpenGL style 4x4 - right handed, column major)
+///
+/// NOTE: Fields are declared in the same order as `raw::Matrix`, so element
+/// `m12`, `m13`, `m14` holds the translation
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Matrix {
+    pub m0: f32,
+    pub m4: f32,
+    pub m8: f32,
+    pub m12: f32,
+    pub m1: f32,
+    pub m5: f32,
+    pub m9: f32,
+    pub m13: f32,
+    pub m2: f32,
+    pub m6: f32,
+    pub m10: f32,
+    pub m14: f32,
+    pub m3: f32,
+    pub m7: f32,
+    pub m11: f32,
+    pub m15: f32,
+}
+/// Quaternion type
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Quaternion {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+impl Quaternion {
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Quaternion {
+        Quaternion {
+            x: x,
+            y: y,
+            z: z,
+            w: w,
+        }
+    }
+}
 
 /// Color type/functions, RGBA (32bit)
-#[derive(Debug, Copy, Clone)]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -115,12 +249,12 @@ impl Color {
     /// Returns color normalized as [`0.0f32..1.0f32`]
     pub fn normalize(self) -> Vector4 {
         let raw = self.into_raw();
-        unsafe { raw::ColorNormalize(raw) }
+        Vector4::from_raw(unsafe { raw::ColorNormalize(raw) })
     }
     /// Returns HSV values for a Color
     pub fn to_hsv(self) -> Vector3 {
         let raw = self.into_raw();
-        unsafe { raw::ColorToHSV(raw) }
+        Vector3::from_raw(unsafe { raw::ColorToHSV(raw) })
     }
     /// Returns a Color struct from hexadecimal value
     pub fn from_int(value: u32) -> Color {
@@ -133,33 +267,84 @@ impl Color {
     }
 }
 
+/// Rectangle type
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rectangle {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+impl Rectangle {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Rectangle {
+        Rectangle {
+            x: x,
+            y: y,
+            width: width,
+            height: height,
+        }
+    }
+    fn from_raw(raw: raw::Rectangle) -> Rectangle {
+        Rectangle::new(raw.x, raw.y, raw.width, raw.height)
+    }
+    fn into_raw(self) -> raw::Rectangle {
+        raw::Rectangle {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
 /// Image type, bpp always RGBA (32bit)
 ///
-/// NOTE: Data stored in CPU memory (RAM)
-#[derive(Debug, Clone)]
+/// NOTE: Data stored in CPU memory (RAM), freed on drop
 pub struct Image {
     /// Image raw data
     data: *mut c_void,
     /// Image base width
-    pub width: i32,
+    width: i32,
     /// Image base height
-    pub height: i32,
+    height: i32,
     /// Mipmap levels, 1 by default
-    pub mipmaps: i32,
+    mipmaps: i32,
     /// Data format (PixelFormat type)
-    pub format: PixelFormat,
+    format: PixelFormat,
+    /// Data size in bytes, 0 if unknown
+    data_size: usize,
 }
 impl Image {
     fn from_raw(raw: raw::Image) -> Image {
+        let format = PixelFormat::from_raw(raw.format);
         Image {
             data: raw.data,
             width: raw.width,
             height: raw.height,
             mipmaps: raw.mipmaps,
-            format: PixelFormat::from_raw(raw.format),
+            format: format,
+            data_size: Image::allocated_size(raw.width, raw.height, raw.mipmaps, format),
+        }
+    }
+    // Bytes raylib allocates for image data with all mipmap levels
+    //
+    // NOTE: raylib sizes compressed data from the file it was loaded from, it
+    // is recorded as unknown
+    fn allocated_size(width: i32, height: i32, mipmaps: i32, format: PixelFormat) -> usize {
+        if format.is_compressed() {
+            return 0;
         }
+        let (mut width, mut height, mut size) = (width, height, 0);
+        for _ in 0..mipmaps.max(1) {
+            size += format.data_size(width, height);
+            width = (width / 2).max(1);
+            height = (height / 2).max(1);
+        }
+        size
     }
-    fn into_raw(self) -> raw::Image {
+    /// Borrow as a raw image, ownership of the data stays with `self`
+    fn as_raw(&self) -> raw::Image {
         raw::Image {
             data: self.data,
             width: self.width,
@@ -168,6 +353,457 @@ impl Image {
             format: self.format as c_int,
         }
     }
+    /// Image base width
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+    /// Image base height
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+    /// Mipmap levels, 1 by default
+    pub fn mipmaps(&self) -> i32 {
+        self.mipmaps
+    }
+    /// Data format
+    pub fn format(&self) -> PixelFormat {
+        self.format
+    }
+}
+impl Clone for Image {
+    /// Create an image duplicate (useful for transformations)
+    ///
+    /// NOTE: Compressed data of known size is copied in full, raylib's
+    /// `ImageCopy()` doesn't round compressed levels up to whole blocks
+    fn clone(&self) -> Image {
+        if !self.format.is_compressed() || self.data_size == 0 {
+            return Image::from_raw(unsafe { raw::ImageCopy(self.as_raw()) });
+        }
+        let data = unsafe { slice::from_raw_parts(self.data as *const u8, self.data_size) };
+        let mut image = textures::load_image_pro(
+            data,
+            data.len() as i32,
+            1,
+            PixelFormat::UncompressedGrayscale,
+        )
+        .expect("failed to copy compressed image data");
+        image.width = self.width;
+        image.height = self.height;
+        image.mipmaps = self.mipmaps;
+        image.format = self.format;
+        image
+    }
+}
+impl Drop for Image {
+    /// Unload image from CPU memory (RAM)
+    fn drop(&mut self) {
+        if !self.data.is_null() {
+            unsafe { raw::UnloadImage(self.as_raw()) }
+        }
+    }
+}
+// Pixel data is a heap allocation owned by the image alone
+unsafe impl Send for Image {}
+impl fmt::Debug for Image {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Image")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("mipmaps", &self.mipmaps)
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+/// Texture2D type
+///
+/// NOTE: Data stored in GPU memory (VRAM), unloaded on drop, borrows the `Window`
+pub struct Texture2D<'w> {
+    /// OpenGL texture id
+    id: u32,
+    /// Texture base width
+    width: i32,
+    /// Texture base height
+    height: i32,
+    /// Mipmap levels, 1 by default
+    mipmaps: i32,
+    /// Data format (PixelFormat type)
+    format: PixelFormat,
+    _window: PhantomData<&'w Window>,
+}
+impl<'w> Texture2D<'w> {
+    fn from_raw(raw: raw::Texture2D) -> Texture2D<'w> {
+        Texture2D {
+            id: raw.id,
+            width: raw.width,
+            height: raw.height,
+            mipmaps: raw.mipmaps,
+            format: PixelFormat::from_raw(raw.format),
+            _window: PhantomData,
+        }
+    }
+    /// Borrow as a raw texture, ownership of the GPU data stays with `self`
+    fn as_raw(&self) -> raw::Texture2D {
+        raw::Texture2D {
+            id: self.id,
+            width: self.width,
+            height: self.height,
+            mipmaps: self.mipmaps,
+            format: self.format as c_int,
+        }
+    }
+    /// OpenGL texture id
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+    /// Texture base width
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+    /// Texture base height
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+    /// Mipmap levels, 1 by default
+    pub fn mipmaps(&self) -> i32 {
+        self.mipmaps
+    }
+    /// Data format
+    pub fn format(&self) -> PixelFormat {
+        self.format
+    }
+}
+impl<'w> Drop for Texture2D<'w> {
+    /// Unload texture from GPU memory (VRAM)
+    fn drop(&mut self) {
+        unsafe { raw::UnloadTexture(self.as_raw()) }
+    }
+}
+impl<'w> fmt::Debug for Texture2D<'w> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Texture2D")
+            .field("id", &self.id)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("mipmaps", &self.mipmaps)
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+/// RenderTexture2D type, for texture rendering
+///
+/// NOTE: Color texture and depth buffer are owned by the render texture and unloaded with it
+pub struct RenderTexture2D<'w> {
+    /// OpenGL Framebuffer Object (FBO) id
+    id: u32,
+    /// Color buffer attachment texture
+    texture: ManuallyDrop<Texture2D<'w>>,
+    /// Depth buffer attachment, a renderbuffer that can't be sampled
+    depth: raw::Texture2D,
+}
+impl<'w> RenderTexture2D<'w> {
+    fn from_raw(raw: raw::RenderTexture2D) -> RenderTexture2D<'w> {
+        RenderTexture2D {
+            id: raw.id,
+            texture: ManuallyDrop::new(Texture2D::from_raw(raw.texture)),
+            depth: raw.depth,
+        }
+    }
+    /// Borrow as a raw render texture, ownership of the GPU data stays with `self`
+    fn as_raw(&self) -> raw::RenderTexture2D {
+        raw::RenderTexture2D {
+            id: self.id,
+            texture: self.texture.as_raw(),
+            depth: self.depth,
+        }
+    }
+    /// OpenGL Framebuffer Object (FBO) id
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+    /// Color buffer attachment texture
+    pub fn texture(&self) -> &Texture2D<'w> {
+        &self.texture
+    }
+    /// OpenGL renderbuffer id of the depth buffer attachment
+    pub fn depth_id(&self) -> u32 {
+        self.depth.id
+    }
+}
+impl<'w> Drop for RenderTexture2D<'w> {
+    /// Unload render texture from GPU memory (VRAM)
+    fn drop(&mut self) {
+        unsafe { raw::UnloadRenderTexture(self.as_raw()) }
+    }
+}
+impl<'w> fmt::Debug for RenderTexture2D<'w> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RenderTexture2D")
+            .field("id", &self.id)
+            .field("texture", &*self.texture)
+            .field("depth_id", &self.depth.id)
+            .finish()
+    }
+}
+
+/// CharInfo type, font character info
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CharInfo {
+    /// Character value (Unicode)
+    pub value: i32,
+    /// Character rectangle in sprite font
+    pub rec: Rectangle,
+    /// Character offset X when drawing
+    pub offset_x: i32,
+    /// Character offset Y when drawing
+    pub offset_y: i32,
+    /// Character advance position X
+    pub advance_x: i32,
+}
+impl CharInfo {
+    fn from_raw(raw: &raw::CharInfo) -> CharInfo {
+        CharInfo {
+            value: raw.value,
+            rec: Rectangle::from_raw(raw.rec),
+            offset_x: raw.offsetX,
+            offset_y: raw.offsetY,
+            advance_x: raw.advanceX,
+        }
+    }
+}
+
+/// Font type, includes texture and charSet array data
+///
+/// NOTE: Unloaded on drop (except the default font), borrows the `Window`
+pub struct Font<'w> {
+    /// Font texture
+    texture: ManuallyDrop<Texture2D<'w>>,
+    /// Base size (default chars height)
+    base_size: i32,
+    /// Characters info data
+    chars: Vec<CharInfo>,
+    /// Characters info data, as loaded by raylib
+    raw_chars: *mut raw::CharInfo,
+    /// Unload on drop, false for the default font
+    owned: bool,
+}
+impl<'w> Font<'w> {
+    fn from_raw(raw: raw::Font, owned: bool) -> Font<'w> {
+        let chars = if raw.chars.is_null() {
+            Vec::new()
+        } else {
+            let raw_chars = unsafe { slice::from_raw_parts(raw.chars, raw.charsCount as usize) };
+            raw_chars.iter().map(CharInfo::from_raw).collect()
+        };
+        Font {
+            texture: ManuallyDrop::new(Texture2D::from_raw(raw.texture)),
+            base_size: raw.baseSize,
+            chars: chars,
+            raw_chars: raw.chars,
+            owned: owned,
+        }
+    }
+    /// Borrow as a raw font, ownership of the font data stays with `self`
+    fn as_raw(&self) -> raw::Font {
+        raw::Font {
+            texture: self.texture.as_raw(),
+            baseSize: self.base_size,
+            charsCount: self.chars.len() as c_int,
+            chars: self.raw_chars,
+        }
+    }
+    /// Font texture
+    pub fn texture(&self) -> &Texture2D<'w> {
+        &self.texture
+    }
+    /// Base size (default chars height)
+    pub fn base_size(&self) -> i32 {
+        self.base_size
+    }
+    /// Characters info data
+    pub fn chars(&self) -> &[CharInfo] {
+        &self.chars
+    }
+}
+impl<'w> Drop for Font<'w> {
+    /// Unload Font from GPU memory (VRAM)
+    fn drop(&mut self) {
+        if self.owned {
+            unsafe { raw::UnloadFont(self.as_raw()) }
+        }
+    }
+}
+impl<'w> fmt::Debug for Font<'w> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Font")
+            .field("texture", &*self.texture)
+            .field("base_size", &self.base_size)
+            .field("chars_count", &self.chars.len())
+            .field("owned", &self.owned)
+            .finish()
+    }
+}
+
+/// Wave type, defines audio wave data
+///
+/// NOTE: Data stored in CPU memory (RAM), freed on drop
+pub struct Wave {
+    /// Number of samples
+    sample_count: u32,
+    /// Frequency (samples per second)
+    sample_rate: u32,
+    /// Bit depth (bits per sample): 8, 16, 32 (24 not supported)
+    sample_size: u32,
+    /// Number of channels (1-mono, 2-stereo)
+    channels: u32,
+    /// Buffer data pointer
+    data: *mut c_void,
+}
+impl Wave {
+    fn from_raw(raw: raw::Wave) -> Wave {
+        Wave {
+            sample_count: raw.sampleCount,
+            sample_rate: raw.sampleRate,
+            sample_size: raw.sampleSize,
+            channels: raw.channels,
+            data: raw.data,
+        }
+    }
+    /// Borrow as a raw wave, ownership of the data stays with `self`
+    fn as_raw(&self) -> raw::Wave {
+        raw::Wave {
+            sampleCount: self.sample_count,
+            sampleRate: self.sample_rate,
+            sampleSize: self.sample_size,
+            channels: self.channels,
+            data: self.data,
+        }
+    }
+    /// Number of samples
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+    /// Frequency (samples per second)
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+    /// Bit depth (bits per sample)
+    pub fn sample_size(&self) -> u32 {
+        self.sample_size
+    }
+    /// Number of channels (1-mono, 2-stereo)
+    pub fn channels(&self) -> u32 {
+        self.channels
+    }
+}
+impl Clone for Wave {
+    /// Copy a wave to a new wave
+    fn clone(&self) -> Wave {
+        Wave::from_raw(unsafe { raw::WaveCopy(self.as_raw()) })
+    }
+}
+impl Drop for Wave {
+    /// Unload wave data
+    fn drop(&mut self) {
+        if !self.data.is_null() {
+            unsafe { raw::UnloadWave(self.as_raw()) }
+        }
+    }
+}
+// Sample data is a heap allocation owned by the wave alone
+unsafe impl Send for Wave {}
+impl fmt::Debug for Wave {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Wave")
+            .field("sample_count", &self.sample_count)
+            .field("sample_rate", &self.sample_rate)
+            .field("sample_size", &self.sample_size)
+            .field("channels", &self.channels)
+            .finish()
+    }
+}
+
+/// Camera type, defines a camera position/orientation in 3d space
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Camera3D {
+    /// Camera position
+    pub position: Vector3,
+    /// Camera target it looks-at
+    pub target: Vector3,
+    /// Camera up vector (rotation over its axis)
+    pub up: Vector3,
+    /// Camera field-of-view apperture in Y (degrees) in perspective, used as near plane width in orthographic
+    pub fovy: f32,
+    /// Camera type, defines projection type
+    pub camera_type: CameraType,
+}
+impl Camera3D {
+    fn into_raw(self) -> raw::Camera3D {
+        raw::Camera3D {
+            position: self.position.into_raw(),
+            target: self.target.into_raw(),
+            up: self.up.into_raw(),
+            fovy: self.fovy,
+            type_: self.camera_type as c_int,
+        }
+    }
+}
+
+/// Camera type fallback, defaults to Camera3D
+pub type Camera = Camera3D;
+
+/// Bounding box type
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BoundingBox {
+    /// Minimum vertex box-corner
+    pub min: Vector3,
+    /// Maximum vertex box-corner
+    pub max: Vector3,
+}
+impl BoundingBox {
+    pub fn new(min: Vector3, max: Vector3) -> BoundingBox {
+        BoundingBox { min: min, max: max }
+    }
+}
+
+/// Ray type (useful for raycast)
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ray {
+    /// Ray position (origin)
+    pub position: Vector3,
+    /// Ray direction
+    pub direction: Vector3,
+}
+impl Ray {
+    pub fn new(position: Vector3, direction: Vector3) -> Ray {
+        Ray {
+            position: position,
+            direction: direction,
+        }
+    }
+    fn from_raw(raw: raw::Ray) -> Ray {
+        Ray {
+            position: Vector3::from_raw(raw.position),
+            direction: Vector3::from_raw(raw.direction),
+        }
+    }
+}
+
+/// Raycast hit information
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RayHitInfo {
+    /// Distance to nearest hit
+    pub distance: f32,
+    /// Position of nearest hit
+    pub position: Vector3,
+    /// Surface normal of hit
+    pub normal: Vector3,
 }
 
 //------------------------------------------------------------------------------
@@ -177,6 +813,7 @@ impl Image {
 /// Config flags
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumFlags, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ConfigFlag {
     /// Set to show raylib logo at startup
     ShowLogo = 1, // raw::FLAG_SHOW_LOGO
@@ -197,6 +834,7 @@ pub enum ConfigFlag {
 /// Trace log types
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumFlags, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LogType {
     Info = 1,    // raw::LogType::LOG_INFO
     Warning = 2, // raw::LogType::LOG_WARNING
@@ -210,6 +848,7 @@ pub enum LogType {
 /// NOTE: Support depends on OpenGL version and platform
 #[repr(u32)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PixelFormat {
     /// 8 bit per pixel (no alpha)
     UncompressedGrayscale = raw::PixelFormat::UNCOMPRESSED_GRAYSCALE,
@@ -258,4 +897,84 @@ impl PixelFormat {
     fn from_raw(raw: c_int) -> PixelFormat {
         PixelFormat::from_i32(raw).expect(&format!("Invalid PixelFormat value `{}`", raw))
     }
+    /// Get pixel data size in bytes (image or texture)
+    ///
+    /// NOTE: Size depends on fragment format
+    pub fn data_size(self, width: i32, height: i32) -> usize {
+        unsafe { raw::GetPixelDataSize(width, height, self as c_int) as usize }
+    }
+}
+
+/// Texture parameters: filter mode
+///
+/// NOTE: Anisotropic filtering falls back to trilinear if not supported
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TextureFilterMode {
+    /// No filter, just pixel aproximation
+    Point = raw::TextureFilterMode::FILTER_POINT,
+    /// Linear filtering
+    Bilinear = raw::TextureFilterMode::FILTER_BILINEAR,
+    /// Trilinear filtering (linear with mipmaps)
+    Trilinear = raw::TextureFilterMode::FILTER_TRILINEAR,
+    /// Anisotropic filtering 4x
+    Anisotropic4x = raw::TextureFilterMode::FILTER_ANISOTROPIC_4X,
+    /// Anisotropic filtering 8x
+    Anisotropic8x = raw::TextureFilterMode::FILTER_ANISOTROPIC_8X,
+    /// Anisotropic filtering 16x
+    Anisotropic16x = raw::TextureFilterMode::FILTER_ANISOTROPIC_16X,
+}
+
+/// Texture parameters: wrap mode
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TextureWrapMode {
+    Repeat = raw::TextureWrapMode::WRAP_REPEAT,
+    Clamp = raw::TextureWrapMode::WRAP_CLAMP,
+    Mirror = raw::TextureWrapMode::WRAP_MIRROR,
+}
+
+/// Camera projection modes
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CameraType {
+    Perspective = raw::CameraType::CAMERA_PERSPECTIVE,
+    Orthographic = raw::CameraType::CAMERA_ORTHOGRAPHIC,
+}
+
+//------------------------------------------------------------------------------
+// Errors
+//------------------------------------------------------------------------------
+
+/// Error type for fallible raylib operations
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// Resource could not be loaded from the given file
+    LoadFailed(String),
+    /// Resource could not be decoded from memory
+    DecodeFailed(String),
+    /// Resource could not be written to the given file
+    ExportFailed(String),
+    /// Provided data does not match the expected size or layout
+    InvalidData(String),
+    /// Operation is not supported for the pixel format
+    UnsupportedFormat(PixelFormat),
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::LoadFailed(file_name) => write!(f, "failed to load `{}`", file_name),
+            Error::DecodeFailed(reason) => write!(f, "failed to decode: {}", reason),
+            Error::ExportFailed(file_name) => write!(f, "failed to export `{}`", file_name),
+            Error::InvalidData(reason) => write!(f, "invalid data: {}", reason),
+            Error::UnsupportedFormat(format) => write!(f, "unsupported pixel format {:?}", format),
+        }
+    }
 }
+impl error::Error for Error {}
+
+/// Result type for fallible raylib operations
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,346 @@
+//! Background Asset Loading
+//!
+//! `AsyncLoader` decodes CPU-side data on worker threads, then runs the GPU
+//! upload step on the thread that owns the window, a few uploads per frame:
+//!
+//! ```ignore
+//! let mut loader = AsyncLoader::new(4);
+//! let background = loader.load_texture(&window, "background.png");
+//! let music = loader.load_wave("theme.ogg");
+//! while loader.pending() > 0 {
+//!     loader.update(Duration::from_millis(4));
+//!     let mut d = window.begin_drawing();
+//!     draw_progress_bar(&mut d, loader.progress());
+//! }
+//! let background = background.take().unwrap()?;
+//! ```
+//!
+//! Other data can be loaded with `spawn()` and a custom decode and upload
+//! step. Dropping the loader skips loads that haven't started decoding.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::audio::load_wave;
+use crate::core::Window;
+use crate::textures::load_image;
+use crate::{Error, Font, Image, Result, Texture2D, Wave};
+
+type Decoded = Box<dyn Any + Send>;
+type Upload<'w> = Box<dyn FnOnce(Decoded) + 'w>;
+
+struct Job {
+    id: u64,
+    decode: Box<dyn FnOnce() -> Decoded + Send>,
+}
+
+/// Result of a background load, filled in by `AsyncLoader::update()`
+pub struct Pending<T> {
+    slot: Rc<RefCell<Option<Result<T>>>>,
+}
+impl<T> Pending<T> {
+    /// Check if the load has finished (successfully or not)
+    pub fn is_ready(&self) -> bool {
+        self.slot.borrow().is_some()
+    }
+    /// Take the result once ready, `None` while loading or if already taken
+    pub fn take(&self) -> Option<Result<T>> {
+        self.slot.borrow_mut().take()
+    }
+}
+impl<T> fmt::Debug for Pending<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pending")
+            .field("ready", &self.is_ready())
+            .finish()
+    }
+}
+
+/// Worker thread pool with a main-thread upload queue
+pub struct AsyncLoader<'w> {
+    jobs: Option<Sender<Job>>,
+    decoded: Receiver<(u64, Decoded)>,
+    workers: Vec<JoinHandle<()>>,
+    // Set on drop, workers skip the jobs still queued
+    cancelled: Arc<AtomicBool>,
+    // Upload steps waiting for their decode to finish
+    uploads: HashMap<u64, Upload<'w>>,
+    // Decoded data waiting for its upload step
+    ready: VecDeque<(u64, Decoded)>,
+    next_id: u64,
+    total: usize,
+    finished: usize,
+}
+impl<'w> AsyncLoader<'w> {
+    /// Start a loader with `threads` worker threads (at least one)
+    pub fn new(threads: usize) -> AsyncLoader<'w> {
+        let (job_sender, job_receiver) = mpsc::channel::<Job>();
+        let (decoded_sender, decoded_receiver) = mpsc::channel();
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let workers = (0..threads.max(1))
+            .map(|i| {
+                let jobs = job_receiver.clone();
+                let decoded = decoded_sender.clone();
+                let cancelled = cancelled.clone();
+                thread::Builder::new()
+                    .name(format!("raylib-loader-{}", i))
+                    .spawn(move || loop {
+                        // Hold the lock only while waiting for the next job
+                        let job = match jobs.lock().unwrap().recv() {
+                            Ok(job) => job,
+                            Err(_) => break,
+                        };
+                        if cancelled.load(Ordering::SeqCst) {
+                            continue;
+                        }
+                        if decoded.send((job.id, (job.decode)())).is_err() {
+                            break;
+                        }
+                    })
+                    .expect("failed to spawn loader thread")
+            })
+            .collect();
+        AsyncLoader {
+            jobs: Some(job_sender),
+            decoded: decoded_receiver,
+            workers: workers,
+            cancelled: cancelled,
+            uploads: HashMap::new(),
+            ready: VecDeque::new(),
+            next_id: 0,
+            total: 0,
+            finished: 0,
+        }
+    }
+    /// Run `decode` on a worker thread, then `upload` on this thread in `update()`
+    ///
+    /// NOTE: A panic in `decode` is reported as `Error::LoadFailed`
+    pub fn spawn<D, T, F, U>(&mut self, decode: F, upload: U) -> Pending<T>
+    where
+        D: Send + 'static,
+        T: 'w,
+        F: FnOnce() -> Result<D> + Send + 'static,
+        U: FnOnce(D) -> Result<T> + 'w,
+    {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.total += 1;
+
+        let slot = Rc::new(RefCell::new(None));
+        let upload_slot = slot.clone();
+        self.uploads.insert(
+            id,
+            Box::new(move |decoded: Decoded| {
+                let decoded = *decoded.downcast::<Result<D>>().unwrap();
+                *upload_slot.borrow_mut() = Some(decoded.and_then(upload));
+            }),
+        );
+        let job = Job {
+            id: id,
+            decode: Box::new(move || {
+                let result = panic::catch_unwind(AssertUnwindSafe(decode))
+                    .unwrap_or_else(|_| Err(Error::LoadFailed(String::from("<decode panicked>"))));
+                Box::new(result)
+            }),
+        };
+        self.jobs.as_ref().unwrap().send(job).unwrap();
+        Pending { slot: slot }
+    }
+    /// Load image data from file in the background
+    pub fn load_image<S: Into<String>>(&mut self, file_name: S) -> Pending<Image> {
+        let file_name = file_name.into();
+        self.spawn(move || load_image(file_name), Ok)
+    }
+    /// Load wave data from file in the background
+    pub fn load_wave<S: Into<String>>(&mut self, file_name: S) -> Pending<Wave> {
+        let file_name = file_name.into();
+        self.spawn(move || load_wave(file_name), Ok)
+    }
+    /// Load image data in the background and upload it as a texture
+    pub fn load_texture<S: Into<String>>(
+        &mut self,
+        window: &'w Window,
+        file_name: S,
+    ) -> Pending<Texture2D<'w>> {
+        let file_name = file_name.into();
+        self.spawn(
+            move || load_image(file_name),
+            move |image| window.load_texture_from_image(&image),
+        )
+    }
+    /// Read font file data in the background and load it as a font
+    ///
+    /// NOTE: raylib 2.0 rasterizes glyphs while loading the font, only the
+    /// file read runs on a worker thread
+    pub fn load_font<S: Into<String>>(
+        &mut self,
+        window: &'w Window,
+        file_name: S,
+        font_size: i32,
+        chars: &[char],
+    ) -> Pending<Font<'w>> {
+        let file_name = file_name.into();
+        let ext = Path::new(&file_name)
+            .extension()
+            .map(|ext| ext.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let chars = chars.to_vec();
+        self.spawn(
+            move || fs::read(&file_name).map_err(|e| Error::LoadFailed(e.to_string())),
+            move |data| Font::from_memory(window, &data, &ext, font_size, &chars),
+        )
+    }
+    /// Run upload steps for finished decodes, stops after `budget` has passed
+    ///
+    /// NOTE: Runs at least one upload if any are ready, returns the number run
+    pub fn update(&mut self, budget: Duration) -> usize {
+        let start = Instant::now();
+        self.ready.extend(self.decoded.try_iter());
+        let mut count = 0;
+        while let Some((id, decoded)) = self.ready.pop_front() {
+            let upload = self.uploads.remove(&id).unwrap();
+            upload(decoded);
+            self.finished += 1;
+            count += 1;
+            if start.elapsed() >= budget {
+                break;
+            }
+        }
+        count
+    }
+    /// Block until every queued load has decoded and uploaded
+    pub fn finish(&mut self) {
+        while self.pending() > 0 {
+            if self.ready.is_empty() {
+                let next = self.decoded.recv().unwrap();
+                self.ready.push_back(next);
+            }
+            self.update(Duration::from_secs(u64::MAX));
+        }
+    }
+    /// Number of loads not yet finished
+    pub fn pending(&self) -> usize {
+        self.total - self.finished
+    }
+    /// Fraction of queued loads that have finished, 1.0 when nothing is queued
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.finished as f32 / self.total as f32
+        }
+    }
+}
+impl<'w> Drop for AsyncLoader<'w> {
+    /// Skip queued loads, wait for running decodes and stop the worker threads
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.jobs.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+impl<'w> fmt::Debug for AsyncLoader<'w> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AsyncLoader")
+            .field("threads", &self.workers.len())
+            .field("total", &self.total)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::colors;
+    use crate::textures::load_image_ex;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn decode_then_upload() {
+        let mut loader = AsyncLoader::new(2);
+        assert_eq!(loader.progress(), 1.0);
+        let image = loader.spawn(
+            || load_image_ex(&[colors::RED; 4], 2, 2),
+            |image: Image| Ok((image.width(), thread::current().id())),
+        );
+        let failed = loader.spawn(|| -> Result<i32> { panic!("broken file") }, |_| Ok(()));
+        assert_eq!(loader.pending(), 2);
+        assert!(!image.is_ready());
+
+        loader.finish();
+        assert_eq!(loader.progress(), 1.0);
+        let (width, upload_thread) = image.take().unwrap().unwrap();
+        assert_eq!(width, 2);
+        assert_eq!(upload_thread, thread::current().id());
+        assert!(failed.take().unwrap().is_err());
+        assert!(image.take().is_none());
+    }
+
+    #[test]
+    fn upload_budget() {
+        let mut loader = AsyncLoader::new(1);
+        let pending: Vec<_> = (0..3).map(|i| loader.spawn(move || Ok(i), Ok)).collect();
+        // Wait for every decode without running uploads
+        while loader.ready.len() < 3 {
+            loader.ready.extend(loader.decoded.recv());
+        }
+        assert_eq!(loader.update(Duration::from_secs(0)), 1);
+        assert!((loader.progress() - 1.0 / 3.0).abs() < 1e-6);
+        assert_eq!(loader.update(Duration::from_secs(60)), 2);
+        let values: Vec<i32> = pending.iter().map(|p| p.take().unwrap().unwrap()).collect();
+        assert_eq!(values, [0, 1, 2]);
+    }
+
+    #[test]
+    fn drop_skips_queued_jobs() {
+        let decoded = Arc::new(AtomicUsize::new(0));
+        let mut loader = AsyncLoader::new(1);
+        let (started, wait_started) = mpsc::channel();
+        let (release, wait_release) = mpsc::channel::<()>();
+        loader.spawn(
+            move || {
+                started.send(()).unwrap();
+                wait_release.recv().unwrap();
+                Ok(())
+            },
+            Ok,
+        );
+        for _ in 0..3 {
+            let decoded = decoded.clone();
+            loader.spawn(
+                move || {
+                    decoded.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                },
+                Ok,
+            );
+        }
+        wait_started.recv().unwrap();
+        // Release the running decode once drop has cancelled the queue
+        let cancelled = loader.cancelled.clone();
+        let releaser = thread::spawn(move || {
+            while !cancelled.load(Ordering::SeqCst) {
+                thread::yield_now();
+            }
+            release.send(()).unwrap();
+        });
+        drop(loader);
+        releaser.join().unwrap();
+        assert_eq!(decoded.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,865 @@
+//! Vector, Matrix and Quaternion Math Functions
+//!
+//! NOTE: Pure Rust port of raymath, results match the C implementation
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::{Matrix, Quaternion, Vector2, Vector3};
+
+//------------------------------------------------------------------------------
+// Vector2 math
+//------------------------------------------------------------------------------
+
+impl Vector2 {
+    /// Vector with components value `0.0f32`
+    pub fn zero() -> Vector2 {
+        Vector2::new(0.0, 0.0)
+    }
+    /// Vector with components value `1.0f32`
+    pub fn one() -> Vector2 {
+        Vector2::new(1.0, 1.0)
+    }
+    /// Calculate vector length
+    pub fn length(self) -> f32 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
+    /// Calculate two vectors dot product
+    pub fn dot(self, v: Vector2) -> f32 {
+        self.x * v.x + self.y * v.y
+    }
+    /// Calculate distance between two vectors
+    pub fn distance(self, v: Vector2) -> f32 {
+        (self - v).length()
+    }
+    /// Calculate angle from two vectors in X-axis, in degrees
+    pub fn angle(self, v: Vector2) -> f32 {
+        let result = (v.y - self.y).atan2(v.x - self.x).to_degrees();
+        if result < 0.0 {
+            result + 360.0
+        } else {
+            result
+        }
+    }
+    /// Scale vector (multiply by value)
+    pub fn scale(self, scale: f32) -> Vector2 {
+        Vector2::new(self.x * scale, self.y * scale)
+    }
+    /// Normalize provided vector
+    pub fn normalize(self) -> Vector2 {
+        let length = self.length();
+        if length == 0.0 {
+            self
+        } else {
+            self.scale(1.0 / length)
+        }
+    }
+    /// Calculate linear interpolation between two vectors
+    pub fn lerp(self, v: Vector2, amount: f32) -> Vector2 {
+        Vector2::new(
+            self.x + amount * (v.x - self.x),
+            self.y + amount * (v.y - self.y),
+        )
+    }
+}
+impl Add for Vector2 {
+    type Output = Vector2;
+    fn add(self, v: Vector2) -> Vector2 {
+        Vector2::new(self.x + v.x, self.y + v.y)
+    }
+}
+impl Sub for Vector2 {
+    type Output = Vector2;
+    fn sub(self, v: Vector2) -> Vector2 {
+        Vector2::new(self.x - v.x, self.y - v.y)
+    }
+}
+impl Mul<f32> for Vector2 {
+    type Output = Vector2;
+    fn mul(self, scale: f32) -> Vector2 {
+        self.scale(scale)
+    }
+}
+impl Neg for Vector2 {
+    type Output = Vector2;
+    fn neg(self) -> Vector2 {
+        Vector2::new(-self.x, -self.y)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Vector3 math
+//------------------------------------------------------------------------------
+
+impl Vector3 {
+    /// Vector with components value `0.0f32`
+    pub fn zero() -> Vector3 {
+        Vector3::new(0.0, 0.0, 0.0)
+    }
+    /// Vector with components value `1.0f32`
+    pub fn one() -> Vector3 {
+        Vector3::new(1.0, 1.0, 1.0)
+    }
+    /// Calculate vector length
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+    /// Calculate two vectors dot product
+    pub fn dot(self, v: Vector3) -> f32 {
+        self.x * v.x + self.y * v.y + self.z * v.z
+    }
+    /// Calculate two vectors cross product
+    pub fn cross(self, v: Vector3) -> Vector3 {
+        Vector3::new(
+            self.y * v.z - self.z * v.y,
+            self.z * v.x - self.x * v.z,
+            self.x * v.y - self.y * v.x,
+        )
+    }
+    /// Calculate distance between two vectors
+    pub fn distance(self, v: Vector3) -> f32 {
+        (self - v).length()
+    }
+    /// Scale vector (multiply by value)
+    pub fn scale(self, scale: f32) -> Vector3 {
+        Vector3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+    /// Normalize provided vector
+    pub fn normalize(self) -> Vector3 {
+        let length = self.length();
+        if length == 0.0 {
+            self
+        } else {
+            self.scale(1.0 / length)
+        }
+    }
+    /// Calculate linear interpolation between two vectors
+    pub fn lerp(self, v: Vector3, amount: f32) -> Vector3 {
+        Vector3::new(
+            self.x + amount * (v.x - self.x),
+            self.y + amount * (v.y - self.y),
+            self.z + amount * (v.z - self.z),
+        )
+    }
+    /// Transforms a Vector3 by a given Matrix
+    pub fn transform(self, mat: Matrix) -> Vector3 {
+        let (x, y, z) = (self.x, self.y, self.z);
+        Vector3::new(
+            mat.m0 * x + mat.m4 * y + mat.m8 * z + mat.m12,
+            mat.m1 * x + mat.m5 * y + mat.m9 * z + mat.m13,
+            mat.m2 * x + mat.m6 * y + mat.m10 * z + mat.m14,
+        )
+    }
+    /// Transform a vector by quaternion rotation
+    pub fn rotate_by_quaternion(self, q: Quaternion) -> Vector3 {
+        let (x, y, z) = (self.x, self.y, self.z);
+        Vector3::new(
+            x * (q.x * q.x + q.w * q.w - q.y * q.y - q.z * q.z)
+                + y * (2.0 * q.x * q.y - 2.0 * q.w * q.z)
+                + z * (2.0 * q.x * q.z + 2.0 * q.w * q.y),
+            x * (2.0 * q.w * q.z + 2.0 * q.x * q.y)
+                + y * (q.w * q.w - q.x * q.x + q.y * q.y - q.z * q.z)
+                + z * (-2.0 * q.w * q.x + 2.0 * q.y * q.z),
+            x * (-2.0 * q.w * q.y + 2.0 * q.x * q.z)
+                + y * (2.0 * q.w * q.x + 2.0 * q.y * q.z)
+                + z * (q.w * q.w - q.x * q.x - q.y * q.y + q.z * q.z),
+        )
+    }
+}
+impl Add for Vector3 {
+    type Output = Vector3;
+    fn add(self, v: Vector3) -> Vector3 {
+        Vector3::new(self.x + v.x, self.y + v.y, self.z + v.z)
+    }
+}
+impl Sub for Vector3 {
+    type Output = Vector3;
+    fn sub(self, v: Vector3) -> Vector3 {
+        Vector3::new(self.x - v.x, self.y - v.y, self.z - v.z)
+    }
+}
+impl Mul<f32> for Vector3 {
+    type Output = Vector3;
+    fn mul(self, scale: f32) -> Vector3 {
+        self.scale(scale)
+    }
+}
+impl Neg for Vector3 {
+    type Output = Vector3;
+    fn neg(self) -> Vector3 {
+        Vector3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Matrix math
+//------------------------------------------------------------------------------
+
+impl Matrix {
+    /// Returns identity matrix
+    pub fn identity() -> Matrix {
+        Matrix::from_array([
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0,
+        ])
+    }
+    /// Creates a matrix from elements `m0..m15` in order (column major)
+    pub fn from_array(m: [f32; 16]) -> Matrix {
+        Matrix {
+            m0: m[0],
+            m1: m[1],
+            m2: m[2],
+            m3: m[3],
+            m4: m[4],
+            m5: m[5],
+            m6: m[6],
+            m7: m[7],
+            m8: m[8],
+            m9: m[9],
+            m10: m[10],
+            m11: m[11],
+            m12: m[12],
+            m13: m[13],
+            m14: m[14],
+            m15: m[15],
+        }
+    }
+    /// Returns elements `m0..m15` in order (column major), as expected by OpenGL
+    pub fn to_array(self) -> [f32; 16] {
+        [
+            self.m0, self.m1, self.m2, self.m3, //
+            self.m4, self.m5, self.m6, self.m7, //
+            self.m8, self.m9, self.m10, self.m11, //
+            self.m12, self.m13, self.m14, self.m15,
+        ]
+    }
+    /// Compute matrix determinant
+    pub fn determinant(self) -> f32 {
+        let [a00, a01, a02, a03, a10, a11, a12, a13, a20, a21, a22, a23, a30, a31, a32, a33] =
+            self.to_array();
+        a30 * a21 * a12 * a03 - a20 * a31 * a12 * a03 - a30 * a11 * a22 * a03
+            + a10 * a31 * a22 * a03
+            + a20 * a11 * a32 * a03
+            - a10 * a21 * a32 * a03
+            - a30 * a21 * a02 * a13
+            + a20 * a31 * a02 * a13
+            + a30 * a01 * a22 * a13
+            - a00 * a31 * a22 * a13
+            - a20 * a01 * a32 * a13
+            + a00 * a21 * a32 * a13
+            + a30 * a11 * a02 * a23
+            - a10 * a31 * a02 * a23
+            - a30 * a01 * a12 * a23
+            + a00 * a31 * a12 * a23
+            + a10 * a01 * a32 * a23
+            - a00 * a11 * a32 * a23
+            - a20 * a11 * a02 * a33
+            + a10 * a21 * a02 * a33
+            + a20 * a01 * a12 * a33
+            - a00 * a21 * a12 * a33
+            - a10 * a01 * a22 * a33
+            + a00 * a11 * a22 * a33
+    }
+    /// Returns the trace of the matrix (sum of the values along the diagonal)
+    pub fn trace(self) -> f32 {
+        self.m0 + self.m5 + self.m10 + self.m15
+    }
+    /// Transposes provided matrix
+    pub fn transpose(self) -> Matrix {
+        let m = self.to_array();
+        let mut result = [0.0; 16];
+        for i in 0..4 {
+            for j in 0..4 {
+                result[i * 4 + j] = m[j * 4 + i];
+            }
+        }
+        Matrix::from_array(result)
+    }
+    /// Invert provided matrix
+    ///
+    /// NOTE: Like raymath, a singular matrix produces non-finite elements
+    pub fn invert(self) -> Matrix {
+        let [a00, a01, a02, a03, a10, a11, a12, a13, a20, a21, a22, a23, a30, a31, a32, a33] =
+            self.to_array();
+
+        let b00 = a00 * a11 - a01 * a10;
+        let b01 = a00 * a12 - a02 * a10;
+        let b02 = a00 * a13 - a03 * a10;
+        let b03 = a01 * a12 - a02 * a11;
+        let b04 = a01 * a13 - a03 * a11;
+        let b05 = a02 * a13 - a03 * a12;
+        let b06 = a20 * a31 - a21 * a30;
+        let b07 = a20 * a32 - a22 * a30;
+        let b08 = a20 * a33 - a23 * a30;
+        let b09 = a21 * a32 - a22 * a31;
+        let b10 = a21 * a33 - a23 * a31;
+        let b11 = a22 * a33 - a23 * a32;
+
+        let inv_det = 1.0 / (b00 * b11 - b01 * b10 + b02 * b09 + b03 * b08 - b04 * b07 + b05 * b06);
+
+        Matrix::from_array([
+            (a11 * b11 - a12 * b10 + a13 * b09) * inv_det,
+            (-a01 * b11 + a02 * b10 - a03 * b09) * inv_det,
+            (a31 * b05 - a32 * b04 + a33 * b03) * inv_det,
+            (-a21 * b05 + a22 * b04 - a23 * b03) * inv_det,
+            (-a10 * b11 + a12 * b08 - a13 * b07) * inv_det,
+            (a00 * b11 - a02 * b08 + a03 * b07) * inv_det,
+            (-a30 * b05 + a32 * b02 - a33 * b01) * inv_det,
+            (a20 * b05 - a22 * b02 + a23 * b01) * inv_det,
+            (a10 * b10 - a11 * b08 + a13 * b06) * inv_det,
+            (-a00 * b10 + a01 * b08 - a03 * b06) * inv_det,
+            (a30 * b04 - a31 * b02 + a33 * b00) * inv_det,
+            (-a20 * b04 + a21 * b02 - a23 * b00) * inv_det,
+            (-a10 * b09 + a11 * b07 - a12 * b06) * inv_det,
+            (a00 * b09 - a01 * b07 + a02 * b06) * inv_det,
+            (-a30 * b03 + a31 * b01 - a32 * b00) * inv_det,
+            (a20 * b03 - a21 * b01 + a22 * b00) * inv_det,
+        ])
+    }
+    /// Returns translation matrix
+    pub fn translate(x: f32, y: f32, z: f32) -> Matrix {
+        Matrix::from_array([
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            x, y, z, 1.0,
+        ])
+    }
+    /// Create rotation matrix from axis and angle
+    ///
+    /// NOTE: Angle should be provided in radians
+    pub fn rotate(axis: Vector3, angle: f32) -> Matrix {
+        let axis = axis.normalize();
+        let (x, y, z) = (axis.x, axis.y, axis.z);
+        let (sinres, cosres) = angle.sin_cos();
+        let t = 1.0 - cosres;
+        Matrix::from_array([
+            x * x * t + cosres,
+            y * x * t + z * sinres,
+            z * x * t - y * sinres,
+            0.0,
+            x * y * t - z * sinres,
+            y * y * t + cosres,
+            z * y * t + x * sinres,
+            0.0,
+            x * z * t + y * sinres,
+            y * z * t - x * sinres,
+            z * z * t + cosres,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        ])
+    }
+    /// Returns x-rotation matrix (angle in radians)
+    pub fn rotate_x(angle: f32) -> Matrix {
+        let (sinres, cosres) = angle.sin_cos();
+        let mut result = Matrix::identity();
+        result.m5 = cosres;
+        result.m6 = sinres;
+        result.m9 = -sinres;
+        result.m10 = cosres;
+        result
+    }
+    /// Returns y-rotation matrix (angle in radians)
+    pub fn rotate_y(angle: f32) -> Matrix {
+        let (sinres, cosres) = angle.sin_cos();
+        let mut result = Matrix::identity();
+        result.m0 = cosres;
+        result.m2 = -sinres;
+        result.m8 = sinres;
+        result.m10 = cosres;
+        result
+    }
+    /// Returns z-rotation matrix (angle in radians)
+    pub fn rotate_z(angle: f32) -> Matrix {
+        let (sinres, cosres) = angle.sin_cos();
+        let mut result = Matrix::identity();
+        result.m0 = cosres;
+        result.m1 = sinres;
+        result.m4 = -sinres;
+        result.m5 = cosres;
+        result
+    }
+    /// Returns scaling matrix
+    pub fn scale(x: f32, y: f32, z: f32) -> Matrix {
+        Matrix::from_array([
+            x, 0.0, 0.0, 0.0, //
+            0.0, y, 0.0, 0.0, //
+            0.0, 0.0, z, 0.0, //
+            0.0, 0.0, 0.0, 1.0,
+        ])
+    }
+    /// Returns two matrix multiplication
+    ///
+    /// NOTE: As in raymath, the result applies `self` first and then `right`
+    pub fn multiply(self, right: Matrix) -> Matrix {
+        let l = self.to_array();
+        let r = right.to_array();
+        let mut result = [0.0; 16];
+        for i in 0..4 {
+            for j in 0..4 {
+                result[i * 4 + j] = (0..4).map(|k| l[i * 4 + k] * r[k * 4 + j]).sum();
+            }
+        }
+        Matrix::from_array(result)
+    }
+    /// Returns perspective projection matrix
+    pub fn frustum(left: f64, right: f64, bottom: f64, top: f64, near: f64, far: f64) -> Matrix {
+        let rl = right - left;
+        let tb = top - bottom;
+        let fne = far - near;
+        Matrix::from_array([
+            (near * 2.0 / rl) as f32,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            (near * 2.0 / tb) as f32,
+            0.0,
+            0.0,
+            ((right + left) / rl) as f32,
+            ((top + bottom) / tb) as f32,
+            (-(far + near) / fne) as f32,
+            -1.0,
+            0.0,
+            0.0,
+            (-(far * near * 2.0) / fne) as f32,
+            0.0,
+        ])
+    }
+    /// Returns perspective projection matrix
+    ///
+    /// NOTE: Angle should be provided in radians
+    pub fn perspective(fovy: f64, aspect: f64, near: f64, far: f64) -> Matrix {
+        let top = near * (fovy * 0.5).tan();
+        let right = top * aspect;
+        Matrix::frustum(-right, right, -top, top, near, far)
+    }
+    /// Returns orthographic projection matrix
+    pub fn ortho(left: f64, right: f64, bottom: f64, top: f64, near: f64, far: f64) -> Matrix {
+        let rl = right - left;
+        let tb = top - bottom;
+        let fne = far - near;
+        Matrix::from_array([
+            (2.0 / rl) as f32,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            (2.0 / tb) as f32,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            (-2.0 / fne) as f32,
+            0.0,
+            (-(left + right) / rl) as f32,
+            (-(top + bottom) / tb) as f32,
+            (-(far + near) / fne) as f32,
+            1.0,
+        ])
+    }
+    /// Returns camera look-at matrix (view matrix)
+    pub fn look_at(eye: Vector3, target: Vector3, up: Vector3) -> Matrix {
+        let z = (eye - target).normalize();
+        let x = up.cross(z).normalize();
+        let y = z.cross(x);
+        Matrix::from_array([
+            x.x,
+            y.x,
+            z.x,
+            0.0,
+            x.y,
+            y.y,
+            z.y,
+            0.0,
+            x.z,
+            y.z,
+            z.z,
+            0.0,
+            -x.dot(eye),
+            -y.dot(eye),
+            -z.dot(eye),
+            1.0,
+        ])
+    }
+}
+impl Mul for Matrix {
+    type Output = Matrix;
+    /// Same as [`Matrix::multiply`](struct.Matrix.html#method.multiply)
+    fn mul(self, right: Matrix) -> Matrix {
+        self.multiply(right)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Quaternion math
+//------------------------------------------------------------------------------
+
+impl Quaternion {
+    /// Returns identity quaternion
+    pub fn identity() -> Quaternion {
+        Quaternion::new(0.0, 0.0, 0.0, 1.0)
+    }
+    /// Computes the length of a quaternion
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+    fn dot(self, q: Quaternion) -> f32 {
+        self.x * q.x + self.y * q.y + self.z * q.z + self.w * q.w
+    }
+    fn scale(self, scale: f32) -> Quaternion {
+        Quaternion::new(
+            self.x * scale,
+            self.y * scale,
+            self.z * scale,
+            self.w * scale,
+        )
+    }
+    /// Normalize provided quaternion
+    pub fn normalize(self) -> Quaternion {
+        let length = self.length();
+        if length == 0.0 {
+            self
+        } else {
+            self.scale(1.0 / length)
+        }
+    }
+    /// Invert provided quaternion
+    pub fn invert(self) -> Quaternion {
+        let length_sq = self.dot(self);
+        if length_sq == 0.0 {
+            self
+        } else {
+            let i = 1.0 / length_sq;
+            Quaternion::new(-self.x * i, -self.y * i, -self.z * i, self.w * i)
+        }
+    }
+    /// Calculate two quaternion multiplication
+    pub fn multiply(self, q: Quaternion) -> Quaternion {
+        let (qax, qay, qaz, qaw) = (self.x, self.y, self.z, self.w);
+        let (qbx, qby, qbz, qbw) = (q.x, q.y, q.z, q.w);
+        Quaternion::new(
+            qax * qbw + qaw * qbx + qay * qbz - qaz * qby,
+            qay * qbw + qaw * qby + qaz * qbx - qax * qbz,
+            qaz * qbw + qaw * qbz + qax * qby - qay * qbx,
+            qaw * qbw - qax * qbx - qay * qby - qaz * qbz,
+        )
+    }
+    /// Calculate linear interpolation between two quaternions
+    pub fn lerp(self, q: Quaternion, amount: f32) -> Quaternion {
+        Quaternion::new(
+            self.x + amount * (q.x - self.x),
+            self.y + amount * (q.y - self.y),
+            self.z + amount * (q.z - self.z),
+            self.w + amount * (q.w - self.w),
+        )
+    }
+    /// Calculate slerp-optimized interpolation between two quaternions
+    pub fn nlerp(self, q: Quaternion, amount: f32) -> Quaternion {
+        self.lerp(q, amount).normalize()
+    }
+    /// Calculates spherical linear interpolation between two quaternions
+    pub fn slerp(self, q: Quaternion, amount: f32) -> Quaternion {
+        let mut q2 = q;
+        let mut cos_half_theta = self.dot(q2);
+        if cos_half_theta < 0.0 {
+            q2 = q2.scale(-1.0);
+            cos_half_theta = -cos_half_theta;
+        }
+
+        if cos_half_theta >= 1.0 {
+            self
+        } else if cos_half_theta > 0.95 {
+            self.nlerp(q2, amount)
+        } else {
+            let half_theta = cos_half_theta.acos();
+            let sin_half_theta = (1.0 - cos_half_theta * cos_half_theta).sqrt();
+            if sin_half_theta.abs() < 0.001 {
+                self.lerp(q2, 0.5)
+            } else {
+                let ratio_a = ((1.0 - amount) * half_theta).sin() / sin_half_theta;
+                let ratio_b = (amount * half_theta).sin() / sin_half_theta;
+                Quaternion::new(
+                    self.x * ratio_a + q2.x * ratio_b,
+                    self.y * ratio_a + q2.y * ratio_b,
+                    self.z * ratio_a + q2.z * ratio_b,
+                    self.w * ratio_a + q2.w * ratio_b,
+                )
+            }
+        }
+    }
+    /// Returns a quaternion for a given rotation matrix
+    pub fn from_matrix(mat: Matrix) -> Quaternion {
+        let trace = mat.m0 + mat.m5 + mat.m10;
+        if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            let inv_s = 1.0 / s;
+            Quaternion::new(
+                (mat.m6 - mat.m9) * inv_s,
+                (mat.m8 - mat.m2) * inv_s,
+                (mat.m1 - mat.m4) * inv_s,
+                s * 0.25,
+            )
+        } else if mat.m0 > mat.m5 && mat.m0 > mat.m10 {
+            let s = (1.0 + mat.m0 - mat.m5 - mat.m10).sqrt() * 2.0;
+            Quaternion::new(
+                s * 0.25,
+                (mat.m4 + mat.m1) / s,
+                (mat.m2 + mat.m8) / s,
+                (mat.m6 - mat.m9) / s,
+            )
+        } else if mat.m5 > mat.m10 {
+            let s = (1.0 + mat.m5 - mat.m0 - mat.m10).sqrt() * 2.0;
+            Quaternion::new(
+                (mat.m4 + mat.m1) / s,
+                s * 0.25,
+                (mat.m9 + mat.m6) / s,
+                (mat.m8 - mat.m2) / s,
+            )
+        } else {
+            let s = (1.0 + mat.m10 - mat.m0 - mat.m5).sqrt() * 2.0;
+            Quaternion::new(
+                (mat.m2 + mat.m8) / s,
+                (mat.m9 + mat.m6) / s,
+                s * 0.25,
+                (mat.m1 - mat.m4) / s,
+            )
+        }
+    }
+    /// Returns a matrix for a given quaternion
+    pub fn to_matrix(self) -> Matrix {
+        let (a2, b2, c2) = (self.x * self.x, self.y * self.y, self.z * self.z);
+        let (ac, ab, bc) = (self.x * self.z, self.x * self.y, self.y * self.z);
+        let (ad, bd, cd) = (self.w * self.x, self.w * self.y, self.w * self.z);
+        Matrix::from_array([
+            1.0 - 2.0 * (b2 + c2),
+            2.0 * (ab + cd),
+            2.0 * (ac - bd),
+            0.0,
+            2.0 * (ab - cd),
+            1.0 - 2.0 * (a2 + c2),
+            2.0 * (bc + ad),
+            0.0,
+            2.0 * (ac + bd),
+            2.0 * (bc - ad),
+            1.0 - 2.0 * (a2 + b2),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        ])
+    }
+    /// Returns rotation quaternion for an angle and axis
+    ///
+    /// NOTE: Angle must be provided in radians
+    pub fn from_axis_angle(axis: Vector3, angle: f32) -> Quaternion {
+        if axis.length() == 0.0 {
+            return Quaternion::identity();
+        }
+        let axis = axis.normalize();
+        let (sinres, cosres) = (angle * 0.5).sin_cos();
+        Quaternion::new(axis.x * sinres, axis.y * sinres, axis.z * sinres, cosres).normalize()
+    }
+    /// Returns the rotation angle and axis for a given quaternion
+    pub fn to_axis_angle(self) -> (Vector3, f32) {
+        let q = if self.w.abs() > 1.0 {
+            self.normalize()
+        } else {
+            self
+        };
+        let angle = 2.0 * q.w.acos();
+        let den = (1.0 - q.w * q.w).sqrt();
+        let axis = if den > 0.0001 {
+            Vector3::new(q.x / den, q.y / den, q.z / den)
+        } else {
+            // This occurs when the angle is zero, not a problem: just set an arbitrary normalized axis
+            Vector3::new(1.0, 0.0, 0.0)
+        };
+        (axis, angle)
+    }
+    /// Returns the quaternion equivalent to Euler angles
+    ///
+    /// NOTE: Rotation order is ZYX, angles in radians
+    pub fn from_euler(roll: f32, pitch: f32, yaw: f32) -> Quaternion {
+        let (x1, x0) = (roll * 0.5).sin_cos();
+        let (y1, y0) = (pitch * 0.5).sin_cos();
+        let (z1, z0) = (yaw * 0.5).sin_cos();
+        Quaternion::new(
+            x1 * y0 * z0 - x0 * y1 * z1,
+            x0 * y1 * z0 + x1 * y0 * z1,
+            x0 * y0 * z1 - x1 * y1 * z0,
+            x0 * y0 * z0 + x1 * y1 * z1,
+        )
+    }
+    /// Returns the Euler angles equivalent to quaternion (roll, pitch, yaw)
+    ///
+    /// NOTE: Angles are returned in a Vector3 struct in radians
+    pub fn to_euler(self) -> Vector3 {
+        let q = self;
+        // roll (x-axis rotation)
+        let x0 = 2.0 * (q.w * q.x + q.y * q.z);
+        let x1 = 1.0 - 2.0 * (q.x * q.x + q.y * q.y);
+        // pitch (y-axis rotation)
+        let y0 = (2.0 * (q.w * q.y - q.z * q.x)).clamp(-1.0, 1.0);
+        // yaw (z-axis rotation)
+        let z0 = 2.0 * (q.w * q.z + q.x * q.y);
+        let z1 = 1.0 - 2.0 * (q.y * q.y + q.z * q.z);
+        Vector3::new(x0.atan2(x1), y0.asin(), z0.atan2(z1))
+    }
+}
+impl Mul for Quaternion {
+    type Output = Quaternion;
+    /// Same as [`Quaternion::multiply`](struct.Quaternion.html#method.multiply)
+    fn mul(self, q: Quaternion) -> Quaternion {
+        self.multiply(q)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    use crate::{Matrix, Quaternion, Vector3};
+
+    const EPSILON: f32 = 1e-5;
+
+    fn assert_matrix_eq(a: Matrix, b: Matrix) {
+        for (x, y) in a.to_array().iter().zip(b.to_array().iter()) {
+            assert!((x - y).abs() < EPSILON, "{:?} != {:?}", a, b);
+        }
+    }
+
+    fn assert_vector3_eq(a: Vector3, b: Vector3) {
+        assert!((a - b).length() < EPSILON, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn matrix_layout_matches_raw() {
+        use std::mem::{align_of, size_of};
+        assert_eq!(size_of::<Matrix>(), size_of::<crate::raw::Matrix>());
+        assert_eq!(align_of::<Matrix>(), align_of::<crate::raw::Matrix>());
+    }
+
+    #[test]
+    fn translate_moves_points() {
+        let p = Vector3::new(1.0, 2.0, 3.0).transform(Matrix::translate(10.0, 20.0, 30.0));
+        assert_vector3_eq(p, Vector3::new(11.0, 22.0, 33.0));
+    }
+
+    #[test]
+    fn multiply_applies_left_first() {
+        // Translate then rotate a quarter turn around Z
+        let m = Matrix::translate(1.0, 0.0, 0.0) * Matrix::rotate_z(FRAC_PI_2);
+        let p = Vector3::zero().transform(m);
+        assert_vector3_eq(p, Vector3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn rotate_matches_axis_rotations() {
+        let angle = 0.7;
+        assert_matrix_eq(
+            Matrix::rotate(Vector3::new(1.0, 0.0, 0.0), angle),
+            Matrix::rotate_x(angle),
+        );
+        assert_matrix_eq(
+            Matrix::rotate(Vector3::new(0.0, 2.0, 0.0), angle),
+            Matrix::rotate_y(angle),
+        );
+        assert_matrix_eq(
+            Matrix::rotate(Vector3::new(0.0, 0.0, 1.0), angle),
+            Matrix::rotate_z(angle),
+        );
+    }
+
+    #[test]
+    fn invert_and_transpose() {
+        let m = Matrix::scale(2.0, 3.0, 4.0)
+            * Matrix::rotate(Vector3::new(1.0, 1.0, 0.0), 0.3)
+            * Matrix::translate(5.0, -1.0, 2.0);
+        assert_matrix_eq(m * m.invert(), Matrix::identity());
+        assert!((m.determinant() - 24.0).abs() < 1e-3);
+        assert_matrix_eq(m.transpose().transpose(), m);
+        assert_eq!(Matrix::translate(1.0, 2.0, 3.0).transpose().m3, 1.0);
+    }
+
+    #[test]
+    fn perspective_matches_frustum() {
+        let m = Matrix::perspective(std::f64::consts::FRAC_PI_2, 2.0, 1.0, 101.0);
+        assert!((m.m0 - 0.5).abs() < EPSILON);
+        assert!((m.m5 - 1.0).abs() < EPSILON);
+        assert!((m.m10 + 1.02).abs() < EPSILON);
+        assert_eq!(m.m11, -1.0);
+        assert!((m.m14 + 2.02).abs() < EPSILON);
+    }
+
+    #[test]
+    fn ortho_maps_box_to_clip_space() {
+        let m = Matrix::ortho(0.0, 800.0, 450.0, 0.0, 0.0, 1.0);
+        assert_vector3_eq(
+            Vector3::new(0.0, 0.0, 0.0).transform(m),
+            Vector3::new(-1.0, 1.0, -1.0),
+        );
+        assert_vector3_eq(
+            Vector3::new(800.0, 450.0, -1.0).transform(m),
+            Vector3::new(1.0, -1.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn look_at_is_inverse_camera_transform() {
+        let eye = Vector3::new(4.0, 3.0, 5.0);
+        let view = Matrix::look_at(eye, Vector3::zero(), Vector3::new(0.0, 1.0, 0.0));
+        assert_vector3_eq(eye.transform(view), Vector3::zero());
+        let forward = Vector3::zero().transform(view);
+        assert!((forward.x).abs() < EPSILON && (forward.y).abs() < EPSILON);
+        assert!((forward.z + eye.length()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn quaternion_axis_angle_matches_matrix_rotate() {
+        let axis = Vector3::new(1.0, 2.0, 3.0);
+        let q = Quaternion::from_axis_angle(axis, 1.2);
+        assert_matrix_eq(q.to_matrix(), Matrix::rotate(axis, 1.2));
+        let (out_axis, out_angle) = q.to_axis_angle();
+        assert_vector3_eq(out_axis, axis.normalize());
+        assert!((out_angle - 1.2).abs() < EPSILON);
+    }
+
+    #[test]
+    fn quaternion_rotates_vectors() {
+        let q = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), FRAC_PI_2);
+        let v = Vector3::new(1.0, 0.0, 0.0).rotate_by_quaternion(q);
+        assert_vector3_eq(v, Vector3::new(0.0, 1.0, 0.0));
+        let m = Matrix::rotate(Vector3::new(0.0, 1.0, 1.0), 2.0);
+        let q = Quaternion::from_matrix(m);
+        let p = Vector3::new(0.3, -2.0, 1.5);
+        assert_vector3_eq(p.rotate_by_quaternion(q), p.transform(m));
+    }
+
+    #[test]
+    fn quaternion_euler_round_trip() {
+        let q = Quaternion::from_euler(0.1, 0.2, 0.3);
+        assert_vector3_eq(q.to_euler(), Vector3::new(0.1, 0.2, 0.3));
+        let roll = Quaternion::from_euler(PI / 3.0, 0.0, 0.0);
+        assert_matrix_eq(roll.to_matrix(), Matrix::rotate_x(PI / 3.0));
+    }
+
+    #[test]
+    fn quaternion_slerp() {
+        let axis = Vector3::new(0.0, 1.0, 0.0);
+        let a = Quaternion::identity();
+        let b = Quaternion::from_axis_angle(axis, FRAC_PI_2);
+        let mid = a.slerp(b, 0.5);
+        let expected = Quaternion::from_axis_angle(axis, FRAC_PI_2 / 2.0);
+        assert!((mid.x - expected.x).abs() < EPSILON);
+        assert!((mid.y - expected.y).abs() < EPSILON);
+        assert!((mid.w - expected.w).abs() < EPSILON);
+        assert!((a.slerp(b, 0.0).w - 1.0).abs() < EPSILON);
+        assert!((a.slerp(b, 1.0).y - b.y).abs() < EPSILON);
+    }
+}
@@ -0,0 +1,272 @@
+//! Seeded Noise Functions
+//!
+//! Pure Rust 2D noise, reproducible across platforms for a given seed.
+//! All functions return values in `[0.0, 1.0]`.
+
+use crate::Vector2;
+
+// Skew factors for the 2D simplex grid
+const F2: f32 = 0.366_025_42; // (sqrt(3) - 1)/2
+const G2: f32 = 0.211_324_87; // (3 - sqrt(3))/6
+
+// Gradient directions for Perlin and simplex noise
+const GRADIENTS: [(f32, f32); 8] = [
+    (1.0, 1.0),
+    (-1.0, 1.0),
+    (1.0, -1.0),
+    (-1.0, -1.0),
+    (1.0, 0.0),
+    (-1.0, 0.0),
+    (0.0, 1.0),
+    (0.0, -1.0),
+];
+
+/// Noise algorithm
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NoiseType {
+    Value,
+    Perlin,
+    Simplex,
+    Worley,
+}
+
+/// Noise generator settings, see [`Image::gen_noise`](../struct.Image.html#method.gen_noise)
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Noise {
+    pub noise_type: NoiseType,
+    pub seed: u32,
+    /// Sample frequency, 1/32 by default (one feature every 32 pixels)
+    pub scale: f32,
+    /// Sample offset, in pixels
+    pub offset: Vector2,
+    /// fBm octaves, 1 by default (no fBm)
+    pub octaves: u32,
+    /// fBm frequency multiplier per octave
+    pub lacunarity: f32,
+    /// fBm amplitude multiplier per octave
+    pub gain: f32,
+}
+impl Noise {
+    pub fn new(noise_type: NoiseType, seed: u32) -> Noise {
+        Noise {
+            noise_type: noise_type,
+            seed: seed,
+            scale: 1.0 / 32.0,
+            offset: Vector2::zero(),
+            octaves: 1,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+    /// Set sample frequency
+    pub fn scale(mut self, scale: f32) -> Noise {
+        self.scale = scale;
+        self
+    }
+    /// Set sample offset, in pixels
+    pub fn offset(mut self, x: f32, y: f32) -> Noise {
+        self.offset = Vector2::new(x, y);
+        self
+    }
+    /// Layer octaves of noise as fractal Brownian motion
+    pub fn fbm(mut self, octaves: u32, lacunarity: f32, gain: f32) -> Noise {
+        self.octaves = octaves;
+        self.lacunarity = lacunarity;
+        self.gain = gain;
+        self
+    }
+    /// Sample noise at (x, y), in pixels
+    pub fn sample(&self, x: f32, y: f32) -> f32 {
+        let x = (x + self.offset.x) * self.scale;
+        let y = (y + self.offset.y) * self.scale;
+        let seed = self.seed;
+        let base = |x, y| match self.noise_type {
+            NoiseType::Value => value(seed, x, y),
+            NoiseType::Perlin => perlin(seed, x, y),
+            NoiseType::Simplex => simplex(seed, x, y),
+            NoiseType::Worley => worley(seed, x, y),
+        };
+        if self.octaves > 1 {
+            fbm(x, y, self.octaves, self.lacunarity, self.gain, base)
+        } else {
+            base(x, y)
+        }
+    }
+}
+
+/// Value noise, interpolated random lattice values
+pub fn value(seed: u32, x: f32, y: f32) -> f32 {
+    let (xi, yi) = (x.floor(), y.floor());
+    let (u, v) = (fade(x - xi), fade(y - yi));
+    let (xi, yi) = (xi as i32, yi as i32);
+    let corner = |dx: i32, dy: i32| to_unit(hash(seed, xi.wrapping_add(dx), yi.wrapping_add(dy)));
+    lerp(
+        lerp(corner(0, 0), corner(1, 0), u),
+        lerp(corner(0, 1), corner(1, 1), u),
+        v,
+    )
+}
+
+/// Perlin (gradient) noise
+pub fn perlin(seed: u32, x: f32, y: f32) -> f32 {
+    let (xf, yf) = (x.floor(), y.floor());
+    let (x0, y0) = (x - xf, y - yf);
+    let (xi, yi) = (xf as i32, yf as i32);
+    let corner = |dx: i32, dy: i32| {
+        let h = hash(seed, xi.wrapping_add(dx), yi.wrapping_add(dy));
+        gradient(h, x0 - dx as f32, y0 - dy as f32)
+    };
+    let (u, v) = (fade(x0), fade(y0));
+    let n = lerp(
+        lerp(corner(0, 0), corner(1, 0), u),
+        lerp(corner(0, 1), corner(1, 1), u),
+        v,
+    );
+    (n * 0.5 + 0.5).clamp(0.0, 1.0)
+}
+
+/// Simplex noise
+pub fn simplex(seed: u32, x: f32, y: f32) -> f32 {
+    let s = (x + y) * F2;
+    let (i, j) = ((x + s).floor(), (y + s).floor());
+    let t = (i + j) * G2;
+    let (x0, y0) = (x - (i - t), y - (j - t));
+    let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+    let (i, j) = (i as i32, j as i32);
+    let corners = [
+        (0, 0, x0, y0),
+        (i1, j1, x0 - i1 as f32 + G2, y0 - j1 as f32 + G2),
+        (1, 1, x0 - 1.0 + 2.0 * G2, y0 - 1.0 + 2.0 * G2),
+    ];
+    let mut n = 0.0;
+    for &(di, dj, cx, cy) in corners.iter() {
+        let t = 0.5 - cx * cx - cy * cy;
+        if t > 0.0 {
+            let h = hash(seed, i.wrapping_add(di), j.wrapping_add(dj));
+            n += t * t * t * t * gradient(h, cx, cy);
+        }
+    }
+    (n * 35.0 + 0.5).clamp(0.0, 1.0)
+}
+
+/// Worley (cellular) noise, distance to the nearest feature point
+pub fn worley(seed: u32, x: f32, y: f32) -> f32 {
+    let (xf, yf) = (x.floor(), y.floor());
+    let (xi, yi) = (xf as i32, yf as i32);
+    let mut nearest = f32::INFINITY;
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let (cx, cy) = (xi.wrapping_add(dx), yi.wrapping_add(dy));
+            let h = hash(seed, cx, cy);
+            let px = xf + dx as f32 + to_unit(h);
+            let py = yf + dy as f32 + to_unit(hash(h, cx, cy));
+            let d = (px - x) * (px - x) + (py - y) * (py - y);
+            nearest = nearest.min(d);
+        }
+    }
+    nearest.sqrt().min(1.0)
+}
+
+/// Fractal Brownian motion, sum octaves of `noise` at increasing frequency
+pub fn fbm<F: Fn(f32, f32) -> f32>(
+    x: f32,
+    y: f32,
+    octaves: u32,
+    lacunarity: f32,
+    gain: f32,
+    noise: F,
+) -> f32 {
+    let (mut frequency, mut amplitude) = (1.0, 1.0);
+    let (mut sum, mut total) = (0.0, 0.0);
+    for octave in 0..octaves {
+        // Shift each octave so lattice points don't line up
+        let shift = octave as f32 * 19.19;
+        sum += amplitude * noise(x * frequency + shift, y * frequency + shift);
+        total += amplitude;
+        frequency *= lacunarity;
+        amplitude *= gain;
+    }
+    if total > 0.0 {
+        sum / total
+    } else {
+        0.0
+    }
+}
+
+// Hash lattice coordinates (lowbias32 mixing)
+fn hash(seed: u32, x: i32, y: i32) -> u32 {
+    let mix = |mut h: u32| {
+        h ^= h >> 16;
+        h = h.wrapping_mul(0x7feb_352d);
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x846c_a68b);
+        h ^ (h >> 16)
+    };
+    mix(mix(mix(seed) ^ x as u32) ^ y as u32)
+}
+
+fn to_unit(h: u32) -> f32 {
+    (h >> 8) as f32 / (1u32 << 24) as f32
+}
+
+fn gradient(h: u32, x: f32, y: f32) -> f32 {
+    let (gx, gy) = GRADIENTS[(h & 7) as usize];
+    gx * x + gy * y
+}
+
+// Quintic fade curve 6t^5 - 15t^4 + 10t^3
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TYPES: [NoiseType; 4] = [
+        NoiseType::Value,
+        NoiseType::Perlin,
+        NoiseType::Simplex,
+        NoiseType::Worley,
+    ];
+
+    #[test]
+    fn seeded_and_reproducible() {
+        for &noise_type in TYPES.iter() {
+            let a = Noise::new(noise_type, 7).fbm(4, 2.0, 0.5);
+            let b = Noise::new(noise_type, 8).fbm(4, 2.0, 0.5);
+            let samples = |noise: Noise| -> Vec<f32> {
+                (0..64)
+                    .map(|i| noise.sample(i as f32 * 3.7, i as f32 * 1.3))
+                    .collect()
+            };
+            assert_eq!(samples(a), samples(a));
+            assert_ne!(samples(a), samples(b));
+        }
+    }
+
+    #[test]
+    fn values_in_unit_range() {
+        for &noise_type in TYPES.iter() {
+            let noise = Noise::new(noise_type, 1).scale(0.37);
+            for y in -20..20 {
+                for x in -20..20 {
+                    let v = noise.sample(x as f32, y as f32);
+                    assert!((0.0..=1.0).contains(&v), "{:?} {}", noise_type, v);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn value_noise_hits_lattice() {
+        assert_eq!(value(3, 2.0, 5.0), to_unit(hash(3, 2, 5)));
+        assert_eq!(perlin(3, 2.0, 5.0), 0.5);
+    }
+}
@@ -0,0 +1,397 @@
+//! Nine-Patch Drawing Functions
+//!
+//! Stretchable panels built from a source rectangle split by fixed borders.
+//! Corners keep their size, edges and center stretch to fill the destination:
+//!
+//! ```ignore
+//! let (image, panel) = load_nine_patch("panel.9.png")?;
+//! let texture = window.load_texture_from_image(&image)?;
+//! d.draw_texture_npatch(&texture, &panel, dest_rec, origin, 0.0, colors::WHITE);
+//! ```
+
+use crate::canvas::ImageCanvas;
+use crate::core::DrawHandle;
+use crate::textures::{load_image, load_image_ex};
+use crate::{Color, Error, Image, Rectangle, Result, Texture2D, Vector2};
+
+/// N-patch layout
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NPatchType {
+    /// 3x3 tiles, uses all four borders
+    NinePatch,
+    /// 1x3 tiles, uses top and bottom borders
+    ThreePatchVertical,
+    /// 3x1 tiles, uses left and right borders
+    ThreePatchHorizontal,
+}
+
+/// N-patch definition, borders are in source pixels
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NPatch {
+    /// Region in the texture
+    pub source_rec: Rectangle,
+    /// Left border offset
+    pub left: i32,
+    /// Top border offset
+    pub top: i32,
+    /// Right border offset
+    pub right: i32,
+    /// Bottom border offset
+    pub bottom: i32,
+    /// Layout of the patch
+    pub patch_type: NPatchType,
+    /// Content insets (left, top, right, bottom), `None` uses the borders
+    pub padding: Option<[i32; 4]>,
+}
+impl NPatch {
+    pub fn new(
+        source_rec: Rectangle,
+        left: i32,
+        top: i32,
+        right: i32,
+        bottom: i32,
+        patch_type: NPatchType,
+    ) -> NPatch {
+        NPatch {
+            source_rec: source_rec,
+            left: left,
+            top: top,
+            right: right,
+            bottom: bottom,
+            patch_type: patch_type,
+            padding: None,
+        }
+    }
+    /// Set content insets (left, top, right, bottom)
+    pub fn padding(mut self, left: i32, top: i32, right: i32, bottom: i32) -> NPatch {
+        self.padding = Some([left, top, right, bottom]);
+        self
+    }
+    /// Get the content area of the patch drawn in `dest_rec`
+    pub fn content_rec(&self, dest_rec: Rectangle) -> Rectangle {
+        let [left, top, right, bottom] =
+            self.padding
+                .unwrap_or([self.left, self.top, self.right, self.bottom]);
+        let width = (dest_rec.width - (left + right) as f32).max(0.0);
+        let height = (dest_rec.height - (top + bottom) as f32).max(0.0);
+        Rectangle::new(
+            dest_rec.x + left as f32,
+            dest_rec.y + top as f32,
+            width,
+            height,
+        )
+    }
+    /// Split the patch into (source, destination) rectangle pairs for `dest_rec`
+    ///
+    /// NOTE: Borders shrink proportionally when `dest_rec` is smaller than them,
+    /// empty tiles are skipped
+    pub fn slices(&self, dest_rec: Rectangle) -> Vec<(Rectangle, Rectangle)> {
+        let (left, right) = match self.patch_type {
+            NPatchType::ThreePatchVertical => (0.0, 0.0),
+            _ => (self.left as f32, self.right as f32),
+        };
+        let (top, bottom) = match self.patch_type {
+            NPatchType::ThreePatchHorizontal => (0.0, 0.0),
+            _ => (self.top as f32, self.bottom as f32),
+        };
+        let src_x = spans(self.source_rec.x, self.source_rec.width, left, right);
+        let src_y = spans(self.source_rec.y, self.source_rec.height, top, bottom);
+        let (left, right) = fit_borders(dest_rec.width, left, right);
+        let (top, bottom) = fit_borders(dest_rec.height, top, bottom);
+        let dst_x = spans(dest_rec.x, dest_rec.width, left, right);
+        let dst_y = spans(dest_rec.y, dest_rec.height, top, bottom);
+
+        let mut slices = Vec::with_capacity(9);
+        for (&(sy, sh), &(dy, dh)) in src_y.iter().zip(dst_y.iter()) {
+            for (&(sx, sw), &(dx, dw)) in src_x.iter().zip(dst_x.iter()) {
+                if sw > 0.0 && sh > 0.0 && dw > 0.0 && dh > 0.0 {
+                    slices.push((
+                        Rectangle::new(sx, sy, sw, sh),
+                        Rectangle::new(dx, dy, dw, dh),
+                    ));
+                }
+            }
+        }
+        slices
+    }
+}
+
+// Start and length of the three spans along one axis
+fn spans(start: f32, length: f32, low: f32, high: f32) -> [(f32, f32); 3] {
+    let middle = (length - low - high).max(0.0);
+    [
+        (start, low),
+        (start + low, middle),
+        (start + low + middle, high),
+    ]
+}
+
+// Shrink borders that don't fit in `length`, keeping their ratio
+fn fit_borders(length: f32, low: f32, high: f32) -> (f32, f32) {
+    if length >= low + high || low + high <= 0.0 {
+        (low, high)
+    } else {
+        let low = (low / (low + high) * length).max(0.0);
+        (low, length.max(0.0) - low)
+    }
+}
+
+//------------------------------------------------------------------------------
+// N-patch drawing functions
+//------------------------------------------------------------------------------
+
+impl DrawHandle<'_> {
+    /// Draws a texture (or part of it) that stretches or shrinks nicely
+    pub fn draw_texture_npatch(
+        &mut self,
+        texture: &Texture2D,
+        npatch: &NPatch,
+        dest_rec: Rectangle,
+        origin: Vector2,
+        rotation: f32,
+        tint: Color,
+    ) {
+        let full_rec = Rectangle::new(0.0, 0.0, dest_rec.width, dest_rec.height);
+        for (source_rec, slice_rec) in npatch.slices(full_rec) {
+            // Every tile rotates around the same origin as the whole patch
+            let slice_origin = Vector2::new(origin.x - slice_rec.x, origin.y - slice_rec.y);
+            let slice_dest =
+                Rectangle::new(dest_rec.x, dest_rec.y, slice_rec.width, slice_rec.height);
+            self.draw_texture_pro(
+                texture,
+                source_rec,
+                slice_dest,
+                slice_origin,
+                rotation,
+                tint,
+            );
+        }
+    }
+}
+
+impl<'a> ImageCanvas<'a> {
+    /// Draws a source image (or part of it) that stretches or shrinks nicely
+    pub fn draw_image_npatch(
+        &mut self,
+        src: &Image,
+        npatch: &NPatch,
+        dest_rec: Rectangle,
+        tint: Color,
+    ) -> Result<()> {
+        for (source_rec, slice_rec) in npatch.slices(dest_rec) {
+            // Snap tile edges to whole pixels so neighbouring tiles don't leave gaps
+            let x = slice_rec.x.round();
+            let y = slice_rec.y.round();
+            let width = (slice_rec.x + slice_rec.width).round() - x;
+            let height = (slice_rec.y + slice_rec.height).round() - y;
+            if width > 0.0 && height > 0.0 {
+                let snapped = Rectangle::new(x, y, width, height);
+                self.draw_image(src, source_rec, snapped, tint)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+// Nine-patch image loading functions
+//------------------------------------------------------------------------------
+
+/// Load an Android-style `.9.png`, returns the image without its marker border
+pub fn load_nine_patch<S: Into<String>>(file_name: S) -> Result<(Image, NPatch)> {
+    nine_patch_from_image(&load_image(file_name)?)
+}
+/// Split an Android-style nine-patch image into its content and `NPatch`
+///
+/// NOTE: Opaque black pixels in the 1 pixel border mark the stretch region
+/// (top and left) and content region (bottom and right). Multiple stretch
+/// segments are merged into one.
+pub fn nine_patch_from_image(image: &Image) -> Result<(Image, NPatch)> {
+    let (width, height) = (image.width(), image.height());
+    if width < 3 || height < 3 {
+        return Err(Error::InvalidData(format!(
+            "nine-patch image {}x{} is too small",
+            width, height
+        )));
+    }
+    let colors = image.get_colors()?;
+    let at = |x: i32, y: i32| colors[(y * width + x) as usize];
+    let inner_w = width - 2;
+    let inner_h = height - 2;
+
+    let top = marked((1..width - 1).map(|x| at(x, 0)))?;
+    let left = marked((1..height - 1).map(|y| at(0, y)))?;
+    let bottom = marked((1..width - 1).map(|x| at(x, height - 1)))?;
+    let right = marked((1..height - 1).map(|y| at(width - 1, y)))?;
+
+    let patch_type = match (top, left) {
+        (Some(_), Some(_)) => NPatchType::NinePatch,
+        (Some(_), None) => NPatchType::ThreePatchHorizontal,
+        (None, Some(_)) => NPatchType::ThreePatchVertical,
+        (None, None) => {
+            return Err(Error::InvalidData(String::from(
+                "nine-patch image has no stretch markers",
+            )))
+        }
+    };
+    let (left_border, right_border) = top.map_or((0, 0), |(a, b)| (a, inner_w - b));
+    let (top_border, bottom_border) = left.map_or((0, 0), |(a, b)| (a, inner_h - b));
+    let mut npatch = NPatch::new(
+        Rectangle::new(0.0, 0.0, inner_w as f32, inner_h as f32),
+        left_border,
+        top_border,
+        right_border,
+        bottom_border,
+        patch_type,
+    );
+    if bottom.is_some() || right.is_some() {
+        // A missing content marker falls back to the stretch region on that axis
+        let (pad_left, pad_right) =
+            bottom.map_or((left_border, right_border), |(a, b)| (a, inner_w - b));
+        let (pad_top, pad_bottom) =
+            right.map_or((top_border, bottom_border), |(a, b)| (a, inner_h - b));
+        npatch = npatch.padding(pad_left, pad_top, pad_right, pad_bottom);
+    }
+
+    let inner: Vec<Color> = (1..height - 1)
+        .flat_map(|y| (1..width - 1).map(move |x| (x, y)))
+        .map(|(x, y)| at(x, y))
+        .collect();
+    Ok((load_image_ex(&inner, inner_w, inner_h)?, npatch))
+}
+
+// Span of marker pixels as (first, one past last), errors on invalid border pixels
+fn marked<I: Iterator<Item = Color>>(border: I) -> Result<Option<(i32, i32)>> {
+    let mut span: Option<(i32, i32)> = None;
+    for (i, color) in border.enumerate() {
+        let i = i as i32;
+        match (color.r, color.g, color.b, color.a) {
+            (0, 0, 0, 255) => {
+                span = Some(span.map_or((i, i + 1), |(first, _)| (first, i + 1)));
+            }
+            (_, _, _, 0) => {}
+            _ => {
+                return Err(Error::InvalidData(format!(
+                    "nine-patch border pixel {:?} is not black or transparent",
+                    color
+                )))
+            }
+        }
+    }
+    Ok(span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::colors;
+
+    #[test]
+    fn nine_patch_slices() {
+        let npatch = NPatch::new(
+            Rectangle::new(10.0, 20.0, 12.0, 12.0),
+            4,
+            4,
+            4,
+            4,
+            NPatchType::NinePatch,
+        );
+        let slices = npatch.slices(Rectangle::new(0.0, 0.0, 40.0, 20.0));
+        assert_eq!(slices.len(), 9);
+        assert_eq!(
+            slices[0],
+            (
+                Rectangle::new(10.0, 20.0, 4.0, 4.0),
+                Rectangle::new(0.0, 0.0, 4.0, 4.0)
+            )
+        );
+        assert_eq!(
+            slices[4],
+            (
+                Rectangle::new(14.0, 24.0, 4.0, 4.0),
+                Rectangle::new(4.0, 4.0, 32.0, 12.0)
+            )
+        );
+        assert_eq!(slices[8].1, Rectangle::new(36.0, 16.0, 4.0, 4.0));
+
+        // Borders shrink and the center disappears when squeezed
+        let slices = npatch.slices(Rectangle::new(0.0, 0.0, 4.0, 20.0));
+        assert_eq!(slices.len(), 6);
+        assert_eq!(slices[0].1, Rectangle::new(0.0, 0.0, 2.0, 4.0));
+
+        let three = NPatch {
+            patch_type: NPatchType::ThreePatchHorizontal,
+            ..npatch
+        };
+        let slices = three.slices(Rectangle::new(0.0, 0.0, 40.0, 20.0));
+        assert_eq!(slices.len(), 3);
+        assert_eq!(slices[1].1, Rectangle::new(4.0, 0.0, 32.0, 20.0));
+    }
+
+    #[test]
+    fn load_nine_patch_markers() {
+        // 4x4 content, stretch column 1..3, stretch row 2, content inset 1 on the x axis
+        let (w, h) = (6, 6);
+        let mut pixels = vec![colors::RED; (w * h) as usize];
+        for i in 0..w {
+            pixels[i as usize] = colors::BLANK;
+            pixels[(i * w) as usize] = colors::BLANK;
+            pixels[((h - 1) * w + i) as usize] = colors::BLANK;
+            pixels[(i * w + w - 1) as usize] = colors::BLANK;
+        }
+        pixels[2] = colors::BLACK;
+        pixels[3] = colors::BLACK;
+        pixels[(3 * w) as usize] = colors::BLACK;
+        pixels[((h - 1) * w + 2) as usize] = colors::BLACK;
+        pixels[((h - 1) * w + 4) as usize] = colors::BLACK;
+        let image = load_image_ex(&pixels, w, h).unwrap();
+
+        let (inner, npatch) = nine_patch_from_image(&image).unwrap();
+        assert_eq!((inner.width(), inner.height()), (4, 4));
+        assert_eq!(inner.get_pixel(0, 0).unwrap(), colors::RED);
+        assert_eq!(npatch.patch_type, NPatchType::NinePatch);
+        assert_eq!(
+            (npatch.left, npatch.top, npatch.right, npatch.bottom),
+            (1, 2, 1, 1)
+        );
+        assert_eq!(npatch.padding, Some([1, 2, 0, 1]));
+
+        pixels[1] = colors::GRAY;
+        let image = load_image_ex(&pixels, w, h).unwrap();
+        assert!(nine_patch_from_image(&image).is_err());
+    }
+
+    #[test]
+    fn canvas_npatch() {
+        let source = load_image_ex(&[colors::WHITE; 9], 3, 3).unwrap();
+        let npatch = NPatch::new(
+            Rectangle::new(0.0, 0.0, 3.0, 3.0),
+            1,
+            1,
+            1,
+            1,
+            NPatchType::NinePatch,
+        );
+        let mut image = load_image_ex(&[colors::BLANK; 100], 10, 10).unwrap();
+        {
+            let mut canvas = ImageCanvas::new(&mut image).unwrap();
+            canvas
+                .draw_image_npatch(
+                    &source,
+                    &npatch,
+                    Rectangle::new(1.5, 1.0, 7.0, 5.0),
+                    colors::WHITE,
+                )
+                .unwrap();
+        }
+        let colors = image.get_colors().unwrap();
+        let covered = colors.iter().filter(|c| **c == colors::WHITE).count();
+        assert_eq!(covered, 7 * 5);
+        assert_eq!(
+            npatch.content_rec(Rectangle::new(0.0, 0.0, 7.0, 5.0)).width,
+            5.0
+        );
+    }
+}
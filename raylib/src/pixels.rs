@@ -0,0 +1,454 @@
+//! Typed Image Pixel Access
+//!
+//! One pixel type per uncompressed [`PixelFormat`](../enum.PixelFormat.html),
+//! laid out exactly as raylib stores it. Views only cover the base mipmap level.
+
+use std::slice;
+
+use crate::{Color, Error, Image, PixelFormat, Result};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Pixel with a fixed memory layout matching one uncompressed pixel format
+pub trait Pixel: private::Sealed + Copy {
+    /// Pixel format this type is stored as
+    const FORMAT: PixelFormat;
+    /// Decode pixel to RGBA (32bit)
+    fn to_color(self) -> Color;
+    /// Encode pixel from RGBA (32bit)
+    fn from_color(color: Color) -> Self;
+}
+
+/// 8 bit grayscale pixel (no alpha)
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Gray(pub u8);
+
+/// 8 bit grayscale pixel with 8 bit alpha
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GrayAlpha {
+    pub gray: u8,
+    pub alpha: u8,
+}
+
+/// 16 bit packed RGB pixel (5, 6, 5 bits)
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct R5g6b5(pub u16);
+
+/// 24 bit RGB pixel
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct R8g8b8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// 16 bit packed RGBA pixel (5, 5, 5, 1 bits)
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct R5g5b5a1(pub u16);
+
+/// 16 bit packed RGBA pixel (4 bits per channel)
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct R4g4b4a4(pub u16);
+
+/// 32 bit float pixel (1 channel)
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct R32(pub f32);
+
+/// 32*3 bit float RGB pixel
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct R32g32b32 {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+/// 32*4 bit float RGBA pixel
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct R32g32b32a32 {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+// Expand an n-bit channel to 8 bits
+fn expand(value: u16, bits: u32) -> u8 {
+    let max = (1u32 << bits) - 1;
+    ((u32::from(value) * 255 + max / 2) / max) as u8
+}
+
+// Reduce an 8 bit channel to n bits
+fn reduce(value: u8, bits: u32) -> u16 {
+    let max = (1u32 << bits) - 1;
+    ((u32::from(value) * max + 127) / 255) as u16
+}
+
+fn luminance(color: Color) -> u8 {
+    (f32::from(color.r) * 0.299 + f32::from(color.g) * 0.587 + f32::from(color.b) * 0.114).round()
+        as u8
+}
+
+fn to_unit(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+impl private::Sealed for Gray {}
+impl Pixel for Gray {
+    const FORMAT: PixelFormat = PixelFormat::UncompressedGrayscale;
+    fn to_color(self) -> Color {
+        Color::new(self.0, self.0, self.0, 255)
+    }
+    fn from_color(color: Color) -> Gray {
+        Gray(luminance(color))
+    }
+}
+
+impl private::Sealed for GrayAlpha {}
+impl Pixel for GrayAlpha {
+    const FORMAT: PixelFormat = PixelFormat::UncompressedGrayAlpha;
+    fn to_color(self) -> Color {
+        Color::new(self.gray, self.gray, self.gray, self.alpha)
+    }
+    fn from_color(color: Color) -> GrayAlpha {
+        GrayAlpha {
+            gray: luminance(color),
+            alpha: color.a,
+        }
+    }
+}
+
+impl private::Sealed for R5g6b5 {}
+impl Pixel for R5g6b5 {
+    const FORMAT: PixelFormat = PixelFormat::UncompressedR5g6b5;
+    fn to_color(self) -> Color {
+        Color::new(
+            expand(self.0 >> 11, 5),
+            expand((self.0 >> 5) & 0x3f, 6),
+            expand(self.0 & 0x1f, 5),
+            255,
+        )
+    }
+    fn from_color(color: Color) -> R5g6b5 {
+        R5g6b5(reduce(color.r, 5) << 11 | reduce(color.g, 6) << 5 | reduce(color.b, 5))
+    }
+}
+
+impl private::Sealed for R8g8b8 {}
+impl Pixel for R8g8b8 {
+    const FORMAT: PixelFormat = PixelFormat::UncompressedR8g8b8;
+    fn to_color(self) -> Color {
+        Color::new(self.r, self.g, self.b, 255)
+    }
+    fn from_color(color: Color) -> R8g8b8 {
+        R8g8b8 {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+        }
+    }
+}
+
+impl private::Sealed for R5g5b5a1 {}
+impl Pixel for R5g5b5a1 {
+    const FORMAT: PixelFormat = PixelFormat::UncompressedR5g5b5a1;
+    fn to_color(self) -> Color {
+        Color::new(
+            expand(self.0 >> 11, 5),
+            expand((self.0 >> 6) & 0x1f, 5),
+            expand((self.0 >> 1) & 0x1f, 5),
+            if self.0 & 1 == 1 { 255 } else { 0 },
+        )
+    }
+    fn from_color(color: Color) -> R5g5b5a1 {
+        let alpha = if color.a > 127 { 1 } else { 0 };
+        R5g5b5a1(
+            reduce(color.r, 5) << 11 | reduce(color.g, 5) << 6 | reduce(color.b, 5) << 1 | alpha,
+        )
+    }
+}
+
+impl private::Sealed for R4g4b4a4 {}
+impl Pixel for R4g4b4a4 {
+    const FORMAT: PixelFormat = PixelFormat::UncompressedR4g4b4a4;
+    fn to_color(self) -> Color {
+        Color::new(
+            expand(self.0 >> 12, 4),
+            expand((self.0 >> 8) & 0xf, 4),
+            expand((self.0 >> 4) & 0xf, 4),
+            expand(self.0 & 0xf, 4),
+        )
+    }
+    fn from_color(color: Color) -> R4g4b4a4 {
+        R4g4b4a4(
+            reduce(color.r, 4) << 12
+                | reduce(color.g, 4) << 8
+                | reduce(color.b, 4) << 4
+                | reduce(color.a, 4),
+        )
+    }
+}
+
+impl private::Sealed for Color {}
+impl Pixel for Color {
+    const FORMAT: PixelFormat = PixelFormat::UncompressedR8g8b8a8;
+    fn to_color(self) -> Color {
+        self
+    }
+    fn from_color(color: Color) -> Color {
+        color
+    }
+}
+
+impl private::Sealed for R32 {}
+impl Pixel for R32 {
+    const FORMAT: PixelFormat = PixelFormat::UncompressedR32;
+    fn to_color(self) -> Color {
+        let value = to_unit(self.0);
+        Color::new(value, value, value, 255)
+    }
+    fn from_color(color: Color) -> R32 {
+        R32(f32::from(luminance(color)) / 255.0)
+    }
+}
+
+impl private::Sealed for R32g32b32 {}
+impl Pixel for R32g32b32 {
+    const FORMAT: PixelFormat = PixelFormat::UncompressedR32g32b32;
+    fn to_color(self) -> Color {
+        Color::new(to_unit(self.r), to_unit(self.g), to_unit(self.b), 255)
+    }
+    fn from_color(color: Color) -> R32g32b32 {
+        R32g32b32 {
+            r: f32::from(color.r) / 255.0,
+            g: f32::from(color.g) / 255.0,
+            b: f32::from(color.b) / 255.0,
+        }
+    }
+}
+
+impl private::Sealed for R32g32b32a32 {}
+impl Pixel for R32g32b32a32 {
+    const FORMAT: PixelFormat = PixelFormat::UncompressedR32g32b32a32;
+    fn to_color(self) -> Color {
+        Color::new(
+            to_unit(self.r),
+            to_unit(self.g),
+            to_unit(self.b),
+            to_unit(self.a),
+        )
+    }
+    fn from_color(color: Color) -> R32g32b32a32 {
+        R32g32b32a32 {
+            r: f32::from(color.r) / 255.0,
+            g: f32::from(color.g) / 255.0,
+            b: f32::from(color.b) / 255.0,
+            a: f32::from(color.a) / 255.0,
+        }
+    }
+}
+
+impl PixelFormat {
+    /// Check if pixel format is block compressed
+    pub fn is_compressed(self) -> bool {
+        self as u32 >= PixelFormat::CompressedDxt1Rgb as u32
+    }
+}
+
+impl Image {
+    /// Get pixel data as a `Color` slice (R8G8B8A8 images only)
+    pub fn pixels(&self) -> Result<&[Color]> {
+        self.view::<Color>()
+    }
+    /// Get mutable pixel data as a `Color` slice (R8G8B8A8 images only)
+    pub fn pixels_mut(&mut self) -> Result<&mut [Color]> {
+        self.view_mut::<Color>()
+    }
+    /// Get pixel data as a slice of the pixel type matching the image format
+    pub fn view<P: Pixel>(&self) -> Result<&[P]> {
+        self.check_format(P::FORMAT)?;
+        let len = self.pixel_count();
+        Ok(unsafe { slice::from_raw_parts(self.data as *const P, len) })
+    }
+    /// Get mutable pixel data as a slice of the pixel type matching the image format
+    pub fn view_mut<P: Pixel>(&mut self) -> Result<&mut [P]> {
+        self.check_format(P::FORMAT)?;
+        let len = self.pixel_count();
+        Ok(unsafe { slice::from_raw_parts_mut(self.data as *mut P, len) })
+    }
+    /// Get pixel color at (x, y), decoded from any uncompressed format
+    ///
+    /// NOTE: Panics if (x, y) is outside the image
+    pub fn get_pixel(&self, x: i32, y: i32) -> Result<Color> {
+        let index = self.pixel_index(x, y);
+        match self.format {
+            PixelFormat::UncompressedGrayscale => self.read::<Gray>(index),
+            PixelFormat::UncompressedGrayAlpha => self.read::<GrayAlpha>(index),
+            PixelFormat::UncompressedR5g6b5 => self.read::<R5g6b5>(index),
+            PixelFormat::UncompressedR8g8b8 => self.read::<R8g8b8>(index),
+            PixelFormat::UncompressedR5g5b5a1 => self.read::<R5g5b5a1>(index),
+            PixelFormat::UncompressedR4g4b4a4 => self.read::<R4g4b4a4>(index),
+            PixelFormat::UncompressedR8g8b8a8 => self.read::<Color>(index),
+            PixelFormat::UncompressedR32 => self.read::<R32>(index),
+            PixelFormat::UncompressedR32g32b32 => self.read::<R32g32b32>(index),
+            PixelFormat::UncompressedR32g32b32a32 => self.read::<R32g32b32a32>(index),
+            format => Err(Error::UnsupportedFormat(format)),
+        }
+    }
+    /// Set pixel color at (x, y), encoded to any uncompressed format
+    ///
+    /// NOTE: Panics if (x, y) is outside the image
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: Color) -> Result<()> {
+        let index = self.pixel_index(x, y);
+        match self.format {
+            PixelFormat::UncompressedGrayscale => self.write::<Gray>(index, color),
+            PixelFormat::UncompressedGrayAlpha => self.write::<GrayAlpha>(index, color),
+            PixelFormat::UncompressedR5g6b5 => self.write::<R5g6b5>(index, color),
+            PixelFormat::UncompressedR8g8b8 => self.write::<R8g8b8>(index, color),
+            PixelFormat::UncompressedR5g5b5a1 => self.write::<R5g5b5a1>(index, color),
+            PixelFormat::UncompressedR4g4b4a4 => self.write::<R4g4b4a4>(index, color),
+            PixelFormat::UncompressedR8g8b8a8 => self.write::<Color>(index, color),
+            PixelFormat::UncompressedR32 => self.write::<R32>(index, color),
+            PixelFormat::UncompressedR32g32b32 => self.write::<R32g32b32>(index, color),
+            PixelFormat::UncompressedR32g32b32a32 => self.write::<R32g32b32a32>(index, color),
+            format => Err(Error::UnsupportedFormat(format)),
+        }
+    }
+    /// Get pixel data decoded to RGBA (32bit) colors
+    ///
+    /// NOTE: Compressed formats decode in software, DXT1/3/5 and ETC1 only.
+    /// Compressed files must be loaded with `load_compressed()`, raylib doesn't
+    /// report their data size.
+    pub fn get_colors(&self) -> Result<Vec<Color>> {
+        match self.format {
+            PixelFormat::UncompressedGrayscale => self.decode::<Gray>(),
+            PixelFormat::UncompressedGrayAlpha => self.decode::<GrayAlpha>(),
+            PixelFormat::UncompressedR5g6b5 => self.decode::<R5g6b5>(),
+            PixelFormat::UncompressedR8g8b8 => self.decode::<R8g8b8>(),
+            PixelFormat::UncompressedR5g5b5a1 => self.decode::<R5g5b5a1>(),
+            PixelFormat::UncompressedR4g4b4a4 => self.decode::<R4g4b4a4>(),
+            PixelFormat::UncompressedR8g8b8a8 => self.decode::<Color>(),
+            PixelFormat::UncompressedR32 => self.decode::<R32>(),
+            PixelFormat::UncompressedR32g32b32 => self.decode::<R32g32b32>(),
+            PixelFormat::UncompressedR32g32b32a32 => self.decode::<R32g32b32a32>(),
+            _ => crate::compressed::decode_colors(self),
+        }
+    }
+    /// Create a R8G8B8A8 copy of the image, decoded from any uncompressed format
+    /// or DXT1/3/5 and ETC1
+    ///
+    /// NOTE: Mipmaps are not copied
+    pub fn to_rgba8(&self) -> Result<Image> {
+        let colors = self.get_colors()?;
+        crate::textures::load_image_ex(&colors, self.width, self.height)
+    }
+
+    fn pixel_count(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+    fn pixel_index(&self, x: i32, y: i32) -> usize {
+        assert!(
+            x >= 0 && y >= 0 && x < self.width && y < self.height,
+            "pixel ({}, {}) is outside the {}x{} image",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        y as usize * self.width as usize + x as usize
+    }
+    fn check_format(&self, format: PixelFormat) -> Result<()> {
+        if self.format == format {
+            Ok(())
+        } else {
+            Err(Error::UnsupportedFormat(self.format))
+        }
+    }
+    fn read<P: Pixel>(&self, index: usize) -> Result<Color> {
+        Ok(self.view::<P>()?[index].to_color())
+    }
+    fn write<P: Pixel>(&mut self, index: usize, color: Color) -> Result<()> {
+        self.view_mut::<P>()?[index] = P::from_color(color);
+        Ok(())
+    }
+    fn decode<P: Pixel>(&self) -> Result<Vec<Color>> {
+        Ok(self.view::<P>()?.iter().map(|p| p.to_color()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::textures::{load_image_ex, load_image_pro};
+
+    #[test]
+    fn rgba_pixels() {
+        let mut image = load_image_ex(&[Color::new(0, 0, 0, 255); 4], 2, 2).unwrap();
+        image.pixels_mut().unwrap()[3] = Color::new(10, 20, 30, 40);
+        assert_eq!(image.get_pixel(1, 1).unwrap(), Color::new(10, 20, 30, 40));
+        image.set_pixel(0, 1, Color::new(1, 2, 3, 4)).unwrap();
+        assert_eq!(image.pixels().unwrap()[2], Color::new(1, 2, 3, 4));
+        assert!(image.view::<Gray>().is_err());
+    }
+
+    #[test]
+    fn typed_views_decode() {
+        let data = [0xffu8, 0x00, 0x00, 0xf8];
+        let image = load_image_pro(&data, 2, 1, PixelFormat::UncompressedR5g6b5).unwrap();
+        assert_eq!(image.view::<R5g6b5>().unwrap().len(), 2);
+        let rgba = image.to_rgba8().unwrap();
+        assert_eq!(rgba.format(), PixelFormat::UncompressedR8g8b8a8);
+        let pixels = rgba.pixels().unwrap();
+        let expected = R5g6b5(u16::from_ne_bytes([0xff, 0x00])).to_color();
+        assert_eq!(pixels[0], expected);
+
+        let mut gray = load_image_pro(&[0, 128], 2, 1, PixelFormat::UncompressedGrayscale).unwrap();
+        assert_eq!(
+            gray.get_pixel(1, 0).unwrap(),
+            Color::new(128, 128, 128, 255)
+        );
+        gray.set_pixel(0, 0, Color::new(255, 255, 255, 255))
+            .unwrap();
+        assert_eq!(gray.view::<Gray>().unwrap()[0], Gray(255));
+    }
+
+    #[test]
+    fn packed_formats_round_trip() {
+        let color = Color::new(255, 0, 255, 255);
+        assert_eq!(R5g6b5::from_color(color).to_color(), color);
+        assert_eq!(R5g5b5a1::from_color(color).to_color(), color);
+        assert_eq!(R4g4b4a4::from_color(color).to_color(), color);
+        assert_eq!(R32g32b32a32::from_color(color).to_color(), color);
+    }
+
+    #[test]
+    fn compressed_pixels_unsupported() {
+        let mut image = load_image_pro(&[0; 8], 4, 4, PixelFormat::CompressedDxt1Rgb).unwrap();
+        assert!(PixelFormat::CompressedDxt1Rgb.is_compressed());
+        assert_eq!(
+            image.get_pixel(0, 0),
+            Err(Error::UnsupportedFormat(PixelFormat::CompressedDxt1Rgb))
+        );
+        assert!(image.set_pixel(0, 0, Color::new(0, 0, 0, 0)).is_err());
+        assert!(image.to_rgba8().is_ok());
+    }
+}
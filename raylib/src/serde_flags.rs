@@ -0,0 +1,90 @@
+//! Serialize `BitFlags` as a list of flag names
+//!
+//! `BitFlags` is a foreign type, so use this module on the field instead:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Settings {
+//!     #[serde(with = "raylib::serde_flags")]
+//!     flags: BitFlags<ConfigFlag>,
+//! }
+//! ```
+//!
+//! `ConfigFlag::ShowLogo | ConfigFlag::VsyncHint` serializes as
+//! `["ShowLogo", "VsyncHint"]`.
+
+use enumflags::RawBitFlags;
+use num_traits::FromPrimitive;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::BitFlags;
+
+/// Serialize flags as a sequence of the set flag variants
+pub fn serialize<T, S>(flags: &BitFlags<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: RawBitFlags + FromPrimitive + Serialize,
+    T::Type: Into<u64>,
+    S: Serializer,
+{
+    let bits: u64 = flags.bits().into();
+    let set = (0..64)
+        .map(|i| 1u64 << i)
+        .filter(|bit| bits & bit != 0)
+        .filter_map(T::from_u64);
+    serializer.collect_seq(set)
+}
+
+/// Deserialize flags from a sequence of flag variants
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<BitFlags<T>, D::Error>
+where
+    T: RawBitFlags + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let set = Vec::<T>::deserialize(deserializer)?;
+    Ok(set.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gestures::Gesture;
+    use crate::{BitFlags, Camera3D, CameraType, Color, ConfigFlag, Vector2, Vector3};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Settings {
+        #[serde(with = "crate::serde_flags")]
+        flags: BitFlags<ConfigFlag>,
+        #[serde(with = "crate::serde_flags")]
+        gestures: BitFlags<Gesture>,
+    }
+
+    #[test]
+    fn flags_as_names() {
+        let settings = Settings {
+            flags: ConfigFlag::ShowLogo | ConfigFlag::VsyncHint,
+            gestures: BitFlags::empty(),
+        };
+        let json = serde_json::to_string(&settings).unwrap();
+        assert_eq!(json, r#"{"flags":["ShowLogo","VsyncHint"],"gestures":[]}"#);
+        assert_eq!(serde_json::from_str::<Settings>(&json).unwrap(), settings);
+    }
+
+    #[test]
+    fn plain_data_round_trip() {
+        let json = serde_json::to_string(&Vector2::new(1.0, 2.0)).unwrap();
+        assert_eq!(json, r#"{"x":1.0,"y":2.0}"#);
+
+        let camera = Camera3D {
+            position: Vector3::new(0.0, 10.0, 10.0),
+            target: Vector3::zero(),
+            up: Vector3::new(0.0, 1.0, 0.0),
+            fovy: 45.0,
+            camera_type: CameraType::Perspective,
+        };
+        let json = serde_json::to_string(&camera).unwrap();
+        assert_eq!(serde_json::from_str::<Camera3D>(&json).unwrap(), camera);
+
+        let color = Color::new(1, 2, 3, 4);
+        let json = serde_json::to_string(&color).unwrap();
+        assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), color);
+    }
+}
@@ -1,13 +1,80 @@
 //! Basic Shape Drawing Functions
 
-use crate::{raw, Color};
+use crate::{raw, Color, Rectangle, Vector2};
 
 //------------------------------------------------------------------------------
 // Basic shapes drawing functions
 //------------------------------------------------------------------------------
 
+/// Draw a pixel
+pub fn draw_pixel(pos_x: i32, pos_y: i32, color: Color) {
+    let raw_color = color.into_raw();
+    unsafe { raw::DrawPixel(pos_x, pos_y, raw_color) }
+}
+/// Draw a pixel (Vector version)
+pub fn draw_pixel_v(position: Vector2, color: Color) {
+    let raw_position = position.into_raw();
+    let raw_color = color.into_raw();
+    unsafe { raw::DrawPixelV(raw_position, raw_color) }
+}
+/// Draw a line
+pub fn draw_line(start_pos_x: i32, start_pos_y: i32, end_pos_x: i32, end_pos_y: i32, color: Color) {
+    let raw_color = color.into_raw();
+    unsafe { raw::DrawLine(start_pos_x, start_pos_y, end_pos_x, end_pos_y, raw_color) }
+}
+/// Draw a line (Vector version)
+pub fn draw_line_v(start_pos: Vector2, end_pos: Vector2, color: Color) {
+    let raw_start_pos = start_pos.into_raw();
+    let raw_end_pos = end_pos.into_raw();
+    let raw_color = color.into_raw();
+    unsafe { raw::DrawLineV(raw_start_pos, raw_end_pos, raw_color) }
+}
+/// Draw a color-filled circle
+pub fn draw_circle(center_x: i32, center_y: i32, radius: f32, color: Color) {
+    let raw_color = color.into_raw();
+    unsafe { raw::DrawCircle(center_x, center_y, radius, raw_color) }
+}
+/// Draw a color-filled circle (Vector version)
+pub fn draw_circle_v(center: Vector2, radius: f32, color: Color) {
+    let raw_center = center.into_raw();
+    let raw_color = color.into_raw();
+    unsafe { raw::DrawCircleV(raw_center, radius, raw_color) }
+}
+/// Draw circle outline
+pub fn draw_circle_lines(center_x: i32, center_y: i32, radius: f32, color: Color) {
+    let raw_color = color.into_raw();
+    unsafe { raw::DrawCircleLines(center_x, center_y, radius, raw_color) }
+}
 /// Draw a color-filled rectangle
 pub fn draw_rectangle(pos_x: i32, pos_y: i32, width: i32, height: i32, color: Color) {
     let raw_color = color.into_raw();
     unsafe { raw::DrawRectangle(pos_x, pos_y, width, height, raw_color) }
 }
+/// Draw a color-filled rectangle (Vector version)
+pub fn draw_rectangle_v(position: Vector2, size: Vector2, color: Color) {
+    let raw_position = position.into_raw();
+    let raw_size = size.into_raw();
+    let raw_color = color.into_raw();
+    unsafe { raw::DrawRectangleV(raw_position, raw_size, raw_color) }
+}
+/// Draw a color-filled rectangle
+pub fn draw_rectangle_rec(rec: Rectangle, color: Color) {
+    let raw_rec = rec.into_raw();
+    let raw_color = color.into_raw();
+    unsafe { raw::DrawRectangleRec(raw_rec, raw_color) }
+}
+/// Draw rectangle outline
+pub fn draw_rectangle_lines(pos_x: i32, pos_y: i32, width: i32, height: i32, color: Color) {
+    let raw_color = color.into_raw();
+    unsafe { raw::DrawRectangleLines(pos_x, pos_y, width, height, raw_color) }
+}
+/// Draw a color-filled triangle
+pub fn draw_triangle(v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+    let raw_color = color.into_raw();
+    unsafe { raw::DrawTriangle(v1.into_raw(), v2.into_raw(), v3.into_raw(), raw_color) }
+}
+/// Draw triangle outline
+pub fn draw_triangle_lines(v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+    let raw_color = color.into_raw();
+    unsafe { raw::DrawTriangleLines(v1.into_raw(), v2.into_raw(), v3.into_raw(), raw_color) }
+}
@@ -0,0 +1,635 @@
+//! Sprite Sheets and Frame Animation
+//!
+//! A `SpriteSheet` lists frame rectangles within a texture, built from a
+//! regular grid, a packed `Atlas` or (with the `json` feature) TexturePacker
+//! and Aseprite JSON exports. An `AnimationPlayer` steps through a named
+//! `AnimationClip`:
+//!
+//! ```ignore
+//! let sheet = SpriteSheet::from_json(&fs::read_to_string("hero.json")?)?;
+//! let mut player = AnimationPlayer::new(sheet.clips["run"].clone());
+//! // Every frame
+//! if player.update(get_frame_time()) == Some(AnimationEvent::Finished) { ... }
+//! d.draw_texture_rec(&texture, player.source_rec(&sheet), position, colors::WHITE);
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::atlas::{Atlas, AtlasSprite};
+use crate::{Rectangle, Vector2};
+
+/// Frame duration used when the source doesn't define one (seconds)
+pub const DEFAULT_FRAME_DURATION: f32 = 0.1;
+
+/// Regular grid of equally sized frames
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Grid {
+    /// Frame width
+    pub frame_width: i32,
+    /// Frame height
+    pub frame_height: i32,
+    /// Number of columns
+    pub columns: i32,
+    /// Number of rows
+    pub rows: i32,
+    /// Pixels around the whole grid
+    pub margin: i32,
+    /// Pixels between frames
+    pub spacing: i32,
+}
+impl Grid {
+    pub fn new(frame_width: i32, frame_height: i32, columns: i32, rows: i32) -> Grid {
+        Grid {
+            frame_width: frame_width,
+            frame_height: frame_height,
+            columns: columns,
+            rows: rows,
+            margin: 0,
+            spacing: 0,
+        }
+    }
+    /// Set pixels around the whole grid
+    pub fn margin(mut self, margin: i32) -> Grid {
+        self.margin = margin;
+        self
+    }
+    /// Set pixels between frames
+    pub fn spacing(mut self, spacing: i32) -> Grid {
+        self.spacing = spacing;
+        self
+    }
+}
+
+/// Sprite sheet frame
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SpriteFrame {
+    /// Frame name, the frame index for grids
+    pub name: String,
+    /// Placement in the texture
+    pub sprite: AtlasSprite,
+    /// Default frame duration (seconds)
+    pub duration: f32,
+}
+
+/// Animation playback mode
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PlayMode {
+    /// Restart from the first frame after the last
+    Loop,
+    /// Play forward then backward, repeating
+    PingPong,
+    /// Play once and stop on the last frame
+    Once,
+}
+
+/// Frame of an animation clip
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClipFrame {
+    /// Index into the sprite sheet frames
+    pub index: usize,
+    /// Frame duration (seconds)
+    pub duration: f32,
+}
+
+/// Sequence of sprite sheet frames
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnimationClip {
+    pub frames: Vec<ClipFrame>,
+    pub mode: PlayMode,
+}
+impl AnimationClip {
+    pub fn new(mode: PlayMode) -> AnimationClip {
+        AnimationClip {
+            frames: Vec::new(),
+            mode: mode,
+        }
+    }
+    /// Add a frame showing sheet frame `index` for `duration` seconds
+    pub fn frame(mut self, index: usize, duration: f32) -> AnimationClip {
+        self.frames.push(ClipFrame {
+            index: index,
+            duration: duration,
+        });
+        self
+    }
+    /// Total duration of one pass through the frames (seconds)
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|f| f.duration).sum()
+    }
+}
+
+/// Sprite sheet frames and named animation clips
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SpriteSheet {
+    pub frames: Vec<SpriteFrame>,
+    pub clips: BTreeMap<String, AnimationClip>,
+}
+impl SpriteSheet {
+    /// Sprite sheet from a grid, frames are numbered row by row
+    pub fn from_grid(grid: &Grid) -> SpriteSheet {
+        let size = Vector2::new(grid.frame_width as f32, grid.frame_height as f32);
+        let mut frames = Vec::new();
+        for row in 0..grid.rows {
+            for column in 0..grid.columns {
+                let x = grid.margin + column * (grid.frame_width + grid.spacing);
+                let y = grid.margin + row * (grid.frame_height + grid.spacing);
+                frames.push(SpriteFrame {
+                    name: frames.len().to_string(),
+                    sprite: AtlasSprite {
+                        rec: Rectangle::new(x as f32, y as f32, size.x, size.y),
+                        rotated: false,
+                        offset: Vector2::new(0.0, 0.0),
+                        source_size: size,
+                    },
+                    duration: DEFAULT_FRAME_DURATION,
+                });
+            }
+        }
+        SpriteSheet {
+            frames: frames,
+            clips: BTreeMap::new(),
+        }
+    }
+    /// Sprite sheet from packed atlas sprites, frames are sorted by name
+    pub fn from_atlas(atlas: &Atlas) -> SpriteSheet {
+        let frames = atlas
+            .sprites
+            .iter()
+            .map(|(name, sprite)| SpriteFrame {
+                name: name.clone(),
+                sprite: *sprite,
+                duration: DEFAULT_FRAME_DURATION,
+            })
+            .collect();
+        SpriteSheet {
+            frames: frames,
+            clips: BTreeMap::new(),
+        }
+    }
+    /// Find a frame index by name
+    pub fn frame_index(&self, name: &str) -> Option<usize> {
+        self.frames.iter().position(|f| f.name == name)
+    }
+    /// Build a clip from frame indices using their default durations
+    pub fn clip<I: IntoIterator<Item = usize>>(&self, indices: I, mode: PlayMode) -> AnimationClip {
+        let mut clip = AnimationClip::new(mode);
+        for index in indices {
+            let duration = self
+                .frames
+                .get(index)
+                .map_or(DEFAULT_FRAME_DURATION, |f| f.duration);
+            clip = clip.frame(index, duration);
+        }
+        clip
+    }
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use serde_json::{Map, Value};
+
+    use super::*;
+    use crate::{Error, Result};
+
+    impl SpriteSheet {
+        /// Sprite sheet from TexturePacker or Aseprite JSON (hash or array frames)
+        ///
+        /// NOTE: Aseprite `frameTags` and TexturePacker `animations` become clips,
+        /// durations are read from milliseconds. Hash frames are sorted by name.
+        pub fn from_json(json: &str) -> Result<SpriteSheet> {
+            let root: Value = serde_json::from_str(json).map_err(invalid)?;
+            let mut sheet = SpriteSheet::default();
+            match root.get("frames") {
+                Some(Value::Object(frames)) => {
+                    for (name, frame) in frames {
+                        sheet.frames.push(parse_frame(name.clone(), frame)?);
+                    }
+                }
+                Some(Value::Array(frames)) => {
+                    for frame in frames {
+                        let name = frame
+                            .get("filename")
+                            .and_then(Value::as_str)
+                            .map_or_else(|| sheet.frames.len().to_string(), String::from);
+                        sheet.frames.push(parse_frame(name, frame)?);
+                    }
+                }
+                _ => return Err(missing("frames")),
+            }
+
+            // TexturePacker animations list frame names
+            if let Some(Value::Object(animations)) = root.get("animations") {
+                for (name, names) in animations {
+                    let names = names
+                        .as_array()
+                        .ok_or_else(|| missing("animation frames"))?;
+                    let mut indices = Vec::new();
+                    for frame_name in names {
+                        let frame_name = frame_name.as_str().unwrap_or_default();
+                        let index = sheet.frame_index(frame_name).ok_or_else(|| {
+                            Error::InvalidData(format!("unknown frame `{}`", frame_name))
+                        })?;
+                        indices.push(index);
+                    }
+                    let clip = sheet.clip(indices, PlayMode::Loop);
+                    sheet.clips.insert(name.clone(), clip);
+                }
+            }
+
+            // Aseprite tags are inclusive frame ranges with a direction
+            let tags = root.pointer("/meta/frameTags").and_then(Value::as_array);
+            for tag in tags.into_iter().flatten() {
+                let name = tag.get("name").and_then(Value::as_str);
+                let from = tag.get("from").and_then(Value::as_u64);
+                let to = tag.get("to").and_then(Value::as_u64);
+                let (name, from, to) = match (name, from, to) {
+                    (Some(name), Some(from), Some(to)) => (name, from as usize, to as usize),
+                    _ => return Err(missing("frameTags name, from or to")),
+                };
+                if from > to || to >= sheet.frames.len() {
+                    return Err(Error::InvalidData(format!(
+                        "frame tag `{}` range {}..={} is out of bounds",
+                        name, from, to
+                    )));
+                }
+                let direction = tag.get("direction").and_then(Value::as_str);
+                let once = tag.get("repeat").and_then(repeat_count) == Some(1);
+                let mode = match direction {
+                    _ if once => PlayMode::Once,
+                    Some("pingpong") | Some("pingpong_reverse") => PlayMode::PingPong,
+                    _ => PlayMode::Loop,
+                };
+                let clip = match direction {
+                    Some("reverse") | Some("pingpong_reverse") => {
+                        sheet.clip((from..=to).rev(), mode)
+                    }
+                    _ => sheet.clip(from..=to, mode),
+                };
+                sheet.clips.insert(String::from(name), clip);
+            }
+            Ok(sheet)
+        }
+    }
+
+    fn parse_frame(name: String, frame: &Value) -> Result<SpriteFrame> {
+        let rec = frame
+            .get("frame")
+            .and_then(rect)
+            .ok_or_else(|| missing("frame"))?;
+        let rotated = frame.get("rotated").and_then(Value::as_bool) == Some(true);
+        let trimmed = frame.get("spriteSourceSize").and_then(rect);
+        let source_size = frame
+            .get("sourceSize")
+            .and_then(Value::as_object)
+            .and_then(|size| Some(Vector2::new(number(size, "w")?, number(size, "h")?)));
+        let duration = frame
+            .get("duration")
+            .and_then(Value::as_f64)
+            .map_or(DEFAULT_FRAME_DURATION, |ms| ms as f32 / 1000.0);
+        Ok(SpriteFrame {
+            name: name,
+            sprite: AtlasSprite {
+                // TexturePacker lists the unrotated size, atlas sprites store the packed one
+                rec: if rotated {
+                    Rectangle::new(rec.x, rec.y, rec.height, rec.width)
+                } else {
+                    rec
+                },
+                rotated: rotated,
+                offset: trimmed.map_or(Vector2::new(0.0, 0.0), |t| Vector2::new(t.x, t.y)),
+                source_size: source_size.unwrap_or(Vector2::new(rec.width, rec.height)),
+            },
+            duration: duration,
+        })
+    }
+
+    fn rect(value: &Value) -> Option<Rectangle> {
+        let value = value.as_object()?;
+        Some(Rectangle::new(
+            number(value, "x")?,
+            number(value, "y")?,
+            number(value, "w")?,
+            number(value, "h")?,
+        ))
+    }
+
+    fn number(object: &Map<String, Value>, key: &str) -> Option<f32> {
+        object.get(key).and_then(Value::as_f64).map(|n| n as f32)
+    }
+
+    // Aseprite writes the repeat count as a string
+    fn repeat_count(value: &Value) -> Option<u64> {
+        value
+            .as_u64()
+            .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+    }
+
+    fn missing(what: &str) -> Error {
+        Error::InvalidData(format!("sprite sheet JSON is missing {}", what))
+    }
+
+    fn invalid(error: serde_json::Error) -> Error {
+        Error::InvalidData(format!("sprite sheet JSON: {}", error))
+    }
+}
+
+//------------------------------------------------------------------------------
+// Animation playback
+//------------------------------------------------------------------------------
+
+/// Event reported by `AnimationPlayer::update()`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AnimationEvent {
+    /// A looping clip started over
+    Looped,
+    /// A `PlayMode::Once` clip reached the end of its last frame
+    Finished,
+}
+
+/// Plays an animation clip, advanced by delta time
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationPlayer {
+    clip: AnimationClip,
+    position: usize,
+    elapsed: f32,
+    forward: bool,
+    finished: bool,
+    /// Playback speed multiplier, 1.0 by default
+    pub speed: f32,
+    /// Pause playback
+    pub paused: bool,
+    /// Mirror the source rectangle horizontally
+    pub flip_x: bool,
+    /// Mirror the source rectangle vertically
+    pub flip_y: bool,
+}
+impl AnimationPlayer {
+    pub fn new(clip: AnimationClip) -> AnimationPlayer {
+        AnimationPlayer {
+            clip: clip,
+            position: 0,
+            elapsed: 0.0,
+            forward: true,
+            finished: false,
+            speed: 1.0,
+            paused: false,
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+    /// Switch to another clip and restart
+    pub fn play(&mut self, clip: AnimationClip) {
+        self.clip = clip;
+        self.restart();
+    }
+    /// Restart the current clip from its first frame
+    pub fn restart(&mut self) {
+        self.position = 0;
+        self.elapsed = 0.0;
+        self.forward = true;
+        self.finished = false;
+    }
+    /// Current clip
+    pub fn clip(&self) -> &AnimationClip {
+        &self.clip
+    }
+    /// Check if a `PlayMode::Once` clip has finished
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+    /// Current position within the clip frames
+    pub fn position(&self) -> usize {
+        self.position
+    }
+    /// Current sprite sheet frame index
+    pub fn frame_index(&self) -> Option<usize> {
+        self.clip.frames.get(self.position).map(|f| f.index)
+    }
+    /// Current source rectangle in the sheet texture, flips use negative sizes
+    ///
+    /// NOTE: Rotated atlas frames are returned as stored, unrotated
+    pub fn source_rec(&self, sheet: &SpriteSheet) -> Rectangle {
+        let frame = self.frame_index().and_then(|i| sheet.frames.get(i));
+        let mut rec = frame.map_or(Rectangle::new(0.0, 0.0, 0.0, 0.0), |f| f.sprite.rec);
+        if self.flip_x {
+            rec.width = -rec.width;
+        }
+        if self.flip_y {
+            rec.height = -rec.height;
+        }
+        rec
+    }
+    /// Advance playback by `delta` seconds
+    ///
+    /// NOTE: Reports `Finished` over `Looped` when both happen in one update,
+    /// non-finite deltas are ignored
+    pub fn update(&mut self, delta: f32) -> Option<AnimationEvent> {
+        let advance = delta * self.speed;
+        if self.paused || self.finished || self.clip.frames.is_empty() || !advance.is_finite() {
+            return None;
+        }
+        self.elapsed += advance;
+        let mut event = None;
+        // Skip whole cycles at once, a cycle ends on the frame it started from
+        let cycle = self.cycle_duration();
+        if self.clip.mode != PlayMode::Once && self.elapsed >= cycle {
+            self.elapsed %= cycle;
+            event = Some(AnimationEvent::Looped);
+        }
+        loop {
+            let duration = frame_duration(&self.clip.frames[self.position]);
+            if self.elapsed < duration {
+                return event;
+            }
+            self.elapsed -= duration;
+            match self.step() {
+                Some(AnimationEvent::Finished) => {
+                    self.finished = true;
+                    self.elapsed = 0.0;
+                    return Some(AnimationEvent::Finished);
+                }
+                Some(AnimationEvent::Looped) => event = Some(AnimationEvent::Looped),
+                None => {}
+            }
+        }
+    }
+
+    // Duration of a full loop, or a full round trip when ping-ponging
+    fn cycle_duration(&self) -> f32 {
+        let frames = &self.clip.frames;
+        let total: f32 = frames.iter().map(frame_duration).sum();
+        match (self.clip.mode, frames.len()) {
+            (PlayMode::PingPong, count) if count > 1 => {
+                2.0 * total - frame_duration(&frames[0]) - frame_duration(&frames[count - 1])
+            }
+            _ => total,
+        }
+    }
+
+    // Move to the next frame position
+    fn step(&mut self) -> Option<AnimationEvent> {
+        let count = self.clip.frames.len();
+        match self.clip.mode {
+            PlayMode::Once if self.position + 1 == count => Some(AnimationEvent::Finished),
+            PlayMode::Loop if self.position + 1 == count => {
+                self.position = 0;
+                Some(AnimationEvent::Looped)
+            }
+            PlayMode::Once | PlayMode::Loop => {
+                self.position += 1;
+                None
+            }
+            PlayMode::PingPong => {
+                if count == 1 {
+                    return Some(AnimationEvent::Looped);
+                }
+                if self.forward && self.position + 1 == count {
+                    self.forward = false;
+                }
+                if self.forward {
+                    self.position += 1;
+                    None
+                } else {
+                    self.position -= 1;
+                    if self.position == 0 {
+                        self.forward = true;
+                        Some(AnimationEvent::Looped)
+                    } else {
+                        None
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Zero length frames would never let time run out
+fn frame_duration(frame: &ClipFrame) -> f32 {
+    frame.duration.max(1e-4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(player: &mut AnimationPlayer, steps: usize) -> Vec<usize> {
+        (0..steps)
+            .map(|_| {
+                player.update(0.1);
+                player.position()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn grid_frames() {
+        let sheet = SpriteSheet::from_grid(&Grid::new(16, 8, 3, 2).margin(1).spacing(2));
+        assert_eq!(sheet.frames.len(), 6);
+        assert_eq!(sheet.frames[4].name, "4");
+        assert_eq!(
+            sheet.frames[4].sprite.rec,
+            Rectangle::new(19.0, 11.0, 16.0, 8.0)
+        );
+    }
+
+    #[test]
+    fn play_modes() {
+        let sheet = SpriteSheet::from_grid(&Grid::new(8, 8, 4, 1));
+        let mut player = AnimationPlayer::new(sheet.clip(0..3, PlayMode::Loop));
+        assert_eq!(positions(&mut player, 4), [1, 2, 0, 1]);
+
+        player.play(sheet.clip(0..3, PlayMode::PingPong));
+        assert_eq!(positions(&mut player, 6), [1, 2, 1, 0, 1, 2]);
+
+        player.play(sheet.clip(0..3, PlayMode::Once));
+        assert_eq!(player.update(0.25), None);
+        assert_eq!(player.update(0.1), Some(AnimationEvent::Finished));
+        assert!(player.is_finished());
+        assert_eq!(player.frame_index(), Some(2));
+        assert_eq!(player.update(1.0), None);
+    }
+
+    #[test]
+    fn durations_and_flips() {
+        let sheet = SpriteSheet::from_grid(&Grid::new(8, 8, 2, 1));
+        let clip = AnimationClip::new(PlayMode::Loop)
+            .frame(1, 0.5)
+            .frame(0, 0.1);
+        assert!((clip.duration() - 0.6).abs() < 1e-6);
+        let mut player = AnimationPlayer::new(clip);
+        assert_eq!(player.update(0.4), None);
+        assert_eq!(player.frame_index(), Some(1));
+        assert_eq!(player.update(0.3), Some(AnimationEvent::Looped));
+        assert_eq!(player.frame_index(), Some(1));
+
+        player.flip_x = true;
+        assert_eq!(
+            player.source_rec(&sheet),
+            Rectangle::new(8.0, 0.0, -8.0, 8.0)
+        );
+    }
+
+    #[test]
+    fn huge_and_non_finite_deltas() {
+        let sheet = SpriteSheet::from_grid(&Grid::new(8, 8, 4, 1));
+        let mut player = AnimationPlayer::new(sheet.clip(0..3, PlayMode::PingPong));
+        assert_eq!(player.update(0.15), None);
+        assert_eq!(player.position(), 1);
+        assert_eq!(player.update(f32::INFINITY), None);
+        assert_eq!(player.update(f32::NAN), None);
+        assert_eq!(player.position(), 1);
+        // Round trip of 0.4 seconds from position 1 returns to it
+        assert_eq!(player.update(4e9), Some(AnimationEvent::Looped));
+        assert_eq!(player.update(1e6 + 0.05), Some(AnimationEvent::Looped));
+
+        player.play(sheet.clip(0..3, PlayMode::Once));
+        assert_eq!(player.update(f32::MAX), Some(AnimationEvent::Finished));
+        assert_eq!(player.frame_index(), Some(2));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn texture_packer_and_aseprite_json() {
+        let texture_packer = r#"{
+            "frames": {
+                "run_0": {"frame": {"x": 0, "y": 0, "w": 10, "h": 20}, "rotated": true,
+                          "spriteSourceSize": {"x": 2, "y": 1, "w": 10, "h": 20},
+                          "sourceSize": {"w": 14, "h": 22}},
+                "run_1": {"frame": {"x": 20, "y": 0, "w": 10, "h": 20}, "rotated": false}
+            },
+            "animations": {"run": ["run_1", "run_0"]}
+        }"#;
+        let sheet = SpriteSheet::from_json(texture_packer).unwrap();
+        let sprite = sheet.frames[0].sprite;
+        assert_eq!(sprite.rec, Rectangle::new(0.0, 0.0, 20.0, 10.0));
+        assert_eq!(sprite.offset, Vector2::new(2.0, 1.0));
+        assert_eq!(sprite.source_size, Vector2::new(14.0, 22.0));
+        assert_eq!(sheet.clips["run"].frames[0].index, 1);
+
+        let aseprite = r#"{
+            "frames": [
+                {"filename": "a 0", "frame": {"x": 0, "y": 0, "w": 8, "h": 8}, "duration": 100},
+                {"filename": "a 1", "frame": {"x": 8, "y": 0, "w": 8, "h": 8}, "duration": 250},
+                {"filename": "a 2", "frame": {"x": 16, "y": 0, "w": 8, "h": 8}, "duration": 100}
+            ],
+            "meta": {"frameTags": [
+                {"name": "idle", "from": 0, "to": 2, "direction": "pingpong"},
+                {"name": "back", "from": 1, "to": 2, "direction": "reverse", "repeat": "1"}
+            ]}
+        }"#;
+        let sheet = SpriteSheet::from_json(aseprite).unwrap();
+        assert_eq!(sheet.frames[1].duration, 0.25);
+        assert_eq!(sheet.clips["idle"].mode, PlayMode::PingPong);
+        let back = &sheet.clips["back"];
+        assert_eq!(back.mode, PlayMode::Once);
+        assert_eq!(back.frames[0].index, 2);
+
+        assert!(SpriteSheet::from_json("{}").is_err());
+    }
+}
@@ -0,0 +1,295 @@
+//! Golden Image Testing Functions
+//!
+//! Render a frame or an [`ImageCanvas`](../canvas/struct.ImageCanvas.html) to
+//! an `Image` and compare it against a stored PNG baseline:
+//!
+//! ```ignore
+//! let image = render_canvas(64, 32, BLACK, |canvas| draw_hud(canvas))?;
+//! assert_golden("tests/golden/hud.png", &image, Tolerance::new(2, 0));
+//! ```
+//!
+//! On mismatch `hud.actual.png`, `hud.expected.png` and `hud.diff.png` are
+//! written next to the baseline. Set `RAYLIB_UPDATE_GOLDEN=1` to (re)write
+//! baselines instead of comparing.
+
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::canvas::ImageCanvas;
+use crate::core::{get_screen_height, get_screen_width, take_screenshot, DrawHandle, Window};
+use crate::textures::{export_image, load_image, load_image_ex};
+use crate::{Color, Error, Image, Result};
+
+/// Environment variable that switches golden checks to updating baselines
+pub const UPDATE_GOLDEN_ENV: &str = "RAYLIB_UPDATE_GOLDEN";
+
+/// Allowed difference between an image and its baseline
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Tolerance {
+    /// Maximum per-channel difference for a pixel to still match
+    pub channel: u8,
+    /// Maximum number of pixels allowed to differ
+    pub max_differing_pixels: usize,
+}
+impl Tolerance {
+    pub fn new(channel: u8, max_differing_pixels: usize) -> Tolerance {
+        Tolerance {
+            channel: channel,
+            max_differing_pixels: max_differing_pixels,
+        }
+    }
+}
+
+/// Result of comparing an image against a baseline
+#[derive(Debug)]
+pub struct Comparison {
+    /// Number of pixels outside the channel tolerance
+    pub differing_pixels: usize,
+    /// Largest per-channel difference found
+    pub max_channel_difference: u8,
+    /// Differing pixels in red over a dimmed grayscale of the baseline
+    pub diff: Image,
+    tolerance: Tolerance,
+}
+impl Comparison {
+    /// Check if the image matches within tolerance
+    pub fn is_match(&self) -> bool {
+        self.differing_pixels <= self.tolerance.max_differing_pixels
+    }
+}
+
+/// Compare two images pixel by pixel, decoded as RGBA (32bit)
+pub fn compare_images(
+    actual: &Image,
+    expected: &Image,
+    tolerance: Tolerance,
+) -> Result<Comparison> {
+    if (actual.width(), actual.height()) != (expected.width(), expected.height()) {
+        return Err(Error::InvalidData(format!(
+            "image is {}x{}, baseline is {}x{}",
+            actual.width(),
+            actual.height(),
+            expected.width(),
+            expected.height()
+        )));
+    }
+    let actual_colors = actual.get_colors()?;
+    let expected_colors = expected.get_colors()?;
+    let mut differing_pixels = 0;
+    let mut max_channel_difference = 0;
+    let diff: Vec<Color> = actual_colors
+        .iter()
+        .zip(expected_colors.iter())
+        .map(|(a, e)| {
+            let difference = [(a.r, e.r), (a.g, e.g), (a.b, e.b), (a.a, e.a)]
+                .iter()
+                .map(|&(a, e)| a.abs_diff(e))
+                .max()
+                .unwrap_or(0);
+            max_channel_difference = max_channel_difference.max(difference);
+            if difference > tolerance.channel {
+                differing_pixels += 1;
+                Color::new(255, 0, 0, 255)
+            } else {
+                let gray = ((u32::from(e.r) + u32::from(e.g) + u32::from(e.b)) / 9) as u8;
+                Color::new(gray, gray, gray, 255)
+            }
+        })
+        .collect();
+    Ok(Comparison {
+        differing_pixels: differing_pixels,
+        max_channel_difference: max_channel_difference,
+        diff: load_image_ex(&diff, actual.width(), actual.height())?,
+        tolerance: tolerance,
+    })
+}
+
+/// Compare an image against a PNG baseline, panics on mismatch
+///
+/// NOTE: Writes `<name>.actual.png`, `<name>.expected.png` and `<name>.diff.png`
+/// next to the baseline on mismatch, rewrites the baseline if
+/// `RAYLIB_UPDATE_GOLDEN` is `1` (or `true`, `yes`)
+pub fn assert_golden<S: Into<String>>(baseline: S, actual: &Image, tolerance: Tolerance) {
+    let baseline = PathBuf::from(baseline.into());
+    let update = env::var(UPDATE_GOLDEN_ENV).map_or(false, |value| is_truthy(&value));
+    if update {
+        if let Some(dir) = baseline.parent() {
+            fs::create_dir_all(dir).expect("failed to create baseline directory");
+        }
+        write_image(&baseline, actual);
+        return;
+    }
+    if !baseline.exists() {
+        write_image(&sibling(&baseline, "actual"), actual);
+        panic!(
+            "missing golden image `{}`, rerun with {}=1 to create it",
+            baseline.display(),
+            UPDATE_GOLDEN_ENV
+        );
+    }
+    let expected = load_image(path_string(&baseline)).unwrap();
+    let comparison = match compare_images(actual, &expected, tolerance) {
+        Ok(comparison) => comparison,
+        Err(error) => {
+            write_image(&sibling(&baseline, "actual"), actual);
+            panic!("golden image `{}` mismatch: {}", baseline.display(), error);
+        }
+    };
+    if !comparison.is_match() {
+        write_image(&sibling(&baseline, "actual"), actual);
+        write_image(&sibling(&baseline, "expected"), &expected);
+        write_image(&sibling(&baseline, "diff"), &comparison.diff);
+        panic!(
+            "golden image `{}` mismatch: {} pixels differ (max channel difference {}), \
+             allowed {} pixels beyond {}",
+            baseline.display(),
+            comparison.differing_pixels,
+            comparison.max_channel_difference,
+            tolerance.max_differing_pixels,
+            tolerance.channel
+        );
+    }
+}
+
+/// Render into a new R8G8B8A8 image through a software canvas
+pub fn render_canvas<F>(width: i32, height: i32, background: Color, draw: F) -> Result<Image>
+where
+    F: FnOnce(&mut ImageCanvas),
+{
+    let mut image = load_image_ex(
+        &vec![background; (width.max(0) * height.max(0)) as usize],
+        width,
+        height,
+    )?;
+    draw(&mut ImageCanvas::new(&mut image)?);
+    Ok(image)
+}
+
+/// Render a frame into a screen-sized render texture and read it back as an image
+///
+/// NOTE: `draw` runs in texture mode, ending it flushes the batched draws
+/// before the read back
+pub fn render_frame<F: FnOnce(&mut DrawHandle<'_>)>(window: &Window, draw: F) -> Result<Image> {
+    let mut target = window.load_render_texture(get_screen_width(), get_screen_height())?;
+    {
+        let mut d = window.begin_drawing();
+        let mut texture_mode = d.begin_texture_mode(&mut target);
+        draw(&mut texture_mode);
+    }
+    target.to_image()
+}
+
+/// Capture current screen to an image
+///
+/// NOTE: raylib batches draws until `EndDrawing()`, draws still queued are
+/// missing from the capture, use `render_frame()` to capture a frame
+pub fn capture_screen() -> Result<Image> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let path = env::temp_dir().join(format!(
+        "raylib-capture-{}-{}.png",
+        process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    // Reserve the file first, a file or link already at the path is an error
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .map_err(|e| Error::LoadFailed(e.to_string()))?;
+    take_screenshot(path_string(&path));
+    let image = load_image(path_string(&path));
+    let _ = fs::remove_file(&path);
+    image?.to_rgba8()
+}
+
+// Environment values that enable baseline updates
+fn is_truthy(value: &str) -> bool {
+    ["1", "true", "yes"]
+        .iter()
+        .any(|truthy| value.trim().eq_ignore_ascii_case(truthy))
+}
+
+// Write a baseline or mismatch image, a failed write fails the test
+fn write_image(path: &Path, image: &Image) {
+    if let Err(error) = export_image(path_string(path), image) {
+        panic!(
+            "failed to write golden image `{}`: {}",
+            path.display(),
+            error
+        );
+    }
+}
+
+// Path for an output image next to the baseline, `hud.png` -> `hud.diff.png`
+fn sibling(baseline: &Path, kind: &str) -> PathBuf {
+    let stem = baseline.file_stem().unwrap_or_default().to_string_lossy();
+    baseline.with_file_name(format!("{}.{}.png", stem, kind))
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Color = Color {
+        r: 0,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+
+    #[test]
+    fn tolerance_budget() {
+        let expected = render_canvas(4, 4, BLACK, |_| {}).unwrap();
+        let actual = render_canvas(4, 4, BLACK, |canvas| {
+            canvas.draw_pixel(0, 0, Color::new(3, 0, 0, 255));
+            canvas.draw_pixel(1, 0, Color::new(40, 0, 0, 255));
+        })
+        .unwrap();
+
+        let strict = compare_images(&actual, &expected, Tolerance::default()).unwrap();
+        assert_eq!(strict.differing_pixels, 2);
+        assert_eq!(strict.max_channel_difference, 40);
+        assert!(!strict.is_match());
+        assert_eq!(
+            strict.diff.get_pixel(1, 0).unwrap(),
+            Color::new(255, 0, 0, 255)
+        );
+        assert_eq!(strict.diff.get_pixel(2, 0).unwrap(), BLACK);
+
+        let loose = compare_images(&actual, &expected, Tolerance::new(4, 1)).unwrap();
+        assert_eq!(loose.differing_pixels, 1);
+        assert!(loose.is_match());
+    }
+
+    #[test]
+    fn size_mismatch_is_an_error() {
+        let a = render_canvas(4, 4, BLACK, |_| {}).unwrap();
+        let b = render_canvas(4, 2, BLACK, |_| {}).unwrap();
+        assert!(compare_images(&a, &b, Tolerance::default()).is_err());
+    }
+
+    #[test]
+    fn update_values() {
+        assert!(is_truthy("1"));
+        assert!(is_truthy("TRUE"));
+        assert!(!is_truthy("0"));
+        assert!(!is_truthy("false"));
+        assert!(!is_truthy(""));
+    }
+
+    #[test]
+    fn output_names() {
+        let path = Path::new("tests/golden/hud.png");
+        assert_eq!(
+            sibling(path, "diff"),
+            Path::new("tests/golden/hud.diff.png")
+        );
+    }
+}
@@ -1,8 +1,111 @@
 //! Font Loading and Text Drawing Functions
 
 use std::ffi::CString;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::raw::c_int;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use crate::{raw, Color};
+use crate::core::{DrawHandle, Window};
+use crate::{raw, Color, Error, Font, Result, Vector2};
+
+mod layout;
+mod markup;
+
+pub use self::layout::{
+    FontMetrics, GlyphRun, MonospaceMetrics, PositionedGlyph, TextAlign, TextBlock, TextLayout,
+    TextLine, TextWrap,
+};
+pub use self::markup::{
+    parse_color, parse_markup, RichText, RichTextBlock, RichTextStyle, TextStyle, ICON_CHARACTER,
+};
+
+//------------------------------------------------------------------------------
+// Font loading functions
+//------------------------------------------------------------------------------
+
+impl Window {
+    /// Get the default Font
+    ///
+    /// NOTE: The default font is owned by raylib, dropping it does nothing
+    pub fn get_font_default(&self) -> Font<'_> {
+        Font::from_raw(unsafe { raw::GetFontDefault() }, false)
+    }
+    /// Load font from file into GPU memory (VRAM)
+    ///
+    /// NOTE: Supports TTF/OTF, BMFont (`.fnt`) and image fonts
+    pub fn load_font<S: Into<String>>(&self, file_name: S) -> Result<Font<'_>> {
+        let file_name = file_name.into();
+        let raw_file_name = CString::new(file_name.clone()).unwrap();
+        let raw_font = unsafe { raw::LoadFont(raw_file_name.as_ptr()) };
+        font_or_error(raw_font, file_name)
+    }
+    /// Load font from file with extended parameters
+    ///
+    /// NOTE: An empty `chars` set loads the default 95 ASCII characters (32..126)
+    pub fn load_font_ex<S: Into<String>>(
+        &self,
+        file_name: S,
+        font_size: i32,
+        chars: &[char],
+    ) -> Result<Font<'_>> {
+        let file_name = file_name.into();
+        let raw_file_name = CString::new(file_name.clone()).unwrap();
+        let mut raw_chars: Vec<c_int> = chars.iter().map(|&c| c as c_int).collect();
+        let raw_chars_ptr = if raw_chars.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            raw_chars.as_mut_ptr()
+        };
+        let raw_font = unsafe {
+            raw::LoadFontEx(
+                raw_file_name.as_ptr(),
+                font_size,
+                raw_chars.len() as c_int,
+                raw_chars_ptr,
+            )
+        };
+        font_or_error(raw_font, file_name)
+    }
+}
+
+impl<'w> Font<'w> {
+    /// Load font from TTF/OTF file data in memory, `ext` is the file extension (`"ttf"`)
+    ///
+    /// NOTE: raylib 2.0 only loads fonts from files, the data goes through a
+    /// temporary file. BMFont data can't be loaded this way, its pages are
+    /// separate files. `ext` must be alphanumeric.
+    pub fn from_memory(
+        window: &'w Window,
+        data: &[u8],
+        ext: &str,
+        font_size: i32,
+        chars: &[char],
+    ) -> Result<Font<'w>> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let ext = font_extension(ext)?;
+        let path = std::env::temp_dir().join(format!(
+            "raylib-font-{}-{}.{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst),
+            ext
+        ));
+        // A file or link already at the path is an error, it isn't overwritten
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| Error::LoadFailed(e.to_string()))?;
+        if let Err(e) = file.write_all(data) {
+            let _ = fs::remove_file(&path);
+            return Err(Error::LoadFailed(e.to_string()));
+        }
+        drop(file);
+        let font = window.load_font_ex(path.to_string_lossy(), font_size, chars);
+        let _ = fs::remove_file(&path);
+        font.map_err(|_| Error::LoadFailed(format!("<font data .{}>", ext)))
+    }
+}
 
 //------------------------------------------------------------------------------
 // Text drawing functions
@@ -14,3 +117,113 @@ pub fn draw_text<S: Into<String>>(text: S, pos_x: i32, pos_y: i32, font_size: i3
     let raw_color = color.into_raw();
     unsafe { raw::DrawText(raw_text.as_ptr(), pos_x, pos_y, font_size, raw_color) }
 }
+impl DrawHandle<'_> {
+    /// Draw text using font and additional parameters
+    pub fn draw_text_ex<S: Into<String>>(
+        &mut self,
+        font: &Font,
+        text: S,
+        position: Vector2,
+        font_size: f32,
+        spacing: f32,
+        tint: Color,
+    ) {
+        let raw_text = CString::new(text.into()).unwrap();
+        unsafe {
+            raw::DrawTextEx(
+                font.as_raw(),
+                raw_text.as_ptr(),
+                position.into_raw(),
+                font_size,
+                spacing,
+                tint.into_raw(),
+            )
+        }
+    }
+}
+
+impl DrawHandle<'_> {
+    /// Draw text laid out by a `TextLayout` with the font it was measured with
+    pub fn draw_text_block(
+        &mut self,
+        font: &Font,
+        block: &TextBlock,
+        position: Vector2,
+        tint: Color,
+    ) {
+        for run in block.runs() {
+            let run_position =
+                Vector2::new(position.x + run.position.x, position.y + run.position.y);
+            self.draw_text_ex(
+                font,
+                run.text,
+                run_position,
+                run.font_size,
+                block.spacing,
+                tint,
+            );
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// Text misc. functions
+//------------------------------------------------------------------------------
+
+/// Measure string width for default font
+pub fn measure_text<S: Into<String>>(text: S, font_size: i32) -> i32 {
+    let raw_text = CString::new(text.into()).unwrap();
+    unsafe { raw::MeasureText(raw_text.as_ptr(), font_size) }
+}
+/// Measure string size for Font
+pub fn measure_text_ex<S: Into<String>>(
+    font: &Font,
+    text: S,
+    font_size: f32,
+    spacing: f32,
+) -> Vector2 {
+    let raw_text = CString::new(text.into()).unwrap();
+    let raw_size =
+        unsafe { raw::MeasureTextEx(font.as_raw(), raw_text.as_ptr(), font_size, spacing) };
+    Vector2::from_raw(raw_size)
+}
+/// Get index position for a unicode character on font
+///
+/// NOTE: Returns 0 for characters missing from the font
+pub fn get_glyph_index(font: &Font, character: char) -> usize {
+    unsafe { raw::GetGlyphIndex(font.as_raw(), character as c_int) as usize }
+}
+
+// raylib falls back to the default font when loading fails
+// File extension for font data, it becomes part of a temporary file name
+fn font_extension(ext: &str) -> Result<&str> {
+    let ext = ext.trim_start_matches('.');
+    if !ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric()) {
+        Ok(ext)
+    } else {
+        Err(Error::InvalidData(format!("font extension `{}`", ext)))
+    }
+}
+
+fn font_or_error<'w>(raw_font: raw::Font, source: String) -> Result<Font<'w>> {
+    let default_id = unsafe { raw::GetFontDefault() }.texture.id;
+    if raw_font.chars.is_null() || raw_font.texture.id == 0 || raw_font.texture.id == default_id {
+        Err(Error::LoadFailed(source))
+    } else {
+        Ok(Font::from_raw(raw_font, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn font_extensions() {
+        assert_eq!(font_extension("ttf").unwrap(), "ttf");
+        assert_eq!(font_extension(".OTF").unwrap(), "OTF");
+        assert!(font_extension("").is_err());
+        assert!(font_extension("../../x").is_err());
+        assert!(font_extension("ttf/a").is_err());
+    }
+}
@@ -0,0 +1,642 @@
+//! Text Layout
+//!
+//! Breaks text into positioned glyph runs using font metrics only, so text
+//! can be measured without a window:
+//!
+//! ```ignore
+//! let layout = TextLayout::new(20.0, 1.0)
+//!     .max_width(240.0)
+//!     .align(TextAlign::Justify)
+//!     .max_lines(3)
+//!     .ellipsis("...");
+//! let block = layout.layout(&font, "Some long dialog text");
+//! d.draw_text_block(&font, &block, Vector2::new(16.0, 16.0), colors::BLACK);
+//! ```
+
+use crate::canvas::{ImageCanvas, CANVAS_FONT_SIZE};
+use crate::{Color, Font, Rectangle, Vector2};
+
+/// Glyph metrics used for text layout
+pub trait FontMetrics {
+    /// Size the advances are given at
+    fn base_size(&self) -> f32;
+    /// Horizontal advance of a character at base size
+    fn advance(&self, character: char) -> f32;
+}
+
+impl<'w> FontMetrics for Font<'w> {
+    fn base_size(&self) -> f32 {
+        self.base_size as f32
+    }
+    /// NOTE: Characters missing from the font use the first glyph, like raylib
+    fn advance(&self, character: char) -> f32 {
+        let chars = &self.chars;
+        let glyph = chars
+            .iter()
+            .find(|c| c.value == character as i32)
+            .or_else(|| chars.first());
+        match glyph {
+            Some(glyph) if glyph.advance_x != 0 => glyph.advance_x as f32,
+            Some(glyph) => glyph.rec.width + glyph.offset_x as f32,
+            None => 0.0,
+        }
+    }
+}
+
+/// Fixed advance metrics, for monospaced fonts
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MonospaceMetrics {
+    /// Size the advance is given at
+    pub base_size: f32,
+    /// Horizontal advance of every character at base size
+    pub advance: f32,
+}
+impl MonospaceMetrics {
+    pub fn new(base_size: f32, advance: f32) -> MonospaceMetrics {
+        MonospaceMetrics {
+            base_size: base_size,
+            advance: advance,
+        }
+    }
+    /// Metrics of the `ImageCanvas` bitmap font
+    ///
+    /// NOTE: The canvas font only scales by whole multiples of `CANVAS_FONT_SIZE`
+    pub fn canvas() -> MonospaceMetrics {
+        MonospaceMetrics::new(CANVAS_FONT_SIZE as f32, 6.0)
+    }
+}
+impl FontMetrics for MonospaceMetrics {
+    fn base_size(&self) -> f32 {
+        self.base_size
+    }
+    fn advance(&self, _character: char) -> f32 {
+        self.advance
+    }
+}
+
+/// Line breaking mode
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TextWrap {
+    /// Break between words, words wider than a line break between characters
+    Word,
+    /// Break between any characters
+    Character,
+    /// Only break at newlines
+    NoWrap,
+}
+
+/// Horizontal line alignment
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+    /// Stretch spaces to fill the width, except on the last line of a paragraph
+    Justify,
+}
+
+/// Text layout settings
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TextLayout {
+    /// Font size in pixels
+    pub font_size: f32,
+    /// Pixels between characters
+    pub spacing: f32,
+    /// Width to wrap and align lines in, `None` disables wrapping
+    pub max_width: Option<f32>,
+    /// Line breaking mode
+    pub wrap: TextWrap,
+    /// Horizontal line alignment
+    pub align: TextAlign,
+    /// Distance between lines as a multiple of the font size, 1.5 by default (like raylib)
+    pub line_height: f32,
+    /// Maximum number of lines, the rest is truncated
+    pub max_lines: Option<usize>,
+    /// Text appended to the last line when truncated
+    pub ellipsis: Option<String>,
+    /// Area glyphs must be fully inside, in layout coordinates
+    pub clip: Option<Rectangle>,
+}
+impl TextLayout {
+    pub fn new(font_size: f32, spacing: f32) -> TextLayout {
+        TextLayout {
+            font_size: font_size,
+            spacing: spacing,
+            max_width: None,
+            wrap: TextWrap::Word,
+            align: TextAlign::Left,
+            line_height: 1.5,
+            max_lines: None,
+            ellipsis: None,
+            clip: None,
+        }
+    }
+    /// Set width to wrap and align lines in
+    pub fn max_width(mut self, max_width: f32) -> TextLayout {
+        self.max_width = Some(max_width);
+        self
+    }
+    /// Set line breaking mode
+    pub fn wrap(mut self, wrap: TextWrap) -> TextLayout {
+        self.wrap = wrap;
+        self
+    }
+    /// Set horizontal line alignment
+    pub fn align(mut self, align: TextAlign) -> TextLayout {
+        self.align = align;
+        self
+    }
+    /// Set distance between lines as a multiple of the font size
+    pub fn line_height(mut self, line_height: f32) -> TextLayout {
+        self.line_height = line_height;
+        self
+    }
+    /// Set maximum number of lines
+    pub fn max_lines(mut self, max_lines: usize) -> TextLayout {
+        self.max_lines = Some(max_lines);
+        self
+    }
+    /// Set text appended to the last line when truncated
+    pub fn ellipsis(mut self, ellipsis: &str) -> TextLayout {
+        self.ellipsis = Some(String::from(ellipsis));
+        self
+    }
+    /// Set area glyphs must be fully inside, lines below it are truncated
+    pub fn clip(mut self, clip: Rectangle) -> TextLayout {
+        self.clip = Some(clip);
+        self
+    }
+    /// Lay out text, positions are relative to the top-left corner of the block
+    pub fn layout<M: FontMetrics + ?Sized>(&self, metrics: &M, text: &str) -> TextBlock {
+        let atoms: Vec<Atom> = text
+            .chars()
+            .enumerate()
+            .map(|(index, c)| Atom::new(metrics, c, Some(index), self.font_size, 0))
+            .collect();
+        self.layout_atoms(&atoms, &self.ellipsis_atoms(metrics, self.font_size, 0))
+    }
+    /// Measure the size of laid out text
+    pub fn measure<M: FontMetrics + ?Sized>(&self, metrics: &M, text: &str) -> Vector2 {
+        self.layout(metrics, text).size
+    }
+
+    pub(crate) fn ellipsis_atoms<M: FontMetrics + ?Sized>(
+        &self,
+        metrics: &M,
+        size: f32,
+        span: usize,
+    ) -> Vec<Atom> {
+        let ellipsis = self.ellipsis.as_ref().map_or("", String::as_str);
+        ellipsis
+            .chars()
+            .map(|c| Atom::new(metrics, c, None, size, span))
+            .collect()
+    }
+
+    // Break, truncate and position pre-measured characters
+    pub(crate) fn layout_atoms(&self, atoms: &[Atom], ellipsis: &[Atom]) -> TextBlock {
+        let max_width = match self.wrap {
+            TextWrap::NoWrap => None,
+            _ => self.max_width,
+        };
+        let mut lines: Vec<(Vec<Atom>, bool)> = Vec::new();
+        for paragraph in atoms.split(|a| a.character == '\n') {
+            match max_width {
+                Some(max_width) => self.break_paragraph(paragraph, max_width, &mut lines),
+                None => lines.push((paragraph.to_vec(), true)),
+            }
+        }
+
+        // Vertical truncation
+        let mut visible = lines.len().min(self.max_lines.unwrap_or(usize::MAX));
+        if let Some(clip) = self.clip {
+            let mut y = 0.0;
+            for (i, (line, _)) in lines.iter().enumerate().take(visible) {
+                if y + self.line_size(line) > clip.y + clip.height {
+                    visible = i;
+                    break;
+                }
+                y += self.line_size(line) * self.line_height;
+            }
+        }
+        let mut truncated = visible < lines.len();
+        lines.truncate(visible);
+
+        // Horizontal truncation of unwrapped lines
+        let fit_width = self.max_width.or_else(|| self.clip.map(|c| c.x + c.width));
+        if let Some(fit_width) = fit_width {
+            for (i, (line, last)) in lines.iter_mut().enumerate() {
+                let overflows = self.width(line) > fit_width;
+                if (overflows && max_width.is_none()) || (truncated && i + 1 == visible) {
+                    if !ellipsis.is_empty() {
+                        self.ellipsize(line, ellipsis, fit_width);
+                        *last = true;
+                    }
+                    truncated |= overflows;
+                }
+            }
+        }
+
+        // Alignment and positioning
+        let widest = lines
+            .iter()
+            .map(|(line, _)| self.width(line))
+            .fold(0.0, f32::max);
+        let align_width = self.max_width.unwrap_or(widest);
+        let mut block = TextBlock {
+            lines: Vec::with_capacity(lines.len()),
+            size: Vector2::new(widest, 0.0),
+            spacing: self.spacing,
+            truncated: truncated,
+        };
+        let mut y = 0.0;
+        for (line, last) in lines {
+            let line_size = self.line_size(&line);
+            let width = self.width(&line);
+            let gaps = line.iter().filter(|a| is_space(a.character)).count();
+            let (mut x, stretch) = match self.align {
+                TextAlign::Left => (0.0, 0.0),
+                TextAlign::Center => ((align_width - width) / 2.0, 0.0),
+                TextAlign::Right => (align_width - width, 0.0),
+                TextAlign::Justify if !last && gaps > 0 => {
+                    (0.0, (align_width - width).max(0.0) / gaps as f32)
+                }
+                TextAlign::Justify => (0.0, 0.0),
+            };
+            let line_x = x;
+            let mut glyphs = Vec::with_capacity(line.len());
+            for atom in &line {
+                let glyph = PositionedGlyph {
+                    character: atom.character,
+                    index: atom.index,
+                    position: Vector2::new(x, y + line_size - atom.size),
+                    font_size: atom.size,
+                    advance: atom.advance,
+                    span: atom.span,
+                };
+                if self.clip.map_or(true, |clip| glyph.inside(clip)) {
+                    glyphs.push(glyph);
+                }
+                x += atom.advance + self.spacing;
+                // Justified gaps break runs, so each run can be drawn as plain text
+                if is_space(atom.character) {
+                    x += stretch;
+                }
+            }
+            block.size.y = y + line_size;
+            block.lines.push(TextLine {
+                glyphs: glyphs,
+                position: Vector2::new(line_x, y),
+                width: width + stretch * gaps as f32,
+                height: line_size,
+            });
+            y += line_size * self.line_height;
+        }
+        block
+    }
+
+    fn break_paragraph(
+        &self,
+        paragraph: &[Atom],
+        max_width: f32,
+        lines: &mut Vec<(Vec<Atom>, bool)>,
+    ) {
+        let mut line: Vec<Atom> = Vec::new();
+        let mut continuation = false;
+        let mut rest = paragraph;
+        while !rest.is_empty() {
+            // Next token: a run of spaces, a word, or a single character
+            let space = is_space(rest[0].character);
+            let len = match self.wrap {
+                TextWrap::Word => rest
+                    .iter()
+                    .position(|a| is_space(a.character) != space)
+                    .unwrap_or(rest.len()),
+                _ => 1,
+            };
+            let (token, tail) = rest.split_at(len);
+            rest = tail;
+
+            if space {
+                // Spaces at a wrap point are dropped
+                if !(continuation && line.is_empty()) {
+                    line.extend_from_slice(token);
+                }
+                continue;
+            }
+            let mut candidate = line.clone();
+            candidate.extend_from_slice(token);
+            if self.width(&candidate) <= max_width {
+                line = candidate;
+                continue;
+            }
+            if !trim_end(&line).is_empty() {
+                lines.push((trim_end(&line).to_vec(), false));
+                line.clear();
+                continuation = true;
+            }
+            // Break a word wider than the line between characters
+            for &atom in token {
+                line.push(atom);
+                if line.len() > 1 && self.width(&line) > max_width {
+                    line.pop();
+                    lines.push((trim_end(&line).to_vec(), false));
+                    line.clear();
+                    line.push(atom);
+                    continuation = true;
+                }
+            }
+        }
+        lines.push((trim_end(&line).to_vec(), true));
+    }
+
+    // Drop characters until the line and ellipsis fit in `fit_width`
+    fn ellipsize(&self, line: &mut Vec<Atom>, ellipsis: &[Atom], fit_width: f32) {
+        loop {
+            let kept = trim_end(line).len();
+            line.truncate(kept);
+            let mut candidate = line.clone();
+            candidate.extend_from_slice(ellipsis);
+            if line.is_empty() || self.width(&candidate) <= fit_width {
+                *line = candidate;
+                return;
+            }
+            line.pop();
+        }
+    }
+
+    fn width(&self, atoms: &[Atom]) -> f32 {
+        let advances: f32 = atoms.iter().map(|a| a.advance).sum();
+        advances + self.spacing * atoms.len().saturating_sub(1) as f32
+    }
+
+    // Largest font size on a line, empty lines use the layout font size
+    fn line_size(&self, atoms: &[Atom]) -> f32 {
+        atoms
+            .iter()
+            .map(|a| a.size)
+            .fold(None, |max: Option<f32>, size| {
+                Some(max.map_or(size, |m| m.max(size)))
+            })
+            .unwrap_or(self.font_size)
+    }
+}
+
+// A measured character waiting for layout
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct Atom {
+    pub(crate) character: char,
+    pub(crate) index: Option<usize>,
+    pub(crate) advance: f32,
+    pub(crate) size: f32,
+    pub(crate) span: usize,
+}
+impl Atom {
+    pub(crate) fn new<M: FontMetrics + ?Sized>(
+        metrics: &M,
+        character: char,
+        index: Option<usize>,
+        size: f32,
+        span: usize,
+    ) -> Atom {
+        Atom {
+            character: character,
+            index: index,
+            advance: metrics.advance(character) * size / metrics.base_size(),
+            size: size,
+            span: span,
+        }
+    }
+}
+
+fn is_space(c: char) -> bool {
+    c != '\n' && c.is_whitespace()
+}
+
+fn trim_end(atoms: &[Atom]) -> &[Atom] {
+    let end = atoms
+        .iter()
+        .rposition(|a| !is_space(a.character))
+        .map_or(0, |i| i + 1);
+    &atoms[..end]
+}
+
+/// Character placed by a `TextLayout`
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PositionedGlyph {
+    pub character: char,
+    /// Character index in the source text, `None` for an inserted ellipsis
+    pub index: Option<usize>,
+    /// Top-left corner, relative to the block
+    pub position: Vector2,
+    pub font_size: f32,
+    /// Horizontal advance, excluding spacing
+    pub advance: f32,
+    /// Markup span the character belongs to, 0 for plain text
+    pub span: usize,
+}
+impl PositionedGlyph {
+    fn inside(&self, clip: Rectangle) -> bool {
+        // Small tolerance for accumulated float error
+        let epsilon = 1e-3;
+        self.position.x >= clip.x - epsilon
+            && self.position.y >= clip.y - epsilon
+            && self.position.x + self.advance <= clip.x + clip.width + epsilon
+            && self.position.y + self.font_size <= clip.y + clip.height + epsilon
+    }
+}
+
+/// Consecutive glyphs that can be drawn with one text call
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GlyphRun {
+    pub text: String,
+    /// Top-left corner, relative to the block
+    pub position: Vector2,
+    pub font_size: f32,
+    pub width: f32,
+    /// Markup span of the run, 0 for plain text
+    pub span: usize,
+}
+
+/// Line of a `TextBlock`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TextLine {
+    /// Visible glyphs, clipped glyphs are left out
+    pub glyphs: Vec<PositionedGlyph>,
+    /// Top-left corner after alignment, relative to the block
+    pub position: Vector2,
+    pub width: f32,
+    /// Largest font size on the line
+    pub height: f32,
+}
+
+/// Laid out text
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TextBlock {
+    pub lines: Vec<TextLine>,
+    /// Widest line and bottom of the last line
+    pub size: Vector2,
+    /// Pixels between characters
+    pub spacing: f32,
+    /// Some text was cut off by `max_lines`, `clip` or an unwrapped `max_width`
+    pub truncated: bool,
+}
+impl TextBlock {
+    /// All visible glyphs in order
+    pub fn glyphs(&self) -> impl Iterator<Item = &PositionedGlyph> {
+        self.lines.iter().flat_map(|line| line.glyphs.iter())
+    }
+    /// Group glyphs into runs sharing a line, size and span with regular spacing
+    pub fn runs(&self) -> Vec<GlyphRun> {
+        let mut runs: Vec<GlyphRun> = Vec::new();
+        for line in &self.lines {
+            let mut previous: Option<&PositionedGlyph> = None;
+            for glyph in &line.glyphs {
+                let joins = previous.map_or(false, |p| {
+                    let expected = p.position.x + p.advance + self.spacing;
+                    p.font_size == glyph.font_size
+                        && p.span == glyph.span
+                        && p.position.y == glyph.position.y
+                        && (glyph.position.x - expected).abs() < 1e-3
+                });
+                match runs.last_mut() {
+                    Some(run) if joins => {
+                        run.text.push(glyph.character);
+                        run.width = glyph.position.x + glyph.advance - run.position.x;
+                    }
+                    _ => runs.push(GlyphRun {
+                        text: glyph.character.to_string(),
+                        position: glyph.position,
+                        font_size: glyph.font_size,
+                        width: glyph.advance,
+                        span: glyph.span,
+                    }),
+                }
+                previous = Some(glyph);
+            }
+        }
+        runs
+    }
+}
+
+impl<'a> ImageCanvas<'a> {
+    /// Draw laid out text (using the canvas bitmap font)
+    ///
+    /// NOTE: Lay out with `MonospaceMetrics::canvas()` and a multiple of `CANVAS_FONT_SIZE`
+    pub fn draw_text_block(&mut self, block: &TextBlock, position: Vector2, color: Color) {
+        for glyph in block.glyphs() {
+            let x = (position.x + glyph.position.x).round() as i32;
+            let y = (position.y + glyph.position.y).round() as i32;
+            let c = glyph.character.to_string();
+            self.draw_text(&c, x, y, glyph.font_size as i32, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::colors;
+    use crate::textures::load_image_ex;
+
+    // 10 pixels per character at size 10, no spacing
+    fn mono() -> MonospaceMetrics {
+        MonospaceMetrics::new(10.0, 10.0)
+    }
+
+    fn line_texts(block: &TextBlock) -> Vec<String> {
+        block
+            .lines
+            .iter()
+            .map(|line| line.glyphs.iter().map(|g| g.character).collect())
+            .collect()
+    }
+
+    #[test]
+    fn word_and_character_wrap() {
+        let layout = TextLayout::new(10.0, 0.0).max_width(100.0);
+        let block = layout.layout(&mono(), "the quick brown fox\njumps");
+        assert_eq!(line_texts(&block), ["the quick", "brown fox", "jumps"]);
+        assert_eq!(block.lines[1].position.y, 15.0);
+        assert_eq!(block.size, Vector2::new(90.0, 40.0));
+        assert_eq!(block.lines[1].glyphs[0].index, Some(10));
+
+        let block = layout.layout(&mono(), "abcdefghijklm no");
+        assert_eq!(line_texts(&block), ["abcdefghij", "klm no"]);
+
+        let layout = layout.wrap(TextWrap::Character);
+        let block = layout.layout(&mono(), "the quick brown");
+        assert_eq!(line_texts(&block), ["the quick", "brown"]);
+
+        let size = TextLayout::new(20.0, 2.0).measure(&mono(), "abc");
+        assert_eq!(size, Vector2::new(64.0, 20.0));
+    }
+
+    #[test]
+    fn alignment() {
+        let layout = TextLayout::new(10.0, 0.0).max_width(100.0);
+        let text = "aa bb cc dd\nee";
+        let center = layout
+            .clone()
+            .align(TextAlign::Center)
+            .layout(&mono(), text);
+        assert_eq!(center.lines[0].position.x, 10.0);
+        let right = layout.clone().align(TextAlign::Right).layout(&mono(), text);
+        assert_eq!(right.lines[1].position.x, 80.0);
+
+        let justify = layout.align(TextAlign::Justify).layout(&mono(), text);
+        let first = &justify.lines[0];
+        assert_eq!(line_texts(&justify), ["aa bb cc", "dd", "ee"]);
+        assert_eq!(first.width, 100.0);
+        assert_eq!(first.glyphs.last().unwrap().position.x, 90.0);
+        // Last line of a paragraph isn't stretched
+        assert_eq!(justify.lines[1].width, 20.0);
+        assert_eq!(justify.runs().len(), 5);
+    }
+
+    #[test]
+    fn truncation_and_clipping() {
+        let layout = TextLayout::new(10.0, 0.0)
+            .max_width(60.0)
+            .max_lines(2)
+            .ellipsis("..");
+        let block = layout.layout(&mono(), "one two three four");
+        assert_eq!(line_texts(&block), ["one", "two.."]);
+        assert!(block.truncated);
+        assert_eq!(block.lines[1].glyphs[3].index, None);
+
+        let layout = TextLayout::new(10.0, 0.0)
+            .wrap(TextWrap::NoWrap)
+            .clip(Rectangle::new(0.0, 0.0, 50.0, 30.0));
+        let block = layout.layout(&mono(), "abcdefgh\nij\nkl");
+        assert_eq!(line_texts(&block), ["abcde", "ij"]);
+        assert!(block.truncated);
+
+        let block = layout.ellipsis("~").layout(&mono(), "abcdefgh");
+        assert_eq!(line_texts(&block), ["abcd~"]);
+    }
+
+    #[test]
+    fn canvas_text_block() {
+        let block = TextLayout::new(8.0, 0.0).layout(&MonospaceMetrics::canvas(), "ab\nc");
+        let mut image = load_image_ex(&[colors::BLANK; 32 * 32], 32, 32).unwrap();
+        {
+            let mut canvas = ImageCanvas::new(&mut image).unwrap();
+            canvas.draw_text_block(&block, Vector2::new(1.0, 1.0), colors::WHITE);
+        }
+        let lit = |x0: i32, y0: i32| {
+            (y0..y0 + 8)
+                .any(|y| (x0..x0 + 5).any(|x| image.get_pixel(x, y).unwrap() == colors::WHITE))
+        };
+        assert!(lit(1, 1) && lit(7, 1) && lit(1, 13));
+        assert!(!lit(13, 1));
+    }
+}
@@ -0,0 +1,545 @@
+//! Rich Text Markup
+//!
+//! Inline styling with BBCode-like tags, laid out with the `TextLayout`
+//! wrapping rules:
+//!
+//! ```ignore
+//! let text = parse_markup("Found [color=#ff0]12 gold[/color] [icon=coin]! [wave]Yay[/wave]")?;
+//! let style = RichTextStyle::new(&font, colors::WHITE)
+//!     .font("bold", &bold_font)
+//!     .icon("coin", Rectangle::new(0.0, 0.0, 16.0, 16.0));
+//! let block = text.layout(&TextLayout::new(20.0, 1.0).max_width(300.0), &style);
+//! // Every frame, revealing 30 characters per second
+//! let reveal = (elapsed * 30.0) as usize;
+//! d.draw_rich_text(&block, &style, Some(&icons), position, elapsed, reveal);
+//! ```
+//!
+//! Tags, closed with `[/name]` in any order:
+//!
+//! - `[color=#rgb]`, `#rgba`, `#rrggbb` or `#rrggbbaa`
+//! - `[b]` and `[i]`, the `"bold"` and `"italic"` fonts of the style
+//! - `[font=name]` a named font of the style
+//! - `[size=24]` font size in pixels
+//! - `[wave]` or `[wave=amplitude]` and `[shake]` or `[shake=amplitude]`
+//! - `[icon=name]` an inline icon, not closed
+//!
+//! `[[` is a literal `[`.
+
+use std::collections::HashMap;
+
+use super::layout::{Atom, FontMetrics, PositionedGlyph, TextBlock, TextLayout};
+use crate::canvas::ImageCanvas;
+use crate::core::DrawHandle;
+use crate::{colors, Color, Error, Font, Image, Rectangle, Result, Texture2D, Vector2};
+
+/// Character standing in for inline icons in the text
+pub const ICON_CHARACTER: char = '\u{fffc}';
+
+/// Default `[wave]` amplitude in pixels
+const WAVE_AMPLITUDE: f32 = 3.0;
+/// Default `[shake]` amplitude in pixels
+const SHAKE_AMPLITUDE: f32 = 1.5;
+
+/// Resolved style of a run of rich text, `None` uses the defaults
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TextStyle {
+    pub color: Option<Color>,
+    /// Font name in the `RichTextStyle`
+    pub font: Option<String>,
+    pub size: Option<f32>,
+    /// Wave amplitude in pixels
+    pub wave: Option<f32>,
+    /// Shake amplitude in pixels
+    pub shake: Option<f32>,
+    /// Icon name in the `RichTextStyle`, for `ICON_CHARACTER`
+    pub icon: Option<String>,
+}
+impl TextStyle {
+    /// Get drawing offset of a wave or shake effect at `time` (seconds)
+    pub fn effect_offset(&self, index: usize, time: f32) -> Vector2 {
+        let mut offset = Vector2::new(0.0, 0.0);
+        if let Some(amplitude) = self.wave {
+            offset.y += amplitude * (time * 6.0 + index as f32 * 0.6).sin();
+        }
+        if let Some(amplitude) = self.shake {
+            // New random offset 30 times per second
+            let hash = shake_hash(index as u32, (time * 30.0) as u32);
+            let unit = |bits: u32| (bits & 0xffff) as f32 / 65535.0 * 2.0 - 1.0;
+            offset.x += amplitude * unit(hash);
+            offset.y += amplitude * unit(hash >> 16);
+        }
+        offset
+    }
+}
+
+/// Parsed rich text, characters with their style
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RichText {
+    /// Characters with an index into `styles`
+    pub chars: Vec<(char, usize)>,
+    /// Distinct styles, the first is the default style
+    pub styles: Vec<TextStyle>,
+}
+impl RichText {
+    /// Text without markup, icons are `ICON_CHARACTER`
+    pub fn plain_text(&self) -> String {
+        self.chars.iter().map(|&(c, _)| c).collect()
+    }
+    /// Number of characters, for typewriter reveals
+    pub fn char_count(&self) -> usize {
+        self.chars.len()
+    }
+    /// Lay out with `layout` wrapping rules, fonts and sizes from `style`
+    pub fn layout<M: FontMetrics + ?Sized>(
+        &self,
+        layout: &TextLayout,
+        style: &RichTextStyle<M>,
+    ) -> RichTextBlock {
+        let atoms: Vec<Atom> = self
+            .chars
+            .iter()
+            .enumerate()
+            .map(|(index, &(c, span))| {
+                let text_style = &self.styles[span];
+                let size = text_style.size.unwrap_or(layout.font_size);
+                let icon = text_style
+                    .icon
+                    .as_ref()
+                    .and_then(|name| style.icons.get(name));
+                match icon {
+                    Some(rec) if c == ICON_CHARACTER && rec.height > 0.0 => Atom {
+                        character: c,
+                        index: Some(index),
+                        advance: rec.width * size / rec.height,
+                        size: size,
+                        span: span,
+                    },
+                    _ => Atom::new(style.font_for(text_style), c, Some(index), size, span),
+                }
+            })
+            .collect();
+        let ellipsis = layout.ellipsis_atoms(style.font, layout.font_size, 0);
+        RichTextBlock {
+            block: layout.layout_atoms(&atoms, &ellipsis),
+            styles: self.styles.clone(),
+            char_count: self.chars.len(),
+        }
+    }
+}
+
+/// Laid out rich text, glyph spans index `styles`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RichTextBlock {
+    pub block: TextBlock,
+    pub styles: Vec<TextStyle>,
+    /// Number of source characters
+    pub char_count: usize,
+}
+impl RichTextBlock {
+    /// Check if a glyph is shown with `reveal` characters revealed
+    ///
+    /// NOTE: An inserted ellipsis shows once the whole text is revealed
+    pub fn is_revealed(&self, glyph: &PositionedGlyph, reveal: usize) -> bool {
+        glyph
+            .index
+            .map_or(reveal >= self.char_count, |index| index < reveal)
+    }
+}
+
+/// Fonts, icons and default color for rich text
+#[derive(Debug, Clone)]
+pub struct RichTextStyle<'a, M: ?Sized> {
+    /// Default font
+    pub font: &'a M,
+    /// Named fonts, `[b]` uses `"bold"` and `[i]` uses `"italic"`
+    pub fonts: HashMap<String, &'a M>,
+    /// Icon source rectangles in the icon texture, by name
+    pub icons: HashMap<String, Rectangle>,
+    /// Default color
+    pub color: Color,
+}
+impl<'a, M: ?Sized> RichTextStyle<'a, M> {
+    pub fn new(font: &'a M, color: Color) -> RichTextStyle<'a, M> {
+        RichTextStyle {
+            font: font,
+            fonts: HashMap::new(),
+            icons: HashMap::new(),
+            color: color,
+        }
+    }
+    /// Add a named font
+    pub fn font(mut self, name: &str, font: &'a M) -> RichTextStyle<'a, M> {
+        self.fonts.insert(String::from(name), font);
+        self
+    }
+    /// Add a named icon
+    pub fn icon(mut self, name: &str, source_rec: Rectangle) -> RichTextStyle<'a, M> {
+        self.icons.insert(String::from(name), source_rec);
+        self
+    }
+
+    // Unknown font names fall back to the default font
+    fn font_for(&self, style: &TextStyle) -> &'a M {
+        let named = style.font.as_ref().and_then(|name| self.fonts.get(name));
+        named.cloned().unwrap_or(self.font)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Markup parsing
+//------------------------------------------------------------------------------
+
+/// Parse rich text markup, unclosed tags end with the text
+pub fn parse_markup(markup: &str) -> Result<RichText> {
+    let mut text = RichText {
+        chars: Vec::new(),
+        styles: vec![TextStyle::default()],
+    };
+    // Open tags with the style change they make
+    let mut open: Vec<(String, TextStyle)> = Vec::new();
+    let mut span = 0;
+    let mut rest = markup;
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+        if c != '[' {
+            text.chars.push((c, span));
+            continue;
+        }
+        if let Some(escaped) = rest.strip_prefix('[') {
+            rest = escaped;
+            text.chars.push(('[', span));
+            continue;
+        }
+        let end = rest.find(']').ok_or_else(|| invalid("unterminated tag"))?;
+        let tag = rest[..end].trim();
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            let position = open
+                .iter()
+                .rposition(|(open_name, _)| open_name == name)
+                .ok_or_else(|| invalid(&format!("`[/{}]` closes no open tag", name)))?;
+            open.remove(position);
+        } else {
+            let (name, value) = match tag.find('=') {
+                Some(i) => (tag[..i].trim(), Some(tag[i + 1..].trim())),
+                None => (tag, None),
+            };
+            let change = tag_style(name, value)?;
+            if name == "icon" {
+                let style = merge(&open, Some(&change));
+                let icon_span = style_index(&mut text.styles, style);
+                text.chars.push((ICON_CHARACTER, icon_span));
+                continue;
+            }
+            open.push((String::from(name), change));
+        }
+        span = style_index(&mut text.styles, merge(&open, None));
+    }
+    Ok(text)
+}
+
+// Style change made by one opening tag
+fn tag_style(name: &str, value: Option<&str>) -> Result<TextStyle> {
+    let mut style = TextStyle::default();
+    let required = || value.ok_or_else(|| invalid(&format!("`[{}]` needs a value", name)));
+    let number = |default: f32| match value {
+        Some(value) => value
+            .parse::<f32>()
+            .map_err(|_| invalid(&format!("`[{}={}]` is not a number", name, value))),
+        None => Ok(default),
+    };
+    match name {
+        "color" => style.color = Some(parse_color(required()?)?),
+        "b" => style.font = Some(String::from("bold")),
+        "i" => style.font = Some(String::from("italic")),
+        "font" => style.font = Some(String::from(required()?)),
+        "size" => {
+            required()?;
+            style.size = Some(number(0.0)?);
+        }
+        "wave" => style.wave = Some(number(WAVE_AMPLITUDE)?),
+        "shake" => style.shake = Some(number(SHAKE_AMPLITUDE)?),
+        "icon" => style.icon = Some(String::from(required()?)),
+        _ => return Err(invalid(&format!("unknown tag `[{}]`", name))),
+    }
+    Ok(style)
+}
+
+// Combined style of the open tags, inner tags override outer ones
+fn merge(open: &[(String, TextStyle)], extra: Option<&TextStyle>) -> TextStyle {
+    let mut style = TextStyle::default();
+    for change in open.iter().map(|(_, change)| change).chain(extra) {
+        style.color = change.color.or(style.color);
+        style.font = change.font.clone().or(style.font);
+        style.size = change.size.or(style.size);
+        style.wave = change.wave.or(style.wave);
+        style.shake = change.shake.or(style.shake);
+        style.icon = change.icon.clone().or(style.icon);
+    }
+    style
+}
+
+fn style_index(styles: &mut Vec<TextStyle>, style: TextStyle) -> usize {
+    match styles.iter().position(|s| *s == style) {
+        Some(index) => index,
+        None => {
+            styles.push(style);
+            styles.len() - 1
+        }
+    }
+}
+
+/// Parse a `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` color
+pub fn parse_color(hex: &str) -> Result<Color> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    // from_str_radix also accepts a sign
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid_color(hex));
+    }
+    let value = u32::from_str_radix(digits, 16).map_err(|_| invalid_color(hex))?;
+    let nibble = |shift: u32| ((value >> shift) & 0xf) as u8 * 0x11;
+    let byte = |shift: u32| (value >> shift) as u8;
+    match digits.len() {
+        3 => Ok(Color::new(nibble(8), nibble(4), nibble(0), 255)),
+        4 => Ok(Color::new(nibble(12), nibble(8), nibble(4), nibble(0))),
+        6 => Ok(Color::new(byte(16), byte(8), byte(0), 255)),
+        8 => Ok(Color::new(byte(24), byte(16), byte(8), byte(0))),
+        _ => Err(invalid_color(hex)),
+    }
+}
+
+fn invalid_color(hex: &str) -> Error {
+    invalid(&format!("invalid color `{}`", hex))
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidData(format!("rich text markup: {}", message))
+}
+
+// Cheap integer hash for shake offsets
+fn shake_hash(index: u32, step: u32) -> u32 {
+    let mut hash = index.wrapping_mul(0x9e37_79b1) ^ step.wrapping_mul(0x85eb_ca77);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x2c1b_3c6d);
+    hash ^ (hash >> 12)
+}
+
+//------------------------------------------------------------------------------
+// Rich text drawing functions
+//------------------------------------------------------------------------------
+
+impl DrawHandle<'_> {
+    /// Draw laid out rich text, `time` drives effects and `reveal` characters are shown
+    pub fn draw_rich_text(
+        &mut self,
+        text: &RichTextBlock,
+        style: &RichTextStyle<Font>,
+        icons: Option<&Texture2D>,
+        position: Vector2,
+        time: f32,
+        reveal: usize,
+    ) {
+        for glyph in text.block.glyphs() {
+            if !text.is_revealed(glyph, reveal) {
+                continue;
+            }
+            let text_style = &text.styles[glyph.span];
+            let offset = text_style.effect_offset(glyph.index.unwrap_or(0), time);
+            let x = position.x + glyph.position.x + offset.x;
+            let y = position.y + glyph.position.y + offset.y;
+            let icon = text_style
+                .icon
+                .as_ref()
+                .and_then(|name| style.icons.get(name));
+            match (icons, icon) {
+                (Some(icons), Some(&source_rec)) if glyph.character == ICON_CHARACTER => {
+                    let dest_rec = Rectangle::new(x, y, glyph.advance, glyph.font_size);
+                    let origin = Vector2::new(0.0, 0.0);
+                    self.draw_texture_pro(icons, source_rec, dest_rec, origin, 0.0, colors::WHITE);
+                }
+                _ => self.draw_text_ex(
+                    style.font_for(text_style),
+                    glyph.character.to_string(),
+                    Vector2::new(x, y),
+                    glyph.font_size,
+                    0.0,
+                    text_style.color.unwrap_or(style.color),
+                ),
+            }
+        }
+    }
+}
+
+impl<'a> ImageCanvas<'a> {
+    /// Draw laid out rich text (using the canvas bitmap font for every font)
+    ///
+    /// NOTE: Lay out with `MonospaceMetrics::canvas()` and multiples of `CANVAS_FONT_SIZE`
+    pub fn draw_rich_text<M: ?Sized>(
+        &mut self,
+        text: &RichTextBlock,
+        style: &RichTextStyle<M>,
+        icons: Option<&Image>,
+        position: Vector2,
+        time: f32,
+        reveal: usize,
+    ) -> Result<()> {
+        for glyph in text.block.glyphs() {
+            if !text.is_revealed(glyph, reveal) {
+                continue;
+            }
+            let text_style = &text.styles[glyph.span];
+            let offset = text_style.effect_offset(glyph.index.unwrap_or(0), time);
+            let x = (position.x + glyph.position.x + offset.x).round();
+            let y = (position.y + glyph.position.y + offset.y).round();
+            let icon = text_style
+                .icon
+                .as_ref()
+                .and_then(|name| style.icons.get(name));
+            match (icons, icon) {
+                (Some(icons), Some(&source_rec)) if glyph.character == ICON_CHARACTER => {
+                    let dest_rec = Rectangle::new(x, y, glyph.advance.round(), glyph.font_size);
+                    self.draw_image(icons, source_rec, dest_rec, colors::WHITE)?;
+                }
+                _ => self.draw_text(
+                    &glyph.character.to_string(),
+                    x as i32,
+                    y as i32,
+                    glyph.font_size as i32,
+                    text_style.color.unwrap_or(style.color),
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::{MonospaceMetrics, TextLayout};
+    use crate::textures::load_image_ex;
+
+    #[test]
+    fn parse_nested_spans() {
+        let text = parse_markup("a[color=#ff0]b[b]c[/color]d[/b]e[[").unwrap();
+        assert_eq!(text.plain_text(), "abcde[");
+        let style = |i: usize| &text.styles[text.chars[i].1];
+        assert_eq!(*style(0), TextStyle::default());
+        assert_eq!(style(1).color, Some(Color::new(255, 255, 0, 255)));
+        assert_eq!(style(2).font.as_deref(), Some("bold"));
+        assert_eq!(style(2).color, style(1).color);
+        // Closing color inside bold keeps bold
+        assert_eq!(style(3).color, None);
+        assert_eq!(style(3).font, style(2).font);
+        assert_eq!(text.chars[4].1, 0);
+
+        let text = parse_markup("[size=16][wave=2]x[icon=coin][/wave]").unwrap();
+        let icon = &text.styles[text.chars[1].1];
+        assert_eq!(text.chars[1].0, ICON_CHARACTER);
+        assert_eq!(icon.icon.as_deref(), Some("coin"));
+        assert_eq!((icon.size, icon.wave), (Some(16.0), Some(2.0)));
+
+        assert!(parse_markup("[blink]x").is_err());
+        assert!(parse_markup("x[/b]").is_err());
+        assert!(parse_markup("[color=#12]x").is_err());
+        assert!(parse_markup("[size]x").is_err());
+        assert!(parse_markup("[b x").is_err());
+    }
+
+    #[test]
+    fn colors_and_effects() {
+        assert_eq!(
+            parse_color("#1234").unwrap(),
+            Color::new(0x11, 0x22, 0x33, 0x44)
+        );
+        assert_eq!(
+            parse_color("80ff0040").unwrap(),
+            Color::new(0x80, 0xff, 0, 0x40)
+        );
+        assert!(parse_color("#ggg").is_err());
+        assert!(parse_color("#+ff").is_err());
+        assert!(parse_markup("[color=#+ff]x").is_err());
+
+        let wave = TextStyle {
+            wave: Some(2.0),
+            ..TextStyle::default()
+        };
+        assert_eq!(wave.effect_offset(0, 0.0), Vector2::new(0.0, 0.0));
+        assert!(wave.effect_offset(3, 0.5).y.abs() <= 2.0);
+        let shake = TextStyle {
+            shake: Some(1.0),
+            ..TextStyle::default()
+        };
+        let a = shake.effect_offset(5, 1.0);
+        assert_eq!(a, shake.effect_offset(5, 1.0));
+        assert!(a.x.abs() <= 1.0 && a.y.abs() <= 1.0);
+        assert_ne!(a, shake.effect_offset(6, 1.0));
+    }
+
+    #[test]
+    fn layout_with_sizes_icons_and_wrapping() {
+        let metrics = MonospaceMetrics::new(10.0, 10.0);
+        let style = RichTextStyle::new(&metrics, colors::WHITE)
+            .icon("coin", Rectangle::new(0.0, 0.0, 8.0, 4.0));
+        let text = parse_markup("ab [size=20]cd[/size] [icon=coin] ef").unwrap();
+        let layout = TextLayout::new(10.0, 0.0).max_width(70.0);
+        let rich = text.layout(&layout, &style);
+        let lines: Vec<String> = rich
+            .block
+            .lines
+            .iter()
+            .map(|line| line.glyphs.iter().map(|g| g.character).collect())
+            .collect();
+        assert_eq!(lines, ["ab cd", "\u{fffc} ef"]);
+        let glyphs: Vec<&PositionedGlyph> = rich.block.glyphs().collect();
+        // Smaller glyphs sit on the bottom of the line
+        assert_eq!(glyphs[0].position.y, 10.0);
+        assert_eq!(glyphs[3].font_size, 20.0);
+        assert_eq!(rich.block.lines[1].position.y, 30.0);
+        // Icons keep their aspect ratio at the font size
+        assert_eq!(glyphs[5].advance, 20.0);
+        assert_eq!(rich.styles[glyphs[5].span].icon.as_deref(), Some("coin"));
+    }
+
+    #[test]
+    fn canvas_typewriter() {
+        let metrics = MonospaceMetrics::canvas();
+        let style = RichTextStyle::new(&metrics, colors::WHITE)
+            .icon("dot", Rectangle::new(0.0, 0.0, 1.0, 1.0));
+        let text = parse_markup("[color=#f00]a[/color]b[icon=dot]").unwrap();
+        let rich = text.layout(&TextLayout::new(8.0, 0.0), &style);
+        let icons = load_image_ex(&[colors::BLUE], 1, 1).unwrap();
+
+        let draw = |reveal: usize| {
+            let mut image = load_image_ex(&[colors::BLANK; 32 * 8], 32, 8).unwrap();
+            {
+                let mut canvas = ImageCanvas::new(&mut image).unwrap();
+                canvas
+                    .draw_rich_text(
+                        &rich,
+                        &style,
+                        Some(&icons),
+                        Vector2::new(0.0, 0.0),
+                        0.0,
+                        reveal,
+                    )
+                    .unwrap();
+            }
+            image.get_colors().unwrap()
+        };
+        let count = |pixels: &[Color], color: Color| pixels.iter().filter(|&&c| c == color).count();
+
+        let partial = draw(1);
+        assert!(count(&partial, Color::new(255, 0, 0, 255)) > 0);
+        assert_eq!(count(&partial, colors::WHITE), 0);
+        let full = draw(text.char_count());
+        assert!(count(&full, colors::WHITE) > 0);
+        // The icon fills its 8x8 cell
+        assert_eq!(count(&full, colors::BLUE), 64);
+    }
+}
@@ -1 +1,731 @@
 //! Texture Loading and Drawing Functions
+
+use std::ffi::CString;
+use std::fs;
+use std::os::raw::{c_int, c_void};
+use std::path::Path;
+use std::process;
+use std::slice;
+
+use num_traits::FromPrimitive;
+
+use crate::core::{DrawHandle, Window};
+use crate::noise::Noise;
+use crate::{
+    raw, Color, Error, Image, PixelFormat, Rectangle, RenderTexture2D, Result, Texture2D,
+    TextureFilterMode, TextureWrapMode, Vector2,
+};
+
+//------------------------------------------------------------------------------
+// Image loading functions
+//------------------------------------------------------------------------------
+
+/// Load image from file into CPU memory (RAM)
+pub fn load_image<S: Into<String>>(file_name: S) -> Result<Image> {
+    let file_name = file_name.into();
+    let raw_file_name = CString::new(file_name.clone()).unwrap();
+    let raw_image = unsafe { raw::LoadImage(raw_file_name.as_ptr()) };
+    image_or_error(raw_image, file_name)
+}
+/// Load image from Color array data (RGBA - 32bit)
+pub fn load_image_ex(pixels: &[Color], width: i32, height: i32) -> Result<Image> {
+    check_image_size(width, height)?;
+    if pixels.len() < (width * height) as usize {
+        return Err(Error::InvalidData(format!(
+            "expected {} pixels, got {}",
+            width * height,
+            pixels.len()
+        )));
+    }
+    let mut raw_pixels: Vec<raw::Color> = pixels.iter().map(|c| c.into_raw()).collect();
+    let raw_image = unsafe { raw::LoadImageEx(raw_pixels.as_mut_ptr(), width, height) };
+    image_or_error(raw_image, String::from("<pixels>"))
+}
+/// Load image from raw data with parameters
+pub fn load_image_pro(data: &[u8], width: i32, height: i32, format: PixelFormat) -> Result<Image> {
+    check_image_size(width, height)?;
+    if data.len() < format.data_size(width, height) {
+        return Err(Error::InvalidData(format!(
+            "expected {} bytes of {:?} data, got {}",
+            format.data_size(width, height),
+            format,
+            data.len()
+        )));
+    }
+    let raw_image =
+        unsafe { raw::LoadImagePro(data.as_ptr() as *mut c_void, width, height, format as i32) };
+    let mut image = image_or_error(raw_image, String::from("<data>"))?;
+    // raylib copied one level of `data_size()` bytes, compressed or not
+    image.data_size = format.data_size(width, height);
+    Ok(image)
+}
+/// Load image from RAW file data
+pub fn load_image_raw<S: Into<String>>(
+    file_name: S,
+    width: i32,
+    height: i32,
+    format: PixelFormat,
+    header_size: i32,
+) -> Result<Image> {
+    let file_name = file_name.into();
+    let raw_file_name = CString::new(file_name.clone()).unwrap();
+    let raw_image = unsafe {
+        raw::LoadImageRaw(
+            raw_file_name.as_ptr(),
+            width,
+            height,
+            format as i32,
+            header_size,
+        )
+    };
+    image_or_error(raw_image, file_name)
+}
+/// Export image as a PNG file
+///
+/// NOTE: Written to a temporary file first, an existing file is only replaced on success
+pub fn export_image<S: Into<String>>(file_name: S, image: &Image) -> Result<()> {
+    let file_name = file_name.into();
+    if image.format().is_compressed() {
+        return Err(Error::UnsupportedFormat(image.format()));
+    }
+    let path = Path::new(&file_name);
+    let name = match path.file_name() {
+        Some(name) => name.to_string_lossy(),
+        None => return Err(Error::ExportFailed(file_name)),
+    };
+    // raylib doesn't report write failures, so check the written file instead
+    let temp = path.with_file_name(format!(".{}.{}.png", name, process::id()));
+    let raw_temp = match CString::new(temp.to_string_lossy().into_owned()) {
+        Ok(raw_temp) => raw_temp,
+        Err(_) => return Err(Error::ExportFailed(file_name)),
+    };
+    let _ = fs::remove_file(&temp);
+    unsafe { raw::ExportImage(raw_temp.as_ptr(), image.as_raw()) }
+    let written = fs::metadata(&temp).map_or(false, |metadata| metadata.len() > 0);
+    if !written || fs::rename(&temp, path).is_err() {
+        let _ = fs::remove_file(&temp);
+        return Err(Error::ExportFailed(file_name));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "decode")]
+impl Image {
+    /// Load image from file data in memory, `ext` is the file extension (`"png"`)
+    ///
+    /// NOTE: Decodes PNG, BMP, TGA, JPG, GIF and HDR data
+    pub fn from_memory(data: &[u8], ext: &str) -> Result<Image> {
+        use std::convert::TryFrom;
+        Image::try_from(crate::decode::decode_image(data, ext)?)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Image generation functions
+//------------------------------------------------------------------------------
+
+impl Image {
+    /// Generate image: plain color
+    pub fn gen_color(width: i32, height: i32, color: Color) -> Result<Image> {
+        check_image_size(width, height)?;
+        let raw_image = unsafe { raw::GenImageColor(width, height, color.into_raw()) };
+        image_or_error(raw_image, String::from("<color>"))
+    }
+    /// Generate image: vertical gradient
+    pub fn gen_gradient_v(width: i32, height: i32, top: Color, bottom: Color) -> Result<Image> {
+        check_image_size(width, height)?;
+        let raw_image =
+            unsafe { raw::GenImageGradientV(width, height, top.into_raw(), bottom.into_raw()) };
+        image_or_error(raw_image, String::from("<gradient>"))
+    }
+    /// Generate image: horizontal gradient
+    pub fn gen_gradient_h(width: i32, height: i32, left: Color, right: Color) -> Result<Image> {
+        check_image_size(width, height)?;
+        let raw_image =
+            unsafe { raw::GenImageGradientH(width, height, left.into_raw(), right.into_raw()) };
+        image_or_error(raw_image, String::from("<gradient>"))
+    }
+    /// Generate image: radial gradient
+    pub fn gen_gradient_radial(
+        width: i32,
+        height: i32,
+        density: f32,
+        inner: Color,
+        outer: Color,
+    ) -> Result<Image> {
+        check_image_size(width, height)?;
+        let raw_image = unsafe {
+            raw::GenImageGradientRadial(width, height, density, inner.into_raw(), outer.into_raw())
+        };
+        image_or_error(raw_image, String::from("<gradient>"))
+    }
+    /// Generate image: checked
+    pub fn gen_checked(
+        width: i32,
+        height: i32,
+        checks_x: i32,
+        checks_y: i32,
+        col1: Color,
+        col2: Color,
+    ) -> Result<Image> {
+        check_image_size(width, height)?;
+        check_image_size(checks_x, checks_y)?;
+        let raw_image = unsafe {
+            raw::GenImageChecked(
+                width,
+                height,
+                checks_x,
+                checks_y,
+                col1.into_raw(),
+                col2.into_raw(),
+            )
+        };
+        image_or_error(raw_image, String::from("<checked>"))
+    }
+    /// Generate image: white noise
+    ///
+    /// NOTE: Uses C `rand()`, see [`gen_noise`](#method.gen_noise) for reproducible noise
+    pub fn gen_white_noise(width: i32, height: i32, factor: f32) -> Result<Image> {
+        check_image_size(width, height)?;
+        let raw_image = unsafe { raw::GenImageWhiteNoise(width, height, factor) };
+        image_or_error(raw_image, String::from("<noise>"))
+    }
+    /// Generate image: perlin noise
+    pub fn gen_perlin_noise(
+        width: i32,
+        height: i32,
+        offset_x: i32,
+        offset_y: i32,
+        scale: f32,
+    ) -> Result<Image> {
+        check_image_size(width, height)?;
+        let raw_image =
+            unsafe { raw::GenImagePerlinNoise(width, height, offset_x, offset_y, scale) };
+        image_or_error(raw_image, String::from("<noise>"))
+    }
+    /// Generate image: cellular algorithm. Bigger tile size, bigger cells
+    ///
+    /// NOTE: Uses C `rand()`, see [`gen_noise`](#method.gen_noise) for reproducible noise
+    pub fn gen_cellular(width: i32, height: i32, tile_size: i32) -> Result<Image> {
+        check_image_size(width, height)?;
+        check_image_size(tile_size, tile_size)?;
+        let raw_image = unsafe { raw::GenImageCellular(width, height, tile_size) };
+        image_or_error(raw_image, String::from("<cellular>"))
+    }
+    /// Generate image: seeded grayscale noise, reproducible across platforms
+    pub fn gen_noise(width: i32, height: i32, noise: &Noise) -> Result<Image> {
+        check_image_size(width, height)?;
+        let mut pixels = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let value = (noise.sample(x as f32, y as f32) * 255.0).round() as u8;
+                pixels.push(Color::new(value, value, value, 255));
+            }
+        }
+        load_image_ex(&pixels, width, height)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Image manipulation functions
+//------------------------------------------------------------------------------
+
+impl Image {
+    /// Convert image data to desired format
+    pub fn set_format(&mut self, new_format: PixelFormat) -> Result<()> {
+        if new_format.is_compressed() {
+            return Err(Error::UnsupportedFormat(new_format));
+        }
+        self.modify(|raw_image| unsafe { raw::ImageFormat(raw_image, new_format as i32) })
+    }
+    /// Convert image to POT (power-of-two)
+    pub fn to_pot(&mut self, fill_color: Color) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageToPOT(raw_image, fill_color.into_raw()) })
+    }
+    /// Resize image (bilinear filtering)
+    pub fn resize(&mut self, new_width: i32, new_height: i32) -> Result<()> {
+        check_image_size(new_width, new_height)?;
+        self.modify(|raw_image| unsafe { raw::ImageResize(raw_image, new_width, new_height) })
+    }
+    /// Resize image (Nearest-Neighbor scaling algorithm)
+    pub fn resize_nn(&mut self, new_width: i32, new_height: i32) -> Result<()> {
+        check_image_size(new_width, new_height)?;
+        self.modify(|raw_image| unsafe { raw::ImageResizeNN(raw_image, new_width, new_height) })
+    }
+    /// Resize canvas and fill with color
+    ///
+    /// NOTE: The original image is placed at offset in the new canvas, parts
+    /// outside it are cropped. Mipmaps are discarded
+    pub fn resize_canvas(
+        &mut self,
+        new_width: i32,
+        new_height: i32,
+        offset_x: i32,
+        offset_y: i32,
+        color: Color,
+    ) -> Result<()> {
+        check_image_size(new_width, new_height)?;
+        if self.format.is_compressed() {
+            return Err(Error::UnsupportedFormat(self.format));
+        }
+        // raylib's ImageResizeCanvas only handles both sizes growing or both shrinking
+        let size = self.format.data_size(new_width, new_height);
+        let mut canvas = load_image_pro(&vec![0; size], new_width, new_height, self.format)?;
+        canvas.set_pixel(0, 0, color)?;
+        let bytes = self.format.data_size(1, 1);
+        let src = unsafe {
+            slice::from_raw_parts(
+                self.data as *const u8,
+                self.format.data_size(self.width, self.height),
+            )
+        };
+        let dst = unsafe { slice::from_raw_parts_mut(canvas.data as *mut u8, size) };
+        let fill = dst[..bytes].to_vec();
+        for pixel in dst.chunks_exact_mut(bytes) {
+            pixel.copy_from_slice(&fill);
+        }
+        let x_start = offset_x.max(0);
+        let x_end = offset_x.saturating_add(self.width).min(new_width);
+        let y_start = offset_y.max(0);
+        let y_end = offset_y.saturating_add(self.height).min(new_height);
+        if x_start < x_end {
+            let len = (x_end - x_start) as usize * bytes;
+            for y in y_start..y_end {
+                let src_start = ((y - offset_y) as usize * self.width as usize
+                    + (x_start - offset_x) as usize)
+                    * bytes;
+                let dst_start = (y as usize * new_width as usize + x_start as usize) * bytes;
+                dst[dst_start..dst_start + len].copy_from_slice(&src[src_start..src_start + len]);
+            }
+        }
+        *self = canvas;
+        Ok(())
+    }
+    /// Crop an image to a defined rectangle
+    pub fn crop(&mut self, crop: Rectangle) -> Result<()> {
+        let inside = crop.x >= 0.0
+            && crop.y >= 0.0
+            && crop.width >= 1.0
+            && crop.height >= 1.0
+            && crop.x + crop.width <= self.width as f32
+            && crop.y + crop.height <= self.height as f32;
+        if !inside {
+            return Err(Error::InvalidData(format!(
+                "crop rectangle {:?} out of bounds",
+                crop
+            )));
+        }
+        self.modify(|raw_image| unsafe { raw::ImageCrop(raw_image, crop.into_raw()) })
+    }
+    /// Flip image vertically
+    pub fn flip_vertical(&mut self) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageFlipVertical(raw_image) })
+    }
+    /// Flip image horizontally
+    pub fn flip_horizontal(&mut self) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageFlipHorizontal(raw_image) })
+    }
+    /// Rotate image clockwise 90deg
+    pub fn rotate_cw(&mut self) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageRotateCW(raw_image) })
+    }
+    /// Rotate image counter-clockwise 90deg
+    pub fn rotate_ccw(&mut self) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageRotateCCW(raw_image) })
+    }
+    /// Modify image color: tint
+    pub fn color_tint(&mut self, color: Color) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageColorTint(raw_image, color.into_raw()) })
+    }
+    /// Modify image color: invert
+    pub fn color_invert(&mut self) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageColorInvert(raw_image) })
+    }
+    /// Modify image color: grayscale
+    pub fn color_grayscale(&mut self) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageColorGrayscale(raw_image) })
+    }
+    /// Modify image color: contrast (-100 to 100)
+    pub fn color_contrast(&mut self, contrast: f32) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageColorContrast(raw_image, contrast) })
+    }
+    /// Modify image color: brightness (-255 to 255)
+    pub fn color_brightness(&mut self, brightness: i32) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageColorBrightness(raw_image, brightness) })
+    }
+    /// Modify image color: replace color
+    pub fn color_replace(&mut self, color: Color, replace: Color) -> Result<()> {
+        let (raw_color, raw_replace) = (color.into_raw(), replace.into_raw());
+        self.modify(|raw_image| unsafe {
+            raw::ImageColorReplace(raw_image, raw_color, raw_replace)
+        })
+    }
+    /// Dither image data to 16bpp or lower (Floyd-Steinberg dithering)
+    ///
+    /// NOTE: Only 5/6/5/0 (R5G6B5), 5/5/5/1 (R5G5B5A1) and 4/4/4/4 (R4G4B4A4)
+    /// bpps are supported
+    pub fn dither(&mut self, r_bpp: i32, g_bpp: i32, b_bpp: i32, a_bpp: i32) -> Result<()> {
+        let bpps = [r_bpp, g_bpp, b_bpp, a_bpp];
+        if ![[5, 6, 5, 0], [5, 5, 5, 1], [4, 4, 4, 4]].contains(&bpps) {
+            return Err(Error::InvalidData(format!(
+                "unsupported dithering bpps {:?}",
+                bpps
+            )));
+        }
+        self.modify(|raw_image| unsafe { raw::ImageDither(raw_image, r_bpp, g_bpp, b_bpp, a_bpp) })
+    }
+    /// Apply alpha mask to image
+    pub fn alpha_mask(&mut self, alpha_mask: &Image) -> Result<()> {
+        if alpha_mask.format.is_compressed() {
+            return Err(Error::UnsupportedFormat(alpha_mask.format));
+        }
+        if (alpha_mask.width, alpha_mask.height) != (self.width, self.height) {
+            return Err(Error::InvalidData(format!(
+                "alpha mask is {}x{}, image is {}x{}",
+                alpha_mask.width, alpha_mask.height, self.width, self.height
+            )));
+        }
+        let raw_mask = alpha_mask.as_raw();
+        self.modify(|raw_image| unsafe { raw::ImageAlphaMask(raw_image, raw_mask) })
+    }
+    /// Clear alpha channel to desired color
+    pub fn alpha_clear(&mut self, color: Color, threshold: f32) -> Result<()> {
+        let raw_color = color.into_raw();
+        self.modify(|raw_image| unsafe { raw::ImageAlphaClear(raw_image, raw_color, threshold) })
+    }
+    /// Crop image depending on alpha value
+    pub fn alpha_crop(&mut self, threshold: f32) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageAlphaCrop(raw_image, threshold) })
+    }
+    /// Premultiply alpha channel
+    pub fn alpha_premultiply(&mut self) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageAlphaPremultiply(raw_image) })
+    }
+    /// Generate all mipmap levels for a provided image
+    pub fn gen_mipmaps(&mut self) -> Result<()> {
+        self.modify(|raw_image| unsafe { raw::ImageMipmaps(raw_image) })
+    }
+
+    // Run a raylib image function in place, picking up the reallocated data
+    //
+    // NOTE: raylib only processes uncompressed data, compressed images are rejected.
+    // An image raylib leaves in an unknown format is left empty (0x0).
+    fn modify<F: FnOnce(*mut raw::Image)>(&mut self, f: F) -> Result<()> {
+        if self.format.is_compressed() {
+            return Err(Error::UnsupportedFormat(self.format));
+        }
+        let mut raw_image = self.as_raw();
+        f(&mut raw_image);
+        self.data = raw_image.data;
+        self.width = raw_image.width;
+        self.height = raw_image.height;
+        self.mipmaps = raw_image.mipmaps;
+        self.format = match PixelFormat::from_i32(raw_image.format) {
+            Some(format) => format,
+            None => {
+                self.width = 0;
+                self.height = 0;
+                self.mipmaps = 1;
+                self.format = PixelFormat::UncompressedR8g8b8a8;
+                self.data_size = 0;
+                return Err(Error::InvalidData(format!(
+                    "raylib returned unknown pixel format `{}`",
+                    raw_image.format
+                )));
+            }
+        };
+        self.data_size = Image::allocated_size(self.width, self.height, self.mipmaps, self.format);
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+// Texture loading functions
+//------------------------------------------------------------------------------
+
+impl Window {
+    /// Load texture from file into GPU memory (VRAM)
+    pub fn load_texture<S: Into<String>>(&self, file_name: S) -> Result<Texture2D<'_>> {
+        let file_name = file_name.into();
+        let raw_file_name = CString::new(file_name.clone()).unwrap();
+        let raw_texture = unsafe { raw::LoadTexture(raw_file_name.as_ptr()) };
+        texture_or_error(raw_texture, file_name)
+    }
+    /// Load texture from image data
+    pub fn load_texture_from_image(&self, image: &Image) -> Result<Texture2D<'_>> {
+        let raw_texture = unsafe { raw::LoadTextureFromImage(image.as_raw()) };
+        texture_or_error(raw_texture, String::from("<image>"))
+    }
+    /// Load texture for rendering (framebuffer)
+    pub fn load_render_texture(&self, width: i32, height: i32) -> Result<RenderTexture2D<'_>> {
+        check_image_size(width, height)?;
+        let raw_target = unsafe { raw::LoadRenderTexture(width, height) };
+        if raw_target.id == 0 {
+            Err(Error::LoadFailed(format!(
+                "<render texture {}x{}>",
+                width, height
+            )))
+        } else {
+            Ok(RenderTexture2D::from_raw(raw_target))
+        }
+    }
+}
+
+/// Get pixel data from GPU texture and return an Image
+pub fn get_texture_data(texture: &Texture2D) -> Result<Image> {
+    let raw_image = unsafe { raw::GetTextureData(texture.as_raw()) };
+    image_or_error(raw_image, format!("<texture {}>", texture.id))
+}
+
+impl<'w> RenderTexture2D<'w> {
+    /// Get pixel data from the color texture and return an Image
+    ///
+    /// NOTE: Render textures are stored bottom-up, the image is flipped right side up
+    pub fn to_image(&self) -> Result<Image> {
+        let mut image = get_texture_data(&self.texture)?;
+        image.flip_vertical()?;
+        Ok(image)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Texture configuration functions
+//------------------------------------------------------------------------------
+
+/// Update GPU texture with new data, laid out in the texture's pixel format
+pub fn update_texture(texture: &mut Texture2D, pixels: &[u8]) -> Result<()> {
+    let size = texture.format.data_size(texture.width, texture.height);
+    if pixels.len() < size {
+        return Err(Error::InvalidData(format!(
+            "expected {} bytes of {:?} data, got {}",
+            size,
+            texture.format,
+            pixels.len()
+        )));
+    }
+    unsafe { raw::UpdateTexture(texture.as_raw(), pixels.as_ptr() as *const c_void) }
+    Ok(())
+}
+/// Generate GPU mipmaps for a texture
+pub fn gen_texture_mipmaps(texture: &mut Texture2D) {
+    let mut raw_texture = texture.as_raw();
+    unsafe { raw::GenTextureMipmaps(&mut raw_texture) }
+    texture.mipmaps = raw_texture.mipmaps;
+}
+/// Set texture scaling filter mode
+pub fn set_texture_filter(texture: &Texture2D, filter_mode: TextureFilterMode) {
+    unsafe { raw::SetTextureFilter(texture.as_raw(), filter_mode as c_int) }
+}
+/// Set texture wrapping mode
+pub fn set_texture_wrap(texture: &Texture2D, wrap_mode: TextureWrapMode) {
+    unsafe { raw::SetTextureWrap(texture.as_raw(), wrap_mode as c_int) }
+}
+
+//------------------------------------------------------------------------------
+// Texture drawing functions
+//------------------------------------------------------------------------------
+
+impl DrawHandle<'_> {
+    /// Draw a Texture2D
+    pub fn draw_texture(&mut self, texture: &Texture2D, pos_x: i32, pos_y: i32, tint: Color) {
+        unsafe { raw::DrawTexture(texture.as_raw(), pos_x, pos_y, tint.into_raw()) }
+    }
+    /// Draw a Texture2D with position defined as Vector2
+    pub fn draw_texture_v(&mut self, texture: &Texture2D, position: Vector2, tint: Color) {
+        unsafe { raw::DrawTextureV(texture.as_raw(), position.into_raw(), tint.into_raw()) }
+    }
+    /// Draw a Texture2D with extended parameters
+    pub fn draw_texture_ex(
+        &mut self,
+        texture: &Texture2D,
+        position: Vector2,
+        rotation: f32,
+        scale: f32,
+        tint: Color,
+    ) {
+        unsafe {
+            raw::DrawTextureEx(
+                texture.as_raw(),
+                position.into_raw(),
+                rotation,
+                scale,
+                tint.into_raw(),
+            )
+        }
+    }
+    /// Draw a part of a texture defined by a rectangle
+    pub fn draw_texture_rec(
+        &mut self,
+        texture: &Texture2D,
+        source_rec: Rectangle,
+        position: Vector2,
+        tint: Color,
+    ) {
+        unsafe {
+            raw::DrawTextureRec(
+                texture.as_raw(),
+                source_rec.into_raw(),
+                position.into_raw(),
+                tint.into_raw(),
+            )
+        }
+    }
+    /// Draw a part of a texture defined by a rectangle with 'pro' parameters
+    pub fn draw_texture_pro(
+        &mut self,
+        texture: &Texture2D,
+        source_rec: Rectangle,
+        dest_rec: Rectangle,
+        origin: Vector2,
+        rotation: f32,
+        tint: Color,
+    ) {
+        unsafe {
+            raw::DrawTexturePro(
+                texture.as_raw(),
+                source_rec.into_raw(),
+                dest_rec.into_raw(),
+                origin.into_raw(),
+                rotation,
+                tint.into_raw(),
+            )
+        }
+    }
+}
+
+fn image_or_error(raw_image: raw::Image, source: String) -> Result<Image> {
+    if raw_image.data.is_null() {
+        Err(Error::LoadFailed(source))
+    } else {
+        Ok(Image::from_raw(raw_image))
+    }
+}
+
+// raylib signals texture load failure with a zero id
+fn texture_or_error<'w>(raw_texture: raw::Texture2D, source: String) -> Result<Texture2D<'w>> {
+    if raw_texture.id == 0 {
+        Err(Error::LoadFailed(source))
+    } else {
+        Ok(Texture2D::from_raw(raw_texture))
+    }
+}
+
+fn check_image_size(width: i32, height: i32) -> Result<()> {
+    // raylib sizes RGBA (32bit) copies of images with `int` arithmetic
+    let fits = width
+        .checked_mul(height)
+        .and_then(|pixels| pixels.checked_mul(4))
+        .is_some();
+    if width <= 0 || height <= 0 || !fits {
+        Err(Error::InvalidData(format!(
+            "invalid image size {}x{}",
+            width, height
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise::NoiseType;
+
+    #[test]
+    fn image_size_overflow() {
+        let pixels = [Color::new(0, 0, 0, 255); 4];
+        assert!(load_image_ex(&pixels, 65536, 65536).is_err());
+        assert!(load_image_ex(&pixels, i32::MAX, 2).is_err());
+        assert!(load_image_ex(&pixels, 2, 2).is_ok());
+    }
+
+    #[test]
+    fn resize_canvas_grows_and_crops() {
+        let (a, b) = (Color::new(1, 2, 3, 255), Color::new(4, 5, 6, 255));
+        let fill = Color::new(9, 9, 9, 255);
+        let mut image = load_image_ex(&[a, b, b, a], 2, 2).unwrap();
+        // Wider but shorter, raylib leaves this case unchanged
+        image.resize_canvas(4, 1, 1, -1, fill).unwrap();
+        assert_eq!((image.width(), image.height()), (4, 1));
+        assert_eq!(image.get_colors().unwrap(), [fill, b, a, fill]);
+
+        let data = [10, 20, 30, 40, 50, 60];
+        let mut image = load_image_pro(&data, 2, 1, PixelFormat::UncompressedR8g8b8).unwrap();
+        image.resize_canvas(2, 2, 0, 1, fill).unwrap();
+        assert_eq!(image.format(), PixelFormat::UncompressedR8g8b8);
+        assert_eq!(image.get_pixel(0, 0).unwrap(), fill);
+        assert_eq!(image.get_pixel(1, 1).unwrap(), Color::new(40, 50, 60, 255));
+        assert!(image.resize_canvas(0, 2, 0, 0, fill).is_err());
+    }
+
+    #[test]
+    fn clone_copies_pixel_data() {
+        let pixels = [Color::new(1, 2, 3, 4); 6];
+        let image = load_image_ex(&pixels, 3, 2).unwrap();
+        let copy = image.clone();
+        assert_ne!(image.data, copy.data);
+        let size = image.format().data_size(3, 2);
+        let (a, b) = unsafe {
+            (
+                slice::from_raw_parts(image.data as *const u8, size),
+                slice::from_raw_parts(copy.data as *const u8, size),
+            )
+        };
+        assert_eq!(a, b);
+        assert_eq!((copy.width(), copy.height()), (3, 2));
+    }
+
+    #[test]
+    fn gen_noise_is_reproducible() {
+        let noise = Noise::new(NoiseType::Simplex, 42).fbm(3, 2.0, 0.5);
+        let a = Image::gen_noise(16, 8, &noise).unwrap();
+        let b = Image::gen_noise(16, 8, &noise).unwrap();
+        assert_eq!((a.width(), a.height()), (16, 8));
+        assert_eq!(a.pixels().unwrap(), b.pixels().unwrap());
+        assert!(Image::gen_noise(0, 8, &noise).is_err());
+    }
+
+    #[test]
+    fn rejects_short_data() {
+        let data = [0u8; 5];
+        assert!(load_image_pro(&data, 2, 2, PixelFormat::UncompressedGrayscale).is_ok());
+        assert!(load_image_pro(&data, 2, 2, PixelFormat::UncompressedR8g8b8).is_err());
+        assert!(load_image_ex(&[], 0, 1).is_err());
+    }
+
+    #[test]
+    fn manipulation_updates_image() {
+        let pixels = [Color::new(1, 1, 1, 255), Color::new(2, 2, 2, 255)];
+        let mut image = load_image_ex(&pixels, 1, 2).unwrap();
+        image.flip_vertical().unwrap();
+        assert_eq!(image.pixels().unwrap(), &[pixels[1], pixels[0]]);
+    }
+
+    #[test]
+    fn unknown_raw_format_empties_image() {
+        let mut image = load_image_ex(&[Color::new(0, 0, 0, 255); 4], 2, 2).unwrap();
+        let result = image.modify(|raw_image| unsafe { (*raw_image).format = 0 });
+        assert!(result.is_err());
+        assert_eq!((image.width(), image.height()), (0, 0));
+        assert!(image.get_colors().unwrap().is_empty());
+    }
+
+    #[test]
+    fn manipulation_rejects_invalid_input() {
+        let mut image = load_image_ex(&[Color::new(0, 0, 0, 255); 4], 2, 2).unwrap();
+        assert!(image.crop(Rectangle::new(1.0, 1.0, 2.0, 1.0)).is_err());
+        assert!(image.resize(0, 4).is_err());
+        assert!(image.dither(8, 8, 8, 8).is_err());
+        assert!(image.dither(1, 1, 1, 1).is_err());
+        assert!(image.dither(5, 5, 5, 0).is_err());
+        assert!(image.set_format(PixelFormat::CompressedDxt5Rgba).is_err());
+        let mask = load_image_ex(&[Color::new(0, 0, 0, 255); 1], 1, 1).unwrap();
+        assert!(image.alpha_mask(&mask).is_err());
+
+        let mut compressed = load_image_pro(&[0; 8], 4, 4, PixelFormat::CompressedDxt1Rgb).unwrap();
+        assert_eq!(
+            compressed.flip_vertical(),
+            Err(Error::UnsupportedFormat(PixelFormat::CompressedDxt1Rgb))
+        );
+    }
+}
@@ -0,0 +1,482 @@
+//! Tweening and Animation Timeline Functions
+//!
+//! A [`Tween`](struct.Tween.html) interpolates a value over time with an
+//! [`Easing`](../easing/enum.Easing.html) curve. Tweens, [`Sequence`](struct.Sequence.html)s
+//! and [`Parallel`](struct.Parallel.html) groups all implement
+//! [`Animation`](trait.Animation.html), so they can be nested to build timelines.
+//! Everything is advanced either by an explicit delta time or by
+//! [`get_frame_time`](../core/fn.get_frame_time.html).
+
+use std::fmt;
+
+use crate::core::get_frame_time;
+use crate::easing::Easing;
+use crate::{Color, Rectangle, Vector2, Vector3};
+
+//------------------------------------------------------------------------------
+// Interpolation
+//------------------------------------------------------------------------------
+
+/// Values that can be interpolated by a [`Tween`](struct.Tween.html)
+pub trait Tweenable: Copy {
+    /// Interpolate between `self` and `to`, `amount` may be outside of [`0.0f32..1.0f32`]
+    fn tween(self, to: Self, amount: f32) -> Self;
+}
+impl Tweenable for f32 {
+    fn tween(self, to: f32, amount: f32) -> f32 {
+        self + (to - self) * amount
+    }
+}
+impl Tweenable for Vector2 {
+    fn tween(self, to: Vector2, amount: f32) -> Vector2 {
+        self.lerp(to, amount)
+    }
+}
+impl Tweenable for Vector3 {
+    fn tween(self, to: Vector3, amount: f32) -> Vector3 {
+        self.lerp(to, amount)
+    }
+}
+impl Tweenable for Color {
+    fn tween(self, to: Color, amount: f32) -> Color {
+        let channel = |from: u8, to: u8| {
+            f32::from(from)
+                .tween(f32::from(to), amount)
+                .round()
+                .clamp(0.0, 255.0) as u8
+        };
+        Color::new(
+            channel(self.r, to.r),
+            channel(self.g, to.g),
+            channel(self.b, to.b),
+            channel(self.a, to.a),
+        )
+    }
+}
+impl Tweenable for Rectangle {
+    fn tween(self, to: Rectangle, amount: f32) -> Rectangle {
+        Rectangle::new(
+            self.x.tween(to.x, amount),
+            self.y.tween(to.y, amount),
+            self.width.tween(to.width, amount),
+            self.height.tween(to.height, amount),
+        )
+    }
+}
+
+//------------------------------------------------------------------------------
+// Animation trait
+//------------------------------------------------------------------------------
+
+/// Anything that can be advanced over time and composed into timelines
+pub trait Animation {
+    /// Advance by `dt` seconds, returns the part of `dt` left over after finishing
+    fn advance(&mut self, dt: f32) -> f32;
+    /// Check if the animation has run to completion
+    fn is_finished(&self) -> bool;
+    /// Rewind the animation to its start
+    fn reset(&mut self);
+    /// Advance by the time of the last frame drawn
+    fn advance_frame(&mut self) {
+        self.advance(get_frame_time());
+    }
+}
+
+/// Tween repetition modes
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Repeat {
+    /// Play the tween this many more times after the first
+    Times(u32),
+    /// Never stop playing
+    Forever,
+}
+
+//------------------------------------------------------------------------------
+// Tween
+//------------------------------------------------------------------------------
+
+/// Interpolates a value between two end points over time
+pub struct Tween<T: Tweenable> {
+    from: T,
+    to: T,
+    duration: f32,
+    easing: Easing,
+    delay: f32,
+    repeat: Repeat,
+    yoyo: bool,
+    elapsed: f32,
+    on_update: Option<Box<dyn FnMut(T)>>,
+}
+impl<T: Tweenable> Tween<T> {
+    /// Create a linear tween from `from` to `to` lasting `duration` seconds
+    pub fn new(from: T, to: T, duration: f32) -> Tween<T> {
+        Tween {
+            from: from,
+            to: to,
+            duration: duration,
+            easing: Easing::Linear,
+            delay: 0.0,
+            repeat: Repeat::Times(0),
+            yoyo: false,
+            elapsed: 0.0,
+            on_update: None,
+        }
+    }
+    /// Set the easing curve
+    pub fn easing(mut self, easing: Easing) -> Tween<T> {
+        self.easing = easing;
+        self
+    }
+    /// Wait `delay` seconds before starting, only applied once
+    pub fn delay(mut self, delay: f32) -> Tween<T> {
+        self.delay = delay;
+        self
+    }
+    /// Set how many times the tween repeats
+    pub fn repeat(mut self, repeat: Repeat) -> Tween<T> {
+        self.repeat = repeat;
+        self
+    }
+    /// Play every other repetition backwards
+    pub fn yoyo(mut self, yoyo: bool) -> Tween<T> {
+        self.yoyo = yoyo;
+        self
+    }
+    /// Call `callback` with the new value every time the tween is advanced
+    ///
+    /// NOTE: This is how values are read back out of tweens nested in timelines
+    pub fn on_update<F: FnMut(T) + 'static>(mut self, callback: F) -> Tween<T> {
+        self.on_update = Some(Box::new(callback));
+        self
+    }
+    /// Advance by `dt` seconds and return the current value
+    pub fn update(&mut self, dt: f32) -> T {
+        self.advance(dt);
+        self.value()
+    }
+    /// Advance by the time of the last frame drawn and return the current value
+    pub fn update_frame(&mut self) -> T {
+        self.update(get_frame_time())
+    }
+    /// Get the current value
+    pub fn value(&self) -> T {
+        self.from.tween(self.to, self.easing.apply(self.progress()))
+    }
+    /// Get the time elapsed since the tween was started or reset, including the delay
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+    /// Get the total running time including the delay, `None` if repeating forever
+    pub fn total_duration(&self) -> Option<f32> {
+        match self.repeat {
+            Repeat::Times(times) => Some(self.delay + self.duration * (times as f32 + 1.0)),
+            Repeat::Forever => None,
+        }
+    }
+    /// Normalized time within the current repetition, before easing
+    fn progress(&self) -> f32 {
+        let active = self.elapsed - self.delay;
+        if active <= 0.0 {
+            return 0.0;
+        }
+        if self.duration <= 0.0 || self.is_finished() {
+            return match self.repeat {
+                Repeat::Times(times) if self.yoyo && times % 2 == 1 => 0.0,
+                _ => 1.0,
+            };
+        }
+        let cycle = (active / self.duration).floor();
+        let t = active / self.duration - cycle;
+        if self.yoyo && cycle as u64 % 2 == 1 {
+            1.0 - t
+        } else {
+            t
+        }
+    }
+}
+impl<T: Tweenable> Animation for Tween<T> {
+    fn advance(&mut self, dt: f32) -> f32 {
+        let was_finished = self.is_finished();
+        self.elapsed += dt;
+        let mut leftover = 0.0;
+        if let Some(total) = self.total_duration() {
+            if self.elapsed >= total {
+                leftover = self.elapsed - total;
+                self.elapsed = total;
+            }
+        }
+        if !was_finished {
+            let value = self.value();
+            if let Some(callback) = self.on_update.as_mut() {
+                callback(value);
+            }
+        }
+        leftover
+    }
+    fn is_finished(&self) -> bool {
+        match self.total_duration() {
+            Some(total) => self.elapsed >= total,
+            None => false,
+        }
+    }
+    fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+impl<T: Tweenable + fmt::Debug> fmt::Debug for Tween<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tween")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("duration", &self.duration)
+            .field("easing", &self.easing)
+            .field("delay", &self.delay)
+            .field("repeat", &self.repeat)
+            .field("yoyo", &self.yoyo)
+            .field("elapsed", &self.elapsed)
+            .finish()
+    }
+}
+
+//------------------------------------------------------------------------------
+// Timelines
+//------------------------------------------------------------------------------
+
+/// Waits for a fixed time, useful as a gap inside a [`Sequence`](struct.Sequence.html)
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Delay {
+    duration: f32,
+    elapsed: f32,
+}
+impl Delay {
+    pub fn new(duration: f32) -> Delay {
+        Delay {
+            duration: duration,
+            elapsed: 0.0,
+        }
+    }
+}
+impl Animation for Delay {
+    fn advance(&mut self, dt: f32) -> f32 {
+        self.elapsed += dt;
+        let leftover = (self.elapsed - self.duration).max(0.0);
+        self.elapsed = self.elapsed.min(self.duration);
+        leftover
+    }
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+    fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+
+/// Plays animations one after another
+#[derive(Default)]
+pub struct Sequence {
+    animations: Vec<Box<dyn Animation>>,
+    current: usize,
+}
+impl Sequence {
+    pub fn new() -> Sequence {
+        Sequence::default()
+    }
+    /// Append an animation to the end of the sequence
+    pub fn then<A: Animation + 'static>(mut self, animation: A) -> Sequence {
+        self.animations.push(Box::new(animation));
+        self
+    }
+    /// Append a pause of `duration` seconds
+    pub fn wait(self, duration: f32) -> Sequence {
+        self.then(Delay::new(duration))
+    }
+}
+impl Animation for Sequence {
+    fn advance(&mut self, dt: f32) -> f32 {
+        let mut dt = dt;
+        while let Some(animation) = self.animations.get_mut(self.current) {
+            dt = animation.advance(dt);
+            if !animation.is_finished() {
+                return 0.0;
+            }
+            self.current += 1;
+        }
+        dt
+    }
+    fn is_finished(&self) -> bool {
+        self.current >= self.animations.len()
+    }
+    fn reset(&mut self) {
+        self.current = 0;
+        for animation in self.animations.iter_mut() {
+            animation.reset();
+        }
+    }
+}
+impl fmt::Debug for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sequence")
+            .field("len", &self.animations.len())
+            .field("current", &self.current)
+            .finish()
+    }
+}
+
+/// Plays animations at the same time, finishing when all of them have
+#[derive(Default)]
+pub struct Parallel {
+    animations: Vec<Box<dyn Animation>>,
+}
+impl Parallel {
+    pub fn new() -> Parallel {
+        Parallel::default()
+    }
+    /// Add an animation to the group
+    pub fn with<A: Animation + 'static>(mut self, animation: A) -> Parallel {
+        self.animations.push(Box::new(animation));
+        self
+    }
+}
+impl Animation for Parallel {
+    fn advance(&mut self, dt: f32) -> f32 {
+        let leftover = self
+            .animations
+            .iter_mut()
+            .map(|animation| animation.advance(dt))
+            .fold(dt, f32::min);
+        if self.is_finished() {
+            leftover
+        } else {
+            0.0
+        }
+    }
+    fn is_finished(&self) -> bool {
+        self.animations
+            .iter()
+            .all(|animation| animation.is_finished())
+    }
+    fn reset(&mut self) {
+        for animation in self.animations.iter_mut() {
+            animation.reset();
+        }
+    }
+}
+impl fmt::Debug for Parallel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Parallel")
+            .field("len", &self.animations.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn tween_interpolates_with_easing() {
+        let mut tween = Tween::new(0.0, 10.0, 2.0);
+        assert_eq!(tween.update(0.5), 2.5);
+        assert_eq!(tween.update(1.5), 10.0);
+        assert!(tween.is_finished());
+
+        let mut eased = Tween::new(0.0, 10.0, 2.0).easing(Easing::QuadIn);
+        assert_eq!(eased.update(1.0), 2.5);
+    }
+
+    #[test]
+    fn tween_delay_and_leftover() {
+        let mut tween = Tween::new(0.0, 1.0, 1.0).delay(0.5);
+        assert_eq!(tween.update(0.5), 0.0);
+        assert_eq!(tween.update(0.5), 0.5);
+        assert_eq!(tween.advance(1.0), 0.5);
+        assert_eq!(tween.value(), 1.0);
+    }
+
+    #[test]
+    fn tween_repeat_and_yoyo() {
+        let mut tween = Tween::new(0.0, 1.0, 1.0)
+            .repeat(Repeat::Times(2))
+            .yoyo(true);
+        assert_eq!(tween.total_duration(), Some(3.0));
+        assert_eq!(tween.update(0.25), 0.25);
+        assert_eq!(tween.update(1.0), 0.75);
+        assert_eq!(tween.update(1.0), 0.25);
+        assert_eq!(tween.update(1.0), 1.0);
+        assert!(tween.is_finished());
+
+        let mut back = Tween::new(0.0, 1.0, 1.0)
+            .repeat(Repeat::Times(1))
+            .yoyo(true);
+        back.advance(5.0);
+        assert_eq!(back.value(), 0.0);
+
+        let mut forever = Tween::new(0.0, 1.0, 1.0).repeat(Repeat::Forever);
+        assert_eq!(forever.update(10.5), 0.5);
+        assert!(!forever.is_finished());
+    }
+
+    #[test]
+    fn tween_types() {
+        let mut color = Tween::new(Color::new(0, 0, 0, 255), Color::new(255, 100, 0, 255), 1.0)
+            .easing(Easing::BackOut);
+        color.advance(0.5);
+        assert_eq!(color.value().r, 255);
+
+        let mut rec = Tween::new(
+            Rectangle::new(0.0, 0.0, 10.0, 10.0),
+            Rectangle::new(10.0, 20.0, 30.0, 40.0),
+            1.0,
+        );
+        assert_eq!(rec.update(0.5), Rectangle::new(5.0, 10.0, 20.0, 25.0));
+
+        let mut v = Tween::new(Vector2::zero(), Vector2::new(2.0, 4.0), 2.0);
+        assert_eq!(v.update(1.0), Vector2::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn sequence_carries_leftover_time() {
+        let x = Rc::new(Cell::new(0.0));
+        let y = Rc::new(Cell::new(0.0));
+        let (x2, y2) = (x.clone(), y.clone());
+        let mut sequence = Sequence::new()
+            .then(Tween::new(0.0, 1.0, 1.0).on_update(move |v| x2.set(v)))
+            .wait(1.0)
+            .then(Tween::new(0.0, 1.0, 1.0).on_update(move |v| y2.set(v)));
+
+        sequence.advance(1.5);
+        assert_eq!((x.get(), y.get()), (1.0, 0.0));
+        sequence.advance(1.0);
+        assert_eq!((x.get(), y.get()), (1.0, 0.5));
+        assert_eq!(sequence.advance(1.0), 0.5);
+        assert!(sequence.is_finished());
+
+        sequence.reset();
+        sequence.advance(0.25);
+        assert_eq!(x.get(), 0.25);
+    }
+
+    #[test]
+    fn parallel_finishes_with_longest() {
+        let a = Rc::new(Cell::new(0.0));
+        let a2 = a.clone();
+        let mut group = Parallel::new()
+            .with(Tween::new(0.0, 1.0, 1.0).on_update(move |v| a2.set(v)))
+            .with(Delay::new(2.0));
+        assert_eq!(group.advance(1.5), 0.0);
+        assert_eq!(a.get(), 1.0);
+        assert!(!group.is_finished());
+        assert_eq!(group.advance(1.0), 0.5);
+        assert!(group.is_finished());
+
+        let mut nested = Sequence::new().then(group).wait(1.0);
+        nested.reset();
+        assert_eq!(nested.advance(3.0), 0.0);
+        assert!(nested.is_finished());
+    }
+}